@@ -1,8 +1,8 @@
 //simple Huffman algorithm for compressing text
+use crate::data_structures::binary_heap::MoHeap;
 use crate::data_structures::tree::TreeNode;
 use std::cmp::Ordering;
 
-use std::collections::BinaryHeap;
 use std::{cell::RefCell, collections::HashMap, rc::Rc};
 
 pub fn count_frequencies(text: &str) -> HashMap<char, usize> {
@@ -22,7 +22,8 @@ pub fn build_huffman_tree(text: &str) -> Option<Rc<RefCell<TreeNode>>> {
 
     //Create the leafs and add to tree
 
-    let mut heap = BinaryHeap::new();
+    // min heap, so the least frequent nodes get combined first
+    let mut heap = MoHeap::new_min();
     for (ch, freq) in freq_map {
         let leaf = TreeNode::new_leaf(ch, freq);
         heap.push(HeapNode { node: leaf });
@@ -162,13 +163,11 @@ pub struct HeapNode {
 }
 
 impl Ord for HeapNode {
-    // Reverse ordering: lower frequencies have higher priority
     fn cmp(&self, other: &Self) -> Ordering {
-        other
-            .node
+        self.node
             .borrow()
             .frequency
-            .cmp(&self.node.borrow().frequency)
+            .cmp(&other.node.borrow().frequency)
     }
 }
 
@@ -413,7 +412,7 @@ mod tests {
         let node2 = TreeNode::new_leaf('b', 5);
         let node3 = TreeNode::new_leaf('c', 3);
 
-        let mut heap = BinaryHeap::new();
+        let mut heap = MoHeap::new_min();
         heap.push(HeapNode {
             node: node2.clone(),
         });
@@ -424,7 +423,7 @@ mod tests {
             node: node3.clone(),
         });
 
-        // Should pop in order: 1, 3, 5 (smallest first due to reverse ordering)
+        // Should pop in order: 1, 3, 5 (smallest first from the min heap)
         assert_eq!(heap.pop().unwrap().node.borrow().frequency, 1);
         assert_eq!(heap.pop().unwrap().node.borrow().frequency, 3);
         assert_eq!(heap.pop().unwrap().node.borrow().frequency, 5);
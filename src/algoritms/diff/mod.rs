@@ -0,0 +1,267 @@
+// Diffs between two sequences of tokens. An algorithm lines the sequences up as runs of
+// `DiffOp`s: tokens kept, deleted from the old one or inserted from the new one.
+// - Myers: a shortest edit script, O((n + m) * d) for d differences, in linear space
+// - patience: lines that occur exactly once on both sides are matched first and the
+//   diff recurses between them. Not always minimal, but it doesn't line up unrelated
+//   blank lines and braces, so the hunks read better.
+//
+// `TextDiff` tokenizes text into lines or words and prints the result as a unified diff.
+// `edit_script` turns the runs into a patch that rebuilds the new sequence from the old.
+
+pub mod myers;
+pub mod patience;
+pub mod text;
+
+use std::hash::Hash;
+use std::ops::Range;
+
+pub use myers::myers_diff;
+pub use patience::patience_diff;
+pub use text::{Granularity, TextDiff};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Algorithm {
+    Myers,
+    Patience,
+}
+
+impl Algorithm {
+    pub const ALL: [Algorithm; 2] = [Algorithm::Myers, Algorithm::Patience];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Algorithm::Myers => "myers",
+            Algorithm::Patience => "patience",
+        }
+    }
+
+    pub fn diff<T: Eq + Hash>(&self, old: &[T], new: &[T]) -> Vec<DiffOp> {
+        match self {
+            Algorithm::Myers => myers_diff(old, new),
+            Algorithm::Patience => patience_diff(old, new),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tag {
+    Equal,
+    Delete,
+    Insert,
+}
+
+/// A run of one kind of edit. `Equal` runs have ranges of the same length, a `Delete`
+/// has an empty `new` range at the position it applies to, an `Insert` an empty `old`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffOp {
+    pub tag: Tag,
+    pub old: Range<usize>,
+    pub new: Range<usize>,
+}
+
+/// Appends a run, merging it into the last one when it continues it. Deletions go before
+/// insertions at the same place, so a replaced run always reads `-` then `+`.
+pub(crate) fn push_op(ops: &mut Vec<DiffOp>, tag: Tag, old: Range<usize>, new: Range<usize>) {
+    if old.is_empty() && new.is_empty() {
+        return;
+    }
+    if tag == Tag::Delete
+        && let Some(insert) = ops.pop_if(|last| last.tag == Tag::Insert)
+    {
+        push_op(
+            ops,
+            Tag::Delete,
+            old.clone(),
+            insert.new.start..insert.new.start,
+        );
+        push_op(ops, Tag::Insert, old.end..old.end, insert.new);
+        return;
+    }
+    if let Some(last) = ops.last_mut()
+        && last.tag == tag
+        && last.old.end == old.start
+        && last.new.end == new.start
+    {
+        last.old.end = old.end;
+        last.new.end = new.end;
+        return;
+    }
+    ops.push(DiffOp { tag, old, new });
+}
+
+/// One step of a patch applied to the old sequence from the start
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Edit<T> {
+    /// copy this many tokens
+    Keep(usize),
+    /// skip this many tokens
+    Delete(usize),
+    /// add these tokens
+    Insert(Vec<T>),
+}
+
+/// The patch that `ops` describe, it needs only the old sequence to be applied
+pub fn edit_script<T: Clone>(ops: &[DiffOp], new: &[T]) -> Vec<Edit<T>> {
+    ops.iter()
+        .map(|op| match op.tag {
+            Tag::Equal => Edit::Keep(op.old.len()),
+            Tag::Delete => Edit::Delete(op.old.len()),
+            Tag::Insert => Edit::Insert(new[op.new.clone()].to_vec()),
+        })
+        .collect()
+}
+
+/// Applies a patch from [`edit_script`]. Panics if it runs past the end of `old`.
+pub fn apply<T: Clone>(old: &[T], script: &[Edit<T>]) -> Vec<T> {
+    let mut result = Vec::with_capacity(old.len());
+    let mut position = 0;
+    for edit in script {
+        match edit {
+            Edit::Keep(count) => {
+                result.extend_from_slice(&old[position..position + count]);
+                position += count;
+            }
+            Edit::Delete(count) => position += count,
+            Edit::Insert(tokens) => result.extend_from_slice(tokens),
+        }
+    }
+    result.extend_from_slice(&old[position..]);
+    result
+}
+
+fn common_prefix<T: Eq>(a: &[T], b: &[T]) -> usize {
+    a.iter().zip(b).take_while(|(x, y)| x == y).count()
+}
+
+fn common_suffix<T: Eq>(a: &[T], b: &[T]) -> usize {
+    a.iter()
+        .rev()
+        .zip(b.iter().rev())
+        .take_while(|(x, y)| x == y)
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algoritms::sorting::quick_sort::XorShift64;
+
+    fn random_tokens(len: usize, alphabet: usize, rng: &mut XorShift64) -> Vec<u8> {
+        (0..len)
+            .map(|_| b'a' + rng.next_below(alphabet) as u8)
+            .collect()
+    }
+
+    /// The runs cover both sequences in order, equal runs hold equal tokens
+    fn check_ops<T: Eq + std::fmt::Debug>(old: &[T], new: &[T], ops: &[DiffOp]) {
+        let (mut x, mut y) = (0, 0);
+        for op in ops {
+            assert_eq!((op.old.start, op.new.start), (x, y), "{ops:?}");
+            match op.tag {
+                Tag::Equal => assert_eq!(old[op.old.clone()], new[op.new.clone()]),
+                Tag::Delete => assert!(op.new.is_empty() && !op.old.is_empty()),
+                Tag::Insert => assert!(op.old.is_empty() && !op.new.is_empty()),
+            }
+            (x, y) = (op.old.end, op.new.end);
+        }
+        assert_eq!((x, y), (old.len(), new.len()));
+        // runs are merged
+        for pair in ops.windows(2) {
+            assert_ne!(pair[0].tag, pair[1].tag);
+        }
+    }
+
+    fn edit_count(ops: &[DiffOp]) -> usize {
+        ops.iter()
+            .filter(|op| op.tag != Tag::Equal)
+            .map(|op| op.old.len() + op.new.len())
+            .sum()
+    }
+
+    #[test]
+    fn test_valid_and_patch_round_trip() {
+        let mut rng = XorShift64::new(44);
+        for _ in 0..500 {
+            let old = random_tokens(rng.next_below(30), 3, &mut rng);
+            let new = random_tokens(rng.next_below(30), 3, &mut rng);
+            for algorithm in Algorithm::ALL {
+                let ops = algorithm.diff(&old, &new);
+                check_ops(&old, &new, &ops);
+                assert_eq!(apply(&old, &edit_script(&ops, &new)), new);
+            }
+        }
+    }
+
+    #[test]
+    fn test_myers_is_minimal() {
+        let mut rng = XorShift64::new(45);
+        for _ in 0..300 {
+            let old = random_tokens(rng.next_below(20), 4, &mut rng);
+            let new = random_tokens(rng.next_below(20), 4, &mut rng);
+            let ops = myers_diff(&old, &new);
+            // with insertions and deletions only, the fewest edits are |a| + |b| - 2 * lcs
+            let mut lcs = vec![vec![0; new.len() + 1]; old.len() + 1];
+            for i in 0..old.len() {
+                for j in 0..new.len() {
+                    lcs[i + 1][j + 1] = if old[i] == new[j] {
+                        lcs[i][j] + 1
+                    } else {
+                        lcs[i][j + 1].max(lcs[i + 1][j])
+                    };
+                }
+            }
+            let expected = old.len() + new.len() - 2 * lcs[old.len()][new.len()];
+            assert_eq!(edit_count(&ops), expected, "{old:?} {new:?}");
+        }
+    }
+
+    #[test]
+    fn test_edge_cases() {
+        for algorithm in Algorithm::ALL {
+            assert!(algorithm.diff::<u8>(&[], &[]).is_empty());
+            assert_eq!(
+                algorithm.diff(b"abc", b"abc"),
+                [DiffOp {
+                    tag: Tag::Equal,
+                    old: 0..3,
+                    new: 0..3
+                }]
+            );
+            assert_eq!(
+                algorithm.diff(b"", b"ab"),
+                [DiffOp {
+                    tag: Tag::Insert,
+                    old: 0..0,
+                    new: 0..2
+                }]
+            );
+            assert_eq!(
+                algorithm.diff(b"ab", b""),
+                [DiffOp {
+                    tag: Tag::Delete,
+                    old: 0..2,
+                    new: 0..0
+                }]
+            );
+        }
+    }
+
+    #[test]
+    fn test_edit_script() {
+        let old = ["a", "b", "c", "d"];
+        let new = ["a", "x", "c", "d", "e"];
+        let ops = myers_diff(&old, &new);
+        let script = edit_script(&ops, &new);
+        assert_eq!(
+            script,
+            [
+                Edit::Keep(1),
+                Edit::Delete(1),
+                Edit::Insert(vec!["x"]),
+                Edit::Keep(2),
+                Edit::Insert(vec!["e"]),
+            ]
+        );
+        assert_eq!(apply(&old, &script), new);
+    }
+}
@@ -0,0 +1,232 @@
+// Myers' O((n + m) * d) diff in linear space. The greedy search walks diagonal k
+// (x - y = k) as far as equal tokens allow, for d = 0, 1, 2, ... edits, from the start
+// and from the end at the same time. Where the two searches meet is the middle snake, a
+// run of equal tokens on some shortest path, and the diff recurses on both sides of it,
+// so only the furthest x per diagonal is stored instead of a table per d.
+
+use std::ops::{Index, IndexMut, Range};
+
+use super::{DiffOp, Tag, common_prefix, common_suffix, push_op};
+
+/// Shortest edit script from `old` to `new`
+pub fn myers_diff<T: Eq>(old: &[T], new: &[T]) -> Vec<DiffOp> {
+    let mut ops = Vec::new();
+    diff_into(old, 0..old.len(), new, 0..new.len(), &mut ops);
+    ops
+}
+
+/// Diffs `old[old_range]` against `new[new_range]`, appending to `ops`
+pub(crate) fn diff_into<T: Eq>(
+    old: &[T],
+    old_range: Range<usize>,
+    new: &[T],
+    new_range: Range<usize>,
+    ops: &mut Vec<DiffOp>,
+) {
+    let max_d = (old_range.len() + new_range.len()).div_ceil(2) + 1;
+    let mut forward = Diagonals::new(max_d);
+    let mut backward = Diagonals::new(max_d);
+    conquer(
+        old,
+        old_range,
+        new,
+        new_range,
+        &mut forward,
+        &mut backward,
+        ops,
+    );
+}
+
+/// Furthest x reached on each diagonal, diagonals run from `-max_d` to `max_d`
+struct Diagonals {
+    x: Vec<usize>,
+    offset: isize,
+}
+
+impl Diagonals {
+    fn new(max_d: usize) -> Self {
+        Self {
+            x: vec![0; 2 * max_d + 2],
+            offset: max_d as isize,
+        }
+    }
+}
+
+impl Index<isize> for Diagonals {
+    type Output = usize;
+
+    fn index(&self, k: isize) -> &usize {
+        &self.x[(k + self.offset) as usize]
+    }
+}
+
+impl IndexMut<isize> for Diagonals {
+    fn index_mut(&mut self, k: isize) -> &mut usize {
+        &mut self.x[(k + self.offset) as usize]
+    }
+}
+
+fn conquer<T: Eq>(
+    old: &[T],
+    mut old_range: Range<usize>,
+    new: &[T],
+    mut new_range: Range<usize>,
+    forward: &mut Diagonals,
+    backward: &mut Diagonals,
+    ops: &mut Vec<DiffOp>,
+) {
+    let prefix = common_prefix(&old[old_range.clone()], &new[new_range.clone()]);
+    push_op(
+        ops,
+        Tag::Equal,
+        old_range.start..old_range.start + prefix,
+        new_range.start..new_range.start + prefix,
+    );
+    old_range.start += prefix;
+    new_range.start += prefix;
+
+    let suffix = common_suffix(&old[old_range.clone()], &new[new_range.clone()]);
+    let old_suffix = old_range.end - suffix..old_range.end;
+    let new_suffix = new_range.end - suffix..new_range.end;
+    old_range.end -= suffix;
+    new_range.end -= suffix;
+
+    if old_range.is_empty() || new_range.is_empty() {
+        push_op(
+            ops,
+            Tag::Delete,
+            old_range.clone(),
+            new_range.start..new_range.start,
+        );
+        push_op(ops, Tag::Insert, old_range.end..old_range.end, new_range);
+    } else {
+        let (x, y) = middle_snake(
+            old,
+            old_range.clone(),
+            new,
+            new_range.clone(),
+            forward,
+            backward,
+        );
+        conquer(
+            old,
+            old_range.start..x,
+            new,
+            new_range.start..y,
+            forward,
+            backward,
+            ops,
+        );
+        conquer(
+            old,
+            x..old_range.end,
+            new,
+            y..new_range.end,
+            forward,
+            backward,
+            ops,
+        );
+    }
+
+    push_op(ops, Tag::Equal, old_suffix, new_suffix);
+}
+
+/// A point on a shortest path that splits it roughly in half. Both ranges are non-empty
+/// and their first and last tokens differ, so the point is never a corner.
+fn middle_snake<T: Eq>(
+    old: &[T],
+    old_range: Range<usize>,
+    new: &[T],
+    new_range: Range<usize>,
+    forward: &mut Diagonals,
+    backward: &mut Diagonals,
+) -> (usize, usize) {
+    let (n, m) = (old_range.len(), new_range.len());
+    let old = &old[old_range.clone()];
+    let new = &new[new_range.clone()];
+    // the diagonal the end (n, m) is on; the searches meet on a forward diagonal k and
+    // the backward diagonal delta - k
+    let delta = n as isize - m as isize;
+    let odd = delta % 2 != 0;
+    forward[1] = 0;
+    backward[1] = 0;
+
+    for d in 0..=(n + m).div_ceil(2) as isize {
+        for k in (-d..=d).rev().step_by(2) {
+            // step down from diagonal k + 1 (an insertion) or right from k - 1 (a deletion)
+            let mut x = if k == -d || (k != d && forward[k - 1] < forward[k + 1]) {
+                forward[k + 1]
+            } else {
+                forward[k - 1] + 1
+            };
+            let y = (x as isize - k) as usize;
+            let snake_start = (x, y);
+            if x < n && y < m {
+                x += common_prefix(&old[x..], &new[y..]);
+            }
+            forward[k] = x;
+
+            if odd && (k - delta).abs() < d && forward[k] + backward[delta - k] >= n {
+                return (
+                    old_range.start + snake_start.0,
+                    new_range.start + snake_start.1,
+                );
+            }
+        }
+
+        // the same from the end: x and y count tokens from the end
+        for k in (-d..=d).rev().step_by(2) {
+            let mut x = if k == -d || (k != d && backward[k - 1] < backward[k + 1]) {
+                backward[k + 1]
+            } else {
+                backward[k - 1] + 1
+            };
+            let mut y = (x as isize - k) as usize;
+            if x < n && y < m {
+                let snake = common_suffix(&old[..n - x], &new[..m - y]);
+                x += snake;
+                y += snake;
+            }
+            backward[k] = x;
+
+            if !odd && (k - delta).abs() <= d && backward[k] + forward[delta - k] >= n {
+                return (old_range.start + n - x, new_range.start + m - y);
+            }
+        }
+    }
+    unreachable!("the searches always meet by d = (n + m) / 2")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_myers_paper_example() {
+        // the example from the paper: ABCABBA to CBABAC takes 5 edits
+        let ops = myers_diff(b"ABCABBA", b"CBABAC");
+        let edits: usize = ops
+            .iter()
+            .filter(|op| op.tag != Tag::Equal)
+            .map(|op| op.old.len() + op.new.len())
+            .sum();
+        assert_eq!(edits, 5);
+    }
+
+    #[test]
+    fn test_long_inputs_in_linear_space() {
+        let old: Vec<u32> = (0..200_000).collect();
+        let mut new = old.clone();
+        for i in (0..new.len()).step_by(1000) {
+            new[i] += 1_000_000;
+        }
+        new.insert(50_000, 7);
+        let ops = myers_diff(&old, &new);
+        let deleted: usize = ops
+            .iter()
+            .filter(|op| op.tag == Tag::Delete)
+            .map(|op| op.old.len())
+            .sum();
+        assert_eq!(deleted, 200);
+    }
+}
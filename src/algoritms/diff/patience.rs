@@ -0,0 +1,162 @@
+// Patience diff (Bram Cohen). Tokens that occur exactly once in both sequences are
+// likely to be the ones that really correspond, so those are lined up first: the longest
+// increasing subsequence of their positions, found with patience sorting, becomes a set
+// of anchors. The gaps between anchors are diffed the same way, and with Myers once they
+// have no unique tokens in common.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::ops::Range;
+
+use super::myers;
+use super::{DiffOp, Tag, common_prefix, common_suffix, push_op};
+use crate::algoritms::search::partition_point;
+
+pub fn patience_diff<T: Eq + Hash>(old: &[T], new: &[T]) -> Vec<DiffOp> {
+    let mut ops = Vec::new();
+    diff_into(old, 0..old.len(), new, 0..new.len(), &mut ops);
+    ops
+}
+
+fn diff_into<T: Eq + Hash>(
+    old: &[T],
+    mut old_range: Range<usize>,
+    new: &[T],
+    mut new_range: Range<usize>,
+    ops: &mut Vec<DiffOp>,
+) {
+    let prefix = common_prefix(&old[old_range.clone()], &new[new_range.clone()]);
+    push_op(
+        ops,
+        Tag::Equal,
+        old_range.start..old_range.start + prefix,
+        new_range.start..new_range.start + prefix,
+    );
+    old_range.start += prefix;
+    new_range.start += prefix;
+
+    let suffix = common_suffix(&old[old_range.clone()], &new[new_range.clone()]);
+    let old_suffix = old_range.end - suffix..old_range.end;
+    let new_suffix = new_range.end - suffix..new_range.end;
+    old_range.end -= suffix;
+    new_range.end -= suffix;
+
+    let anchors = anchors(old, old_range.clone(), new, new_range.clone());
+    if anchors.is_empty() {
+        myers::diff_into(old, old_range, new, new_range, ops);
+    } else {
+        let (mut x, mut y) = (old_range.start, new_range.start);
+        for (i, j) in anchors {
+            diff_into(old, x..i, new, y..j, ops);
+            push_op(ops, Tag::Equal, i..i + 1, j..j + 1);
+            (x, y) = (i + 1, j + 1);
+        }
+        diff_into(old, x..old_range.end, new, y..new_range.end, ops);
+    }
+
+    push_op(ops, Tag::Equal, old_suffix, new_suffix);
+}
+
+/// Pairs of positions of the tokens unique to both ranges, the longest chain of them that
+/// is increasing on both sides
+fn anchors<T: Eq + Hash>(
+    old: &[T],
+    old_range: Range<usize>,
+    new: &[T],
+    new_range: Range<usize>,
+) -> Vec<(usize, usize)> {
+    // per token: count and last position in old, the same in new
+    let mut seen: HashMap<&T, (usize, usize, usize, usize)> = HashMap::new();
+    for i in old_range {
+        let entry = seen.entry(&old[i]).or_insert((0, 0, 0, 0));
+        entry.0 += 1;
+        entry.1 = i;
+    }
+    for j in new_range {
+        if let Some(entry) = seen.get_mut(&new[j]) {
+            entry.2 += 1;
+            entry.3 = j;
+        }
+    }
+    let mut unique: Vec<(usize, usize)> = seen
+        .into_values()
+        .filter(|&(old_count, _, new_count, _)| old_count == 1 && new_count == 1)
+        .map(|(_, i, _, j)| (i, j))
+        .collect();
+    unique.sort_unstable();
+
+    longest_increasing_by_new(&unique)
+}
+
+/// Longest subsequence of `pairs` (sorted by old position) with increasing new positions.
+/// Patience sorting: each pair goes on the leftmost pile whose top is larger, and
+/// remembers the top of the pile before it, so following those links back from the last
+/// pile spells out the subsequence.
+fn longest_increasing_by_new(pairs: &[(usize, usize)]) -> Vec<(usize, usize)> {
+    // indices into pairs of the pile tops, and each pair's predecessor
+    let mut tops: Vec<usize> = Vec::new();
+    let mut previous = vec![usize::MAX; pairs.len()];
+
+    for (index, &(_, j)) in pairs.iter().enumerate() {
+        let pile = partition_point(&tops, |&top| pairs[top].1 < j);
+        if pile > 0 {
+            previous[index] = tops[pile - 1];
+        }
+        if pile == tops.len() {
+            tops.push(index);
+        } else {
+            tops[pile] = index;
+        }
+    }
+
+    let mut chain = Vec::with_capacity(tops.len());
+    let mut current = tops.last().copied();
+    while let Some(index) = current {
+        chain.push(pairs[index]);
+        current = (previous[index] != usize::MAX).then(|| previous[index]);
+    }
+    chain.reverse();
+    chain
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algoritms::diff::myers_diff;
+
+    #[test]
+    fn test_longest_increasing() {
+        let pairs = [(0, 3), (1, 1), (2, 4), (3, 0), (4, 2), (5, 5)];
+        let chain = longest_increasing_by_new(&pairs);
+        assert_eq!(chain.len(), 3);
+        assert!(chain.windows(2).all(|w| w[0].1 < w[1].1));
+        assert!(longest_increasing_by_new(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_patience_keeps_unique_lines_together() {
+        // swapping two functions
+        let old = [
+            "fn a() {", "    one", "}", "", "fn b() {", "    two", "}", "",
+        ];
+        let new = [
+            "fn b() {", "    two", "}", "", "fn a() {", "    one", "}", "",
+        ];
+        let patience = patience_diff(&old, &new);
+        // the unique lines of fn b are kept, fn a is deleted and inserted after it
+        assert!(patience.contains(&DiffOp {
+            tag: Tag::Equal,
+            old: 4..6,
+            new: 0..2
+        }));
+
+        let myers = myers_diff(&old, &new);
+        let edits = |ops: &[DiffOp]| -> usize {
+            ops.iter()
+                .filter(|op| op.tag != Tag::Equal)
+                .map(|op| op.old.len() + op.new.len())
+                .sum()
+        };
+        assert!(edits(&myers) <= edits(&patience));
+    }
+}
@@ -0,0 +1,326 @@
+// Diffs of text: split into lines or words, diff the tokens, print the result.
+// Tokens keep their line ending or surrounding whitespace, so joining them gives back the
+// text exactly and a patch rebuilds the new text byte for byte.
+
+use std::fmt::Write;
+
+use super::{Algorithm, DiffOp, Edit, Tag, edit_script};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Granularity {
+    /// each line with its line ending
+    Lines,
+    /// runs of non-whitespace and runs of whitespace, each a token
+    Words,
+}
+
+/// Splits `text` into tokens that join back to it
+pub fn tokenize(text: &str, granularity: Granularity) -> Vec<&str> {
+    match granularity {
+        Granularity::Lines => text.split_inclusive('\n').collect(),
+        Granularity::Words => {
+            let mut tokens = Vec::new();
+            let mut start = 0;
+            let mut chars = text.char_indices().peekable();
+            while let Some((_, c)) = chars.next() {
+                if let Some(&(next, following)) = chars.peek()
+                    && following.is_whitespace() != c.is_whitespace()
+                {
+                    tokens.push(&text[start..next]);
+                    start = next;
+                }
+            }
+            if start < text.len() {
+                tokens.push(&text[start..]);
+            }
+            tokens
+        }
+    }
+}
+
+pub struct TextDiff<'a> {
+    granularity: Granularity,
+    old: Vec<&'a str>,
+    new: Vec<&'a str>,
+    ops: Vec<DiffOp>,
+}
+
+impl<'a> TextDiff<'a> {
+    pub fn new(old: &'a str, new: &'a str, granularity: Granularity, algorithm: Algorithm) -> Self {
+        let old = tokenize(old, granularity);
+        let new = tokenize(new, granularity);
+        let ops = algorithm.diff(&old, &new);
+        Self {
+            granularity,
+            old,
+            new,
+            ops,
+        }
+    }
+
+    pub fn lines(old: &'a str, new: &'a str, algorithm: Algorithm) -> Self {
+        Self::new(old, new, Granularity::Lines, algorithm)
+    }
+
+    pub fn words(old: &'a str, new: &'a str, algorithm: Algorithm) -> Self {
+        Self::new(old, new, Granularity::Words, algorithm)
+    }
+
+    pub fn old_tokens(&self) -> &[&'a str] {
+        &self.old
+    }
+
+    pub fn new_tokens(&self) -> &[&'a str] {
+        &self.new
+    }
+
+    pub fn ops(&self) -> &[DiffOp] {
+        &self.ops
+    }
+
+    pub fn has_changes(&self) -> bool {
+        self.ops.iter().any(|op| op.tag != Tag::Equal)
+    }
+
+    /// The patch turning the old tokens into the new ones
+    pub fn edit_script(&self) -> Vec<Edit<&'a str>> {
+        edit_script(&self.ops, &self.new)
+    }
+
+    /// The diff in unified format with `context` unchanged tokens around each change, or
+    /// an empty string if there are no changes. Word diffs put one token on each line.
+    pub fn unified(&self, old_name: &str, new_name: &str, context: usize) -> String {
+        let hunks = self.hunks(context);
+        let mut out = String::new();
+        if hunks.is_empty() {
+            return out;
+        }
+        writeln!(out, "--- {old_name}\n+++ {new_name}").unwrap();
+
+        for hunk in hunks {
+            let (first, last) = (&hunk[0], &hunk[hunk.len() - 1]);
+            writeln!(
+                out,
+                "@@ -{} +{} @@",
+                hunk_range(first.old.start, last.old.end),
+                hunk_range(first.new.start, last.new.end)
+            )
+            .unwrap();
+            for op in &hunk {
+                let (sign, tokens) = match op.tag {
+                    Tag::Equal => (' ', &self.old[op.old.clone()]),
+                    Tag::Delete => ('-', &self.old[op.old.clone()]),
+                    Tag::Insert => ('+', &self.new[op.new.clone()]),
+                };
+                for token in tokens {
+                    self.write_token(&mut out, sign, token);
+                }
+            }
+        }
+        out
+    }
+
+    fn write_token(&self, out: &mut String, sign: char, token: &str) {
+        match self.granularity {
+            Granularity::Lines => {
+                out.push(sign);
+                out.push_str(token);
+                if !token.ends_with('\n') {
+                    out.push_str("\n\\ No newline at end of file\n");
+                }
+            }
+            // escape the whitespace tokens, a bare newline would end the diff line
+            Granularity::Words => writeln!(out, "{sign}{}", token.escape_debug()).unwrap(),
+        }
+    }
+
+    /// Runs of changes with `context` equal tokens on either side; changes closer than
+    /// twice the context share a hunk
+    fn hunks(&self, context: usize) -> Vec<Vec<DiffOp>> {
+        let mut hunks = Vec::new();
+        if !self.has_changes() {
+            return hunks;
+        }
+        let mut hunk = Vec::new();
+        let last = self.ops.len() - 1;
+
+        for (i, op) in self.ops.iter().enumerate() {
+            if op.tag != Tag::Equal {
+                hunk.push(op.clone());
+                continue;
+            }
+            let (old, new) = (op.old.clone(), op.new.clone());
+            // context closing the hunk before this run, and opening the one after it
+            let closing = if i == 0 { 0 } else { context.min(old.len()) };
+            let opening = if i == last { 0 } else { context.min(old.len()) };
+
+            if i != 0 && i != last && old.len() <= 2 * context {
+                hunk.push(op.clone());
+                continue;
+            }
+            if closing > 0 {
+                hunk.push(DiffOp {
+                    tag: Tag::Equal,
+                    old: old.start..old.start + closing,
+                    new: new.start..new.start + closing,
+                });
+            }
+            if !hunk.is_empty() {
+                hunks.push(std::mem::take(&mut hunk));
+            }
+            if opening > 0 {
+                hunk.push(DiffOp {
+                    tag: Tag::Equal,
+                    old: old.end - opening..old.end,
+                    new: new.end - opening..new.end,
+                });
+            }
+        }
+        if hunk.iter().any(|op| op.tag != Tag::Equal) {
+            hunks.push(hunk);
+        }
+        hunks
+    }
+
+    /// The new text with changes marked inline: `[-deleted-]` and `{+inserted+}`
+    pub fn inline(&self) -> String {
+        let mut out = String::new();
+        for op in &self.ops {
+            match op.tag {
+                Tag::Equal => self.old[op.old.clone()]
+                    .iter()
+                    .for_each(|token| out.push_str(token)),
+                Tag::Delete => write!(out, "[-{}-]", self.old[op.old.clone()].concat()).unwrap(),
+                Tag::Insert => write!(out, "{{+{}+}}", self.new[op.new.clone()].concat()).unwrap(),
+            }
+        }
+        out
+    }
+}
+
+/// `start,len` of a hunk, 1-based. A single line is just its number, and an empty range
+/// is numbered by the line before it.
+fn hunk_range(start: usize, end: usize) -> String {
+    match end - start {
+        0 => format!("{start},0"),
+        1 => format!("{}", start + 1),
+        len => format!("{},{len}", start + 1),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::algoritms::diff::apply;
+
+    #[test]
+    fn test_tokenize() {
+        assert_eq!(
+            tokenize("a b\n\nc", Granularity::Lines),
+            ["a b\n", "\n", "c"]
+        );
+        assert_eq!(
+            tokenize("  call me\tIshmael.\n", Granularity::Words),
+            ["  ", "call", " ", "me", "\t", "Ishmael.", "\n"]
+        );
+        assert!(tokenize("", Granularity::Words).is_empty());
+        assert_eq!(tokenize("æ ø", Granularity::Words), ["æ", " ", "ø"]);
+    }
+
+    #[test]
+    fn test_unified() {
+        let old = "a\nb\nc\nd\ne\nf\ng\nh\ni\n";
+        let new = "a\nB\nc\nd\ne\nf\ng\nh\ni\nj\n";
+        for algorithm in Algorithm::ALL {
+            let diff = TextDiff::lines(old, new, algorithm);
+            assert_eq!(
+                diff.unified("old", "new", 1),
+                "--- old\n+++ new\n\
+                 @@ -1,3 +1,3 @@\n a\n-b\n+B\n c\n\
+                 @@ -9 +9,2 @@\n i\n+j\n"
+            );
+            // with more context the two changes share a hunk
+            let unified = diff.unified("old", "new", 4);
+            assert_eq!(unified.matches("@@ -").count(), 1);
+            assert!(unified.starts_with("--- old\n+++ new\n@@ -1,9 +1,10 @@\n a\n-b\n+B\n"));
+        }
+        let diff = TextDiff::lines(old, old, Algorithm::Myers);
+        assert!(!diff.has_changes());
+        assert_eq!(diff.unified("old", "new", 3), "");
+    }
+
+    #[test]
+    fn test_missing_newline_and_empty_sides() {
+        let diff = TextDiff::lines("a\nb", "a\nc", Algorithm::Myers);
+        assert_eq!(
+            diff.unified("x", "y", 3),
+            "--- x\n+++ y\n@@ -1,2 +1,2 @@\n a\n-b\n\\ No newline at end of file\n\
+             +c\n\\ No newline at end of file\n"
+        );
+
+        let diff = TextDiff::lines("", "one\n", Algorithm::Patience);
+        assert_eq!(
+            diff.unified("x", "y", 3),
+            "--- x\n+++ y\n@@ -0,0 +1 @@\n+one\n"
+        );
+    }
+
+    #[test]
+    fn test_word_diff() {
+        let old = "Call me Ishmael. Some years ago";
+        let new = "Call me Ahab. Some years ago";
+        for algorithm in Algorithm::ALL {
+            let diff = TextDiff::words(old, new, algorithm);
+            assert_eq!(
+                diff.inline(),
+                "Call me [-Ishmael.-]{+Ahab.+} Some years ago"
+            );
+            assert_eq!(
+                diff.unified("old", "new", 1),
+                "--- old\n+++ new\n@@ -4,3 +4,3 @@\n  \n-Ishmael.\n+Ahab.\n  \n"
+            );
+        }
+    }
+
+    #[test]
+    fn test_moby_dick_diff() {
+        let old = fs::read_to_string("data/moby_dick.txt").unwrap();
+        let new = fs::read_to_string("data/moby_dick2.txt").unwrap();
+
+        let diff = TextDiff::lines(&old, &new, Algorithm::Patience);
+        assert!(diff.has_changes());
+        let script = diff.edit_script();
+        assert_eq!(apply(diff.old_tokens(), &script).concat(), new);
+
+        let unified = diff.unified("moby_dick.txt", "moby_dick2.txt", 3);
+        assert!(unified.contains("\n-*** START OF THE PROJECT GUTENBERG EBOOK"));
+        assert!(unified.contains("\n+*** STALT OF THE PLOJECT GUTENBELG"));
+
+        // myers finds a diff at most as long on the first chapters
+        let prefix = |text: &str| text.lines().take(2000).collect::<Vec<_>>().join("\n");
+        let (old_start, new_start) = (prefix(&old), prefix(&new));
+        let edits = |diff: &TextDiff| {
+            diff.ops()
+                .iter()
+                .filter(|op| op.tag != Tag::Equal)
+                .map(|op| op.old.len() + op.new.len())
+                .sum::<usize>()
+        };
+        let myers = TextDiff::lines(&old_start, &new_start, Algorithm::Myers);
+        let patience = TextDiff::lines(&old_start, &new_start, Algorithm::Patience);
+        assert!(edits(&myers) <= edits(&patience));
+        assert_eq!(
+            apply(myers.old_tokens(), &myers.edit_script()).concat(),
+            new_start
+        );
+
+        let words = TextDiff::words(&old, &new, Algorithm::Patience);
+        assert!(words.inline().contains("[-START-]{+STALT+}"));
+        assert_eq!(
+            apply(words.old_tokens(), &words.edit_script()).concat(),
+            new
+        );
+    }
+}
@@ -1,4 +1,5 @@
 pub mod compression;
+pub mod diff;
 pub mod recursive_stuff;
 pub mod search;
 pub mod sorting;
@@ -0,0 +1,236 @@
+// Permutations, combinations and subsets as backtracking problems. The state is the
+// indices chosen so far; the generators list everything in lexicographic order of those
+// indices, and the problems can be run in any mode or just counted.
+
+use super::{Backtrack, Mode, search};
+
+/// Orderings of all the items
+pub struct Permutations<'a, T> {
+    items: &'a [T],
+    chosen: Vec<usize>,
+    used: Vec<bool>,
+}
+
+impl<'a, T> Permutations<'a, T> {
+    pub fn new(items: &'a [T]) -> Self {
+        Permutations {
+            items,
+            chosen: Vec::with_capacity(items.len()),
+            used: vec![false; items.len()],
+        }
+    }
+}
+
+impl<T> Backtrack for Permutations<'_, T> {
+    type Choice = usize;
+
+    fn is_solution(&self) -> bool {
+        self.chosen.len() == self.items.len()
+    }
+
+    fn candidates(&self) -> Vec<usize> {
+        (0..self.items.len()).collect()
+    }
+
+    fn is_valid(&self, &i: &usize) -> bool {
+        !self.used[i]
+    }
+
+    fn apply(&mut self, &i: &usize) {
+        self.used[i] = true;
+        self.chosen.push(i);
+    }
+
+    fn undo(&mut self, &i: &usize) {
+        self.chosen.pop();
+        self.used[i] = false;
+    }
+}
+
+/// Choices of `k` of the items, in the order they come in
+pub struct Combinations<'a, T> {
+    items: &'a [T],
+    k: usize,
+    chosen: Vec<usize>,
+}
+
+impl<'a, T> Combinations<'a, T> {
+    pub fn new(items: &'a [T], k: usize) -> Self {
+        Combinations {
+            items,
+            k,
+            chosen: Vec::with_capacity(k),
+        }
+    }
+
+    fn next_index(&self) -> usize {
+        self.chosen.last().map_or(0, |last| last + 1)
+    }
+}
+
+impl<T> Backtrack for Combinations<'_, T> {
+    type Choice = usize;
+
+    fn is_solution(&self) -> bool {
+        self.chosen.len() == self.k
+    }
+
+    fn candidates(&self) -> Vec<usize> {
+        if self.is_dead_end() || self.chosen.len() == self.k {
+            return Vec::new();
+        }
+        // leaving enough items after the choice to fill up the rest
+        let last = self.items.len() - (self.k - self.chosen.len());
+        (self.next_index()..=last).collect()
+    }
+
+    /// Too few items left to make up `k`, only possible at the start with the candidates
+    /// bounded
+    fn is_dead_end(&self) -> bool {
+        self.items.len() - self.next_index() < self.k - self.chosen.len()
+    }
+
+    fn apply(&mut self, &i: &usize) {
+        self.chosen.push(i);
+    }
+
+    fn undo(&mut self, _: &usize) {
+        self.chosen.pop();
+    }
+}
+
+/// Every subset of the items, the empty one first. Each state is a solution, extended by
+/// adding a later item.
+pub struct Subsets<'a, T> {
+    items: &'a [T],
+    chosen: Vec<usize>,
+}
+
+impl<'a, T> Subsets<'a, T> {
+    pub fn new(items: &'a [T]) -> Self {
+        Subsets {
+            items,
+            chosen: Vec::new(),
+        }
+    }
+}
+
+impl<T> Backtrack for Subsets<'_, T> {
+    type Choice = usize;
+
+    fn is_solution(&self) -> bool {
+        true
+    }
+
+    fn candidates(&self) -> Vec<usize> {
+        let next = self.chosen.last().map_or(0, |last| last + 1);
+        (next..self.items.len()).collect()
+    }
+
+    fn apply(&mut self, &i: &usize) {
+        self.chosen.push(i);
+    }
+
+    fn undo(&mut self, _: &usize) {
+        self.chosen.pop();
+    }
+}
+
+fn pick<T: Clone>(items: &[T], chosen: &[usize]) -> Vec<T> {
+    chosen.iter().map(|&i| items[i].clone()).collect()
+}
+
+pub fn permutations<T: Clone>(items: &[T]) -> Vec<Vec<T>> {
+    search(&mut Permutations::new(items), Mode::All, |p| {
+        pick(items, &p.chosen)
+    })
+    .solutions
+}
+
+pub fn combinations<T: Clone>(items: &[T], k: usize) -> Vec<Vec<T>> {
+    search(&mut Combinations::new(items, k), Mode::All, |c| {
+        pick(items, &c.chosen)
+    })
+    .solutions
+}
+
+pub fn subsets<T: Clone>(items: &[T]) -> Vec<Vec<T>> {
+    search(&mut Subsets::new(items), Mode::All, |s| {
+        pick(items, &s.chosen)
+    })
+    .solutions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algoritms::recursive_stuff::backtracking::count_solutions;
+
+    fn binomial(n: usize, k: usize) -> usize {
+        (0..k).fold(1, |c, i| c * (n - i) / (i + 1))
+    }
+
+    #[test]
+    fn test_permutations() {
+        assert_eq!(
+            permutations(&['a', 'b', 'c']),
+            [
+                ['a', 'b', 'c'],
+                ['a', 'c', 'b'],
+                ['b', 'a', 'c'],
+                ['b', 'c', 'a'],
+                ['c', 'a', 'b'],
+                ['c', 'b', 'a'],
+            ]
+        );
+        assert_eq!(permutations::<u8>(&[]), [Vec::<u8>::new()]);
+        let items: Vec<usize> = (0..7).collect();
+        assert_eq!(count_solutions(&mut Permutations::new(&items)), 5040);
+        let all = permutations(&items[..5]);
+        assert!(all.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn test_combinations() {
+        assert_eq!(
+            combinations(&[1, 2, 3, 4], 2),
+            [[1, 2], [1, 3], [1, 4], [2, 3], [2, 4], [3, 4]]
+        );
+        assert_eq!(combinations(&[1, 2], 0), [Vec::<i32>::new()]);
+        assert!(combinations(&[1, 2], 3).is_empty());
+        let items: Vec<usize> = (0..12).collect();
+        for k in 0..=12 {
+            let mut problem = Combinations::new(&items, k);
+            assert_eq!(count_solutions(&mut problem), binomial(12, k));
+        }
+        // no branch is started that can't fill up
+        let full = search(&mut Combinations::new(&items, 12), Mode::All, |_| ());
+        assert_eq!(full.nodes, 13);
+    }
+
+    #[test]
+    fn test_subsets() {
+        assert_eq!(
+            subsets(&["x", "y", "z"]),
+            [
+                vec![],
+                vec!["x"],
+                vec!["x", "y"],
+                vec!["x", "y", "z"],
+                vec!["x", "z"],
+                vec!["y"],
+                vec!["y", "z"],
+                vec!["z"],
+            ]
+        );
+        let items = [0u8; 16];
+        assert_eq!(count_solutions(&mut Subsets::new(&items)), 1 << 16);
+        let first = search(&mut Subsets::new(&items), Mode::AtMost(4), |s| {
+            s.chosen.clone()
+        });
+        assert_eq!(
+            first.solutions,
+            [vec![], vec![0], vec![0, 1], vec![0, 1, 2]]
+        );
+    }
+}
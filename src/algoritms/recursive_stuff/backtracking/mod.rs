@@ -0,0 +1,262 @@
+// Backtracking: build a solution one choice at a time, and undo the last choice when
+// there is no way on from it. A problem describes its state and the choices from it
+// (`Backtrack`), the engine does the walk: depth-first, pruning invalid choices and dead
+// ends, handing every solution to a callback that can stop the search.
+// - queens.rs: N-Queens
+// - sudoku.rs: Sudoku, filling the cell with the fewest options first
+// - combinatorics.rs: permutations, combinations and subsets
+// - subset_sum.rs: subsets of numbers adding up to a target
+
+pub mod combinatorics;
+pub mod queens;
+pub mod subset_sum;
+pub mod sudoku;
+
+pub use combinatorics::{Combinations, Permutations, Subsets, combinations, permutations, subsets};
+pub use queens::{NQueens, n_queens};
+pub use subset_sum::{SubsetSum, subset_sum};
+pub use sudoku::Sudoku;
+
+/// A problem for the engine: a state changed in place by `apply` and `undo`
+pub trait Backtrack {
+    type Choice;
+
+    /// Whether the current state is a solution. The search goes on below it, for problems
+    /// where a solution can be extended to another one.
+    fn is_solution(&self) -> bool;
+
+    /// The choices that can be made next, empty when the state is complete
+    fn candidates(&self) -> Vec<Self::Choice>;
+
+    /// Whether `choice` keeps the state consistent, the pruning of single choices
+    fn is_valid(&self, _choice: &Self::Choice) -> bool {
+        true
+    }
+
+    /// Whether no solution can be reached from the current state, pruning whole subtrees
+    fn is_dead_end(&self) -> bool {
+        false
+    }
+
+    fn apply(&mut self, choice: &Self::Choice);
+
+    /// Reverts `apply` with the same choice
+    fn undo(&mut self, choice: &Self::Choice);
+}
+
+/// What the solution callback wants next
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Control {
+    Continue,
+    Stop,
+}
+
+/// How many solutions to look for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    First,
+    All,
+    AtMost(usize),
+}
+
+impl Mode {
+    fn limit(self) -> usize {
+        match self {
+            Mode::First => 1,
+            Mode::All => usize::MAX,
+            Mode::AtMost(limit) => limit,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Search<S> {
+    pub solutions: Vec<S>,
+    /// states visited, the root included
+    pub nodes: usize,
+    /// whether the search ended on the mode's limit rather than running out of states
+    pub stopped: bool,
+}
+
+impl<S> Search<S> {
+    pub fn count(&self) -> usize {
+        self.solutions.len()
+    }
+
+    pub fn first(&self) -> Option<&S> {
+        self.solutions.first()
+    }
+}
+
+/// Walks every state reachable from the current one, calling `on_solution` on each
+/// solution until it returns `Stop`. The problem is back in its starting state after.
+/// Returns the number of states visited and whether the search was stopped.
+pub fn backtrack<P: Backtrack>(
+    problem: &mut P,
+    mut on_solution: impl FnMut(&P) -> Control,
+) -> (usize, bool) {
+    fn walk<P: Backtrack>(
+        problem: &mut P,
+        on_solution: &mut impl FnMut(&P) -> Control,
+        nodes: &mut usize,
+    ) -> Control {
+        *nodes += 1;
+        if problem.is_dead_end() {
+            return Control::Continue;
+        }
+        if problem.is_solution() && on_solution(problem) == Control::Stop {
+            return Control::Stop;
+        }
+        for choice in problem.candidates() {
+            if !problem.is_valid(&choice) {
+                continue;
+            }
+            problem.apply(&choice);
+            let control = walk(problem, on_solution, nodes);
+            problem.undo(&choice);
+            if control == Control::Stop {
+                return Control::Stop;
+            }
+        }
+        Control::Continue
+    }
+
+    let mut nodes = 0;
+    let control = walk(problem, &mut on_solution, &mut nodes);
+    (nodes, control == Control::Stop)
+}
+
+/// The solutions `mode` asks for, each read off the state by `extract`
+pub fn search<P: Backtrack, S>(
+    problem: &mut P,
+    mode: Mode,
+    mut extract: impl FnMut(&P) -> S,
+) -> Search<S> {
+    let limit = mode.limit();
+    let mut solutions = Vec::new();
+    if limit == 0 {
+        return Search {
+            solutions,
+            nodes: 0,
+            stopped: true,
+        };
+    }
+    let (nodes, stopped) = backtrack(problem, |state| {
+        solutions.push(extract(state));
+        if solutions.len() == limit {
+            Control::Stop
+        } else {
+            Control::Continue
+        }
+    });
+    Search {
+        solutions,
+        nodes,
+        stopped,
+    }
+}
+
+/// Number of solutions, without keeping them
+pub fn count_solutions<P: Backtrack>(problem: &mut P) -> usize {
+    let mut count = 0;
+    backtrack(problem, |_| {
+        count += 1;
+        Control::Continue
+    });
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Strings over `alphabet` of length `len` with no letter twice in a row
+    struct NoRepeats {
+        alphabet: Vec<char>,
+        len: usize,
+        word: String,
+    }
+
+    impl Backtrack for NoRepeats {
+        type Choice = char;
+
+        fn is_solution(&self) -> bool {
+            self.word.len() == self.len
+        }
+
+        fn candidates(&self) -> Vec<char> {
+            if self.word.len() == self.len {
+                return Vec::new();
+            }
+            self.alphabet.clone()
+        }
+
+        fn is_valid(&self, choice: &char) -> bool {
+            !self.word.ends_with(*choice)
+        }
+
+        fn apply(&mut self, choice: &char) {
+            self.word.push(*choice);
+        }
+
+        fn undo(&mut self, _choice: &char) {
+            self.word.pop();
+        }
+    }
+
+    fn no_repeats(len: usize) -> NoRepeats {
+        NoRepeats {
+            alphabet: vec!['a', 'b', 'c'],
+            len,
+            word: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_modes() {
+        let mut problem = no_repeats(3);
+        let all = search(&mut problem, Mode::All, |p| p.word.clone());
+        // 3 choices for the first letter, 2 for each after
+        assert_eq!(all.count(), 12);
+        assert!(!all.stopped);
+        assert_eq!(all.first().unwrap(), "aba");
+        assert!(all.solutions.windows(2).all(|w| w[0] < w[1]));
+        // the root, then 3 + 6 + 12 valid states
+        assert_eq!(all.nodes, 22);
+
+        let first = search(&mut problem, Mode::First, |p| p.word.clone());
+        assert_eq!(first.solutions, ["aba"]);
+        assert!(first.stopped);
+        assert!(first.nodes < all.nodes);
+
+        let some = search(&mut problem, Mode::AtMost(5), |p| p.word.clone());
+        assert_eq!(some.solutions, all.solutions[..5]);
+        assert!(
+            search(&mut problem, Mode::AtMost(0), |_| ())
+                .solutions
+                .is_empty()
+        );
+        assert_eq!(count_solutions(&mut problem), 12);
+        // undone back to the start every time
+        assert!(problem.word.is_empty());
+    }
+
+    #[test]
+    fn test_early_stop_from_callback() {
+        let mut problem = no_repeats(4);
+        let mut seen = Vec::new();
+        let (_, stopped) = backtrack(&mut problem, |p| {
+            seen.push(p.word.clone());
+            if p.word.starts_with('b') {
+                Control::Stop
+            } else {
+                Control::Continue
+            }
+        });
+        assert!(stopped);
+        // every word starting with a, then the first starting with b
+        assert_eq!(seen.len(), 2 * 2 * 2 + 1);
+        assert_eq!(seen.last().unwrap(), "baba");
+        assert!(problem.word.is_empty());
+    }
+}
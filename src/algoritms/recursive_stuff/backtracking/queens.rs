@@ -0,0 +1,121 @@
+// N-Queens: n queens on an n x n board, none attacking another. One queen goes in each
+// row, top to bottom; a column is a valid choice when its column and both diagonals are
+// still free, which is checked in O(1) with one flag per column and diagonal.
+
+use super::{Backtrack, Mode, Search, search};
+
+pub struct NQueens {
+    n: usize,
+    /// the column of the queen in each row filled so far
+    columns: Vec<usize>,
+    used_columns: Vec<bool>,
+    /// indexed by row + column
+    used_diagonals: Vec<bool>,
+    /// indexed by row + n - 1 - column
+    used_anti_diagonals: Vec<bool>,
+}
+
+impl NQueens {
+    pub fn new(n: usize) -> Self {
+        let diagonals = (2 * n).saturating_sub(1);
+        NQueens {
+            n,
+            columns: Vec::with_capacity(n),
+            used_columns: vec![false; n],
+            used_diagonals: vec![false; diagonals],
+            used_anti_diagonals: vec![false; diagonals],
+        }
+    }
+
+    pub fn columns(&self) -> &[usize] {
+        &self.columns
+    }
+
+    fn diagonals(&self, column: usize) -> (usize, usize) {
+        let row = self.columns.len();
+        (row + column, row + self.n - 1 - column)
+    }
+
+    fn mark(&mut self, column: usize, used: bool) {
+        let (diagonal, anti_diagonal) = self.diagonals(column);
+        self.used_columns[column] = used;
+        self.used_diagonals[diagonal] = used;
+        self.used_anti_diagonals[anti_diagonal] = used;
+    }
+}
+
+impl Backtrack for NQueens {
+    type Choice = usize;
+
+    fn is_solution(&self) -> bool {
+        self.columns.len() == self.n
+    }
+
+    fn candidates(&self) -> Vec<usize> {
+        if self.columns.len() == self.n {
+            return Vec::new();
+        }
+        (0..self.n).collect()
+    }
+
+    fn is_valid(&self, &column: &usize) -> bool {
+        let (diagonal, anti_diagonal) = self.diagonals(column);
+        !self.used_columns[column]
+            && !self.used_diagonals[diagonal]
+            && !self.used_anti_diagonals[anti_diagonal]
+    }
+
+    fn apply(&mut self, &column: &usize) {
+        self.mark(column, true);
+        self.columns.push(column);
+    }
+
+    fn undo(&mut self, &column: &usize) {
+        self.columns.pop();
+        self.mark(column, false);
+    }
+}
+
+/// Placements as the column of the queen in each row, in lexicographic order
+pub fn n_queens(n: usize, mode: Mode) -> Search<Vec<usize>> {
+    search(&mut NQueens::new(n), mode, |queens| queens.columns.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algoritms::recursive_stuff::backtracking::count_solutions;
+
+    fn is_placement(columns: &[usize]) -> bool {
+        (0..columns.len()).all(|r1| {
+            (r1 + 1..columns.len()).all(|r2| {
+                columns[r1] != columns[r2] && columns[r1].abs_diff(columns[r2]) != r2 - r1
+            })
+        })
+    }
+
+    #[test]
+    fn test_counts() {
+        // OEIS A000170
+        let expected = [1, 1, 0, 0, 2, 10, 4, 40, 92];
+        for (n, &count) in expected.iter().enumerate() {
+            assert_eq!(count_solutions(&mut NQueens::new(n)), count, "n = {n}");
+        }
+        let all = n_queens(8, Mode::All);
+        assert_eq!(all.count(), 92);
+        assert!(all.solutions.iter().all(|columns| is_placement(columns)));
+    }
+
+    #[test]
+    fn test_first() {
+        assert_eq!(n_queens(4, Mode::First).solutions, [[1, 3, 0, 2]]);
+        assert_eq!(
+            n_queens(8, Mode::First).first().unwrap(),
+            &[0, 4, 7, 5, 2, 6, 1, 3]
+        );
+        let large = n_queens(20, Mode::First);
+        assert!(large.stopped);
+        assert!(is_placement(large.first().unwrap()));
+        assert!(n_queens(3, Mode::First).solutions.is_empty());
+    }
+}
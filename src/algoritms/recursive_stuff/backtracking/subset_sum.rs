@@ -0,0 +1,144 @@
+// Subset sum: which of the numbers add up to the target. One yes/no choice per number,
+// in order. Two bounds prune the search: a number that would overshoot isn't taken, and
+// a branch ends when the numbers left can't make up what is missing.
+// NP-complete, so exponential in the worst case; the dynamic programming version is
+// pseudo-polynomial in the target instead.
+
+use super::{Backtrack, Mode, Search, search};
+
+pub struct SubsetSum<'a> {
+    numbers: &'a [u64],
+    target: u64,
+    /// remaining[i]: the sum of numbers[i..]
+    remaining: Vec<u64>,
+    /// whether each number decided so far is taken
+    taken: Vec<bool>,
+    sum: u64,
+}
+
+impl<'a> SubsetSum<'a> {
+    pub fn new(numbers: &'a [u64], target: u64) -> Self {
+        let mut remaining = vec![0; numbers.len() + 1];
+        for i in (0..numbers.len()).rev() {
+            remaining[i] = remaining[i + 1] + numbers[i];
+        }
+        SubsetSum {
+            numbers,
+            target,
+            remaining,
+            taken: Vec::with_capacity(numbers.len()),
+            sum: 0,
+        }
+    }
+
+    /// Indices of the numbers taken
+    fn chosen(&self) -> Vec<usize> {
+        (0..self.taken.len()).filter(|&i| self.taken[i]).collect()
+    }
+}
+
+impl Backtrack for SubsetSum<'_> {
+    type Choice = bool;
+
+    fn is_solution(&self) -> bool {
+        self.taken.len() == self.numbers.len() && self.sum == self.target
+    }
+
+    fn candidates(&self) -> Vec<bool> {
+        if self.taken.len() == self.numbers.len() {
+            return Vec::new();
+        }
+        vec![true, false]
+    }
+
+    fn is_valid(&self, &take: &bool) -> bool {
+        !take || self.sum + self.numbers[self.taken.len()] <= self.target
+    }
+
+    fn is_dead_end(&self) -> bool {
+        self.sum + self.remaining[self.taken.len()] < self.target
+    }
+
+    fn apply(&mut self, &take: &bool) {
+        if take {
+            self.sum += self.numbers[self.taken.len()];
+        }
+        self.taken.push(take);
+    }
+
+    fn undo(&mut self, _: &bool) {
+        if self.taken.pop() == Some(true) {
+            self.sum -= self.numbers[self.taken.len()];
+        }
+    }
+}
+
+/// Sets of indices into `numbers` whose numbers add up to `target`, sets with earlier
+/// indices first
+pub fn subset_sum(numbers: &[u64], target: u64, mode: Mode) -> Search<Vec<usize>> {
+    search(
+        &mut SubsetSum::new(numbers, target),
+        mode,
+        SubsetSum::chosen,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algoritms::recursive_stuff::backtracking::count_solutions;
+    use crate::algoritms::sorting::quick_sort::XorShift64;
+
+    #[test]
+    fn test_subset_sum() {
+        let numbers = [3, 34, 4, 12, 5, 2];
+        let first = subset_sum(&numbers, 9, Mode::First);
+        assert_eq!(first.solutions, [[0, 2, 5]]);
+
+        let all = subset_sum(&numbers, 9, Mode::All);
+        assert_eq!(all.solutions, [vec![0, 2, 5], vec![2, 4]]);
+        assert!(subset_sum(&numbers, 30, Mode::All).solutions.is_empty());
+        // the empty set makes 0
+        assert_eq!(
+            subset_sum(&numbers, 0, Mode::All).solutions,
+            [Vec::<usize>::new()]
+        );
+        assert_eq!(subset_sum(&[], 0, Mode::All).count(), 1);
+        assert_eq!(subset_sum(&[], 1, Mode::All).count(), 0);
+    }
+
+    #[test]
+    fn test_against_brute_force() {
+        let mut rng = XorShift64::new(49);
+        for _ in 0..100 {
+            let numbers: Vec<u64> = (0..rng.next_below(12))
+                .map(|_| rng.next_below(20) as u64)
+                .collect();
+            let target = rng.next_below(60) as u64;
+            let expected = (0u32..1 << numbers.len())
+                .filter(|mask| {
+                    let sum: u64 = (0..numbers.len())
+                        .filter(|i| mask & (1 << i) != 0)
+                        .map(|i| numbers[i])
+                        .sum();
+                    sum == target
+                })
+                .count();
+            let mut problem = SubsetSum::new(&numbers, target);
+            assert_eq!(count_solutions(&mut problem), expected);
+            for chosen in subset_sum(&numbers, target, Mode::All).solutions {
+                assert_eq!(chosen.iter().map(|&i| numbers[i]).sum::<u64>(), target);
+            }
+        }
+    }
+
+    #[test]
+    fn test_pruning() {
+        // 2^30 subsets, but only sums of 1 to 30 with at most a few numbers are reachable
+        let numbers: Vec<u64> = (1..=30).collect();
+        let search = subset_sum(&numbers, 10, Mode::All);
+        // the partitions of 10 into distinct parts
+        assert_eq!(search.count(), 10);
+        assert!(search.nodes < 10_000);
+    }
+}
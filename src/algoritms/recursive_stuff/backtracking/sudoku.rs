@@ -0,0 +1,289 @@
+// Sudoku on the engine. Every step fills the empty cell with the fewest digits left
+// (most constrained first), which keeps the search tree small: a forced cell has one
+// candidate, and a cell with none ends the branch right away. Digits used per row,
+// column and box are bit masks, so the options for a cell are one OR away.
+
+use std::fmt::{self, Display};
+
+use super::{Backtrack, Mode, Search, search};
+
+const ALL_DIGITS: u16 = 0b11_1111_1110;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sudoku {
+    /// 0 for an empty cell
+    cells: [[u8; 9]; 9],
+    rows: [u16; 9],
+    columns: [u16; 9],
+    boxes: [u16; 9],
+    empty: usize,
+}
+
+fn box_of(row: usize, column: usize) -> usize {
+    row / 3 * 3 + column / 3
+}
+
+impl Sudoku {
+    /// Reads 81 cells row by row, digits and `.` or `0` for empty ones. Whitespace and
+    /// `|`, `-`, `+` drawing a grid are skipped.
+    pub fn parse(text: &str) -> Result<Sudoku, String> {
+        let cells: Vec<u8> = text
+            .chars()
+            .filter(|c| !c.is_whitespace() && !matches!(c, '|' | '-' | '+'))
+            .map(|c| match c {
+                '.' => Ok(0),
+                '0'..='9' => Ok(c as u8 - b'0'),
+                _ => Err(format!("unexpected character {c:?}")),
+            })
+            .collect::<Result<_, _>>()?;
+        if cells.len() != 81 {
+            return Err(format!("expected 81 cells, found {}", cells.len()));
+        }
+        let mut grid = [[0; 9]; 9];
+        for (i, &digit) in cells.iter().enumerate() {
+            grid[i / 9][i % 9] = digit;
+        }
+        Sudoku::from_grid(grid)
+    }
+
+    /// Fails if a digit is out of range or the givens already break a rule
+    pub fn from_grid(grid: [[u8; 9]; 9]) -> Result<Sudoku, String> {
+        let mut sudoku = Sudoku {
+            cells: [[0; 9]; 9],
+            rows: [0; 9],
+            columns: [0; 9],
+            boxes: [0; 9],
+            empty: 81,
+        };
+        for (row, line) in grid.iter().enumerate() {
+            for (column, &digit) in line.iter().enumerate() {
+                if digit == 0 {
+                    continue;
+                }
+                if digit > 9 {
+                    return Err(format!("digit {digit} at row {row}, column {column}"));
+                }
+                if sudoku.options(row, column) & (1 << digit) == 0 {
+                    return Err(format!(
+                        "{digit} at row {row}, column {column} is already in its row, column or box"
+                    ));
+                }
+                sudoku.place(row, column, digit);
+            }
+        }
+        Ok(sudoku)
+    }
+
+    pub fn cells(&self) -> &[[u8; 9]; 9] {
+        &self.cells
+    }
+
+    pub fn is_solved(&self) -> bool {
+        self.empty == 0
+    }
+
+    /// Solutions of the puzzle, the filled in grids
+    pub fn solve(&self, mode: Mode) -> Search<Sudoku> {
+        search(&mut self.clone(), mode, Sudoku::clone)
+    }
+
+    /// Whether the puzzle has exactly one solution, searching no further than two
+    pub fn has_unique_solution(&self) -> bool {
+        self.solve(Mode::AtMost(2)).count() == 1
+    }
+
+    /// Digits still allowed in a cell, as bits 1 to 9
+    fn options(&self, row: usize, column: usize) -> u16 {
+        ALL_DIGITS & !(self.rows[row] | self.columns[column] | self.boxes[box_of(row, column)])
+    }
+
+    fn place(&mut self, row: usize, column: usize, digit: u8) {
+        let bit = 1 << digit;
+        self.cells[row][column] = digit;
+        self.rows[row] |= bit;
+        self.columns[column] |= bit;
+        self.boxes[box_of(row, column)] |= bit;
+        self.empty -= 1;
+    }
+
+    fn clear(&mut self, row: usize, column: usize) {
+        let bit = !(1 << self.cells[row][column]);
+        self.cells[row][column] = 0;
+        self.rows[row] &= bit;
+        self.columns[column] &= bit;
+        self.boxes[box_of(row, column)] &= bit;
+        self.empty += 1;
+    }
+
+    /// The empty cell with the fewest options and those options
+    fn most_constrained(&self) -> Option<(usize, usize, u16)> {
+        let mut best: Option<(usize, usize, u16)> = None;
+        for row in 0..9 {
+            for column in 0..9 {
+                if self.cells[row][column] != 0 {
+                    continue;
+                }
+                let options = self.options(row, column);
+                if best.is_none_or(|(_, _, fewest)| options.count_ones() < fewest.count_ones()) {
+                    best = Some((row, column, options));
+                    if options.count_ones() <= 1 {
+                        return best;
+                    }
+                }
+            }
+        }
+        best
+    }
+}
+
+/// Placing a digit in a cell: (row, column, digit)
+impl Backtrack for Sudoku {
+    type Choice = (usize, usize, u8);
+
+    fn is_solution(&self) -> bool {
+        self.is_solved()
+    }
+
+    fn candidates(&self) -> Vec<(usize, usize, u8)> {
+        let Some((row, column, options)) = self.most_constrained() else {
+            return Vec::new();
+        };
+        (1..=9)
+            .filter(|digit| options & (1 << digit) != 0)
+            .map(|digit| (row, column, digit))
+            .collect()
+    }
+
+    fn apply(&mut self, &(row, column, digit): &(usize, usize, u8)) {
+        self.place(row, column, digit);
+    }
+
+    fn undo(&mut self, &(row, column, _): &(usize, usize, u8)) {
+        self.clear(row, column);
+    }
+}
+
+impl Display for Sudoku {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (row, line) in self.cells.iter().enumerate() {
+            if row > 0 && row % 3 == 0 {
+                writeln!(f, "------+-------+------")?;
+            }
+            for (column, &digit) in line.iter().enumerate() {
+                if column > 0 && column % 3 == 0 {
+                    write!(f, "| ")?;
+                }
+                match digit {
+                    0 => write!(f, ".")?,
+                    _ => write!(f, "{digit}")?,
+                }
+                if column < 8 {
+                    write!(f, " ")?;
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PUZZLE: &str = "
+        53..7....
+        6..195...
+        .98....6.
+        8...6...3
+        4..8.3..1
+        7...2...6
+        .6....28.
+        ...419..5
+        ....8..79";
+
+    const SOLUTION: &str = "
+        534678912
+        672195348
+        198342567
+        859761423
+        426853791
+        713924856
+        961537284
+        287419635
+        345286179";
+
+    /// Every row, column and box holds 1 to 9
+    fn is_valid_solution(sudoku: &Sudoku) -> bool {
+        let cells = sudoku.cells();
+        let full = |digits: &mut dyn Iterator<Item = u8>| {
+            digits.fold(0u16, |mask, digit| mask | 1 << digit) == ALL_DIGITS
+        };
+        (0..9).all(|i| {
+            full(&mut (0..9).map(|j| cells[i][j]))
+                && full(&mut (0..9).map(|j| cells[j][i]))
+                && full(&mut (0..9).map(|j| cells[i / 3 * 3 + j / 3][i % 3 * 3 + j % 3]))
+        })
+    }
+
+    #[test]
+    fn test_solve() {
+        let puzzle = Sudoku::parse(PUZZLE).unwrap();
+        assert!(!puzzle.is_solved());
+        let search = puzzle.solve(Mode::First);
+        let solved = search.first().unwrap();
+        assert_eq!(solved, &Sudoku::parse(SOLUTION).unwrap());
+        assert!(is_valid_solution(solved));
+        assert!(puzzle.has_unique_solution());
+        // the givens are untouched
+        assert_eq!(puzzle.cells()[0][2], 0);
+    }
+
+    #[test]
+    fn test_hard_puzzle() {
+        // "AI Escargot", built to be hard for human solvers
+        let puzzle = Sudoku::parse(
+            "1....7.9..3..2...8..96..5....53..9...1..8...26....4...3......1..4......7..7...3..",
+        )
+        .unwrap();
+        let search = puzzle.solve(Mode::All);
+        assert_eq!(search.count(), 1);
+        assert!(is_valid_solution(&search.solutions[0]));
+    }
+
+    #[test]
+    fn test_several_or_no_solutions() {
+        let empty = Sudoku::parse(&".".repeat(81)).unwrap();
+        let some = empty.solve(Mode::AtMost(3));
+        assert_eq!(some.count(), 3);
+        assert!(some.stopped);
+        assert!(some.solutions.iter().all(is_valid_solution));
+        assert!(!empty.has_unique_solution());
+
+        // the givens follow the rules, but the top right cell can only be a 9 and its
+        // column already has one
+        let mut grid = [[0; 9]; 9];
+        grid[0][..8].copy_from_slice(&[1, 2, 3, 4, 5, 6, 7, 8]);
+        grid[4][8] = 9;
+        let stuck = Sudoku::from_grid(grid).unwrap();
+        let search = stuck.solve(Mode::All);
+        assert_eq!(search.count(), 0);
+        assert!(!search.stopped);
+        assert_eq!(search.nodes, 1);
+    }
+
+    #[test]
+    fn test_parse_errors_and_display() {
+        assert!(Sudoku::parse("123").unwrap_err().contains("81 cells"));
+        assert!(Sudoku::parse(&"x".repeat(81)).is_err());
+        let mut twice = ".".repeat(81);
+        twice.replace_range(0..2, "55");
+        assert!(Sudoku::parse(&twice).unwrap_err().contains("already"));
+
+        let puzzle = Sudoku::parse(PUZZLE).unwrap();
+        let shown = puzzle.to_string();
+        assert!(shown.starts_with("5 3 . | . 7 . | . . .\n"));
+        // the grid lines are skipped when reading it back
+        assert_eq!(Sudoku::parse(&shown).unwrap(), puzzle);
+    }
+}
@@ -0,0 +1,222 @@
+// Closest pair of points in the plane, O(n log n). Split the points (sorted by x) at the
+// median, find the closest pair on each side, then look across the dividing line: only
+// points within the best distance d of the line can do better, and going through them in
+// y order each needs comparing with the next few only (at most 7, the rest are more
+// than d above it). Each half comes back sorted by y, so the strip is a merge away.
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Point {
+    pub x: f64,
+    pub y: f64,
+}
+
+impl Point {
+    pub fn new(x: f64, y: f64) -> Self {
+        Point { x, y }
+    }
+
+    fn distance_squared(&self, other: &Point) -> f64 {
+        let (dx, dy) = (self.x - other.x, self.y - other.y);
+        dx * dx + dy * dy
+    }
+}
+
+/// The two closest points, by index into `points`, smaller index first
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ClosestPair {
+    pub first: usize,
+    pub second: usize,
+    pub distance: f64,
+}
+
+impl ClosestPair {
+    fn new(points: &[Point], i: usize, j: usize) -> Self {
+        ClosestPair {
+            first: i.min(j),
+            second: i.max(j),
+            distance: points[i].distance_squared(&points[j]),
+        }
+    }
+
+    fn closer(self, other: ClosestPair) -> ClosestPair {
+        if other.distance < self.distance {
+            other
+        } else {
+            self
+        }
+    }
+
+    /// `distance` holds the square until the end
+    fn finish(mut self) -> Self {
+        self.distance = self.distance.sqrt();
+        self
+    }
+}
+
+/// Every pair compared, O(n^2). `None` for fewer than two points.
+pub fn closest_pair_naive(points: &[Point]) -> Option<ClosestPair> {
+    let mut best: Option<ClosestPair> = None;
+    for i in 0..points.len() {
+        for j in i + 1..points.len() {
+            let pair = ClosestPair::new(points, i, j);
+            best = Some(best.map_or(pair, |best| best.closer(pair)));
+        }
+    }
+    best.map(ClosestPair::finish)
+}
+
+/// `None` for fewer than two points. Panics on a NaN coordinate.
+pub fn closest_pair(points: &[Point]) -> Option<ClosestPair> {
+    if points.len() < 2 {
+        return None;
+    }
+    let mut by_x: Vec<usize> = (0..points.len()).collect();
+    by_x.sort_by(|&i, &j| {
+        points[i]
+            .x
+            .partial_cmp(&points[j].x)
+            .expect("NaN coordinate")
+    });
+    Some(closest(points, &mut by_x).finish())
+}
+
+/// Closest pair among `indices` (sorted by x, at least two), which are left sorted by y
+fn closest(points: &[Point], indices: &mut [usize]) -> ClosestPair {
+    let n = indices.len();
+    if n <= 3 {
+        let mut best = ClosestPair::new(points, indices[0], indices[1]);
+        for i in 0..n {
+            for j in i + 1..n {
+                best = best.closer(ClosestPair::new(points, indices[i], indices[j]));
+            }
+        }
+        indices.sort_by(|&i, &j| points[i].y.total_cmp(&points[j].y));
+        return best;
+    }
+
+    let mid = n / 2;
+    let split_x = points[indices[mid]].x;
+    let (left, right) = indices.split_at_mut(mid);
+    let mut best = closest(points, left).closer(closest(points, right));
+
+    merge_by_y(points, indices, mid);
+
+    // points near the line, in y order
+    let strip: Vec<usize> = indices
+        .iter()
+        .copied()
+        .filter(|&i| {
+            let dx = points[i].x - split_x;
+            dx * dx < best.distance
+        })
+        .collect();
+    for (k, &i) in strip.iter().enumerate() {
+        for &j in &strip[k + 1..] {
+            let dy = points[j].y - points[i].y;
+            if dy * dy >= best.distance {
+                break;
+            }
+            best = best.closer(ClosestPair::new(points, i, j));
+        }
+    }
+    best
+}
+
+fn merge_by_y(points: &[Point], indices: &mut [usize], mid: usize) {
+    let mut merged = Vec::with_capacity(indices.len());
+    let (mut i, mut j) = (0, mid);
+    while i < mid && j < indices.len() {
+        if points[indices[j]].y < points[indices[i]].y {
+            merged.push(indices[j]);
+            j += 1;
+        } else {
+            merged.push(indices[i]);
+            i += 1;
+        }
+    }
+    merged.extend_from_slice(&indices[i..mid]);
+    merged.extend_from_slice(&indices[j..]);
+    indices.copy_from_slice(&merged);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algoritms::sorting::quick_sort::XorShift64;
+
+    fn random_points(n: usize, range: usize, rng: &mut XorShift64) -> Vec<Point> {
+        (0..n)
+            .map(|_| Point::new(rng.next_below(range) as f64, rng.next_below(range) as f64))
+            .collect()
+    }
+
+    #[test]
+    fn test_small() {
+        let points = [
+            Point::new(2.0, 3.0),
+            Point::new(12.0, 30.0),
+            Point::new(40.0, 50.0),
+            Point::new(5.0, 1.0),
+            Point::new(12.0, 10.0),
+            Point::new(3.0, 4.0),
+        ];
+        let pair = closest_pair(&points).unwrap();
+        assert_eq!((pair.first, pair.second), (0, 5));
+        assert_eq!(pair.distance, 2f64.sqrt());
+        assert_eq!(closest_pair_naive(&points), Some(pair));
+
+        assert_eq!(closest_pair(&points[..1]), None);
+        assert_eq!(closest_pair(&[]), None);
+        let same = [
+            Point::new(1.0, 1.0),
+            Point::new(7.0, 7.0),
+            Point::new(1.0, 1.0),
+        ];
+        assert_eq!(closest_pair(&same).unwrap().distance, 0.0);
+    }
+
+    #[test]
+    fn test_against_naive() {
+        let mut rng = XorShift64::new(54);
+        for n in [2, 3, 4, 5, 10, 50, 200, 1000] {
+            for range in [5, 1000, 1_000_000] {
+                let points = random_points(n, range, &mut rng);
+                let fast = closest_pair(&points).unwrap();
+                let naive = closest_pair_naive(&points).unwrap();
+                assert_eq!(fast.distance, naive.distance, "n = {n}, range = {range}");
+                assert_eq!(
+                    points[fast.first]
+                        .distance_squared(&points[fast.second])
+                        .sqrt(),
+                    fast.distance
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_lines() {
+        // on one vertical line every point is in the strip, on a horizontal one hardly any
+        let vertical: Vec<Point> = (0..500).map(|i| Point::new(0.0, (i * i) as f64)).collect();
+        assert_eq!(closest_pair(&vertical).unwrap().distance, 1.0);
+        let horizontal: Vec<Point> = (0..500).map(|i| Point::new((3 * i) as f64, 0.0)).collect();
+        let pair = closest_pair(&horizontal).unwrap();
+        assert_eq!(pair.distance, 3.0);
+        assert_eq!(closest_pair_naive(&horizontal).unwrap().distance, 3.0);
+    }
+
+    #[test]
+    fn test_many_points() {
+        let mut rng = XorShift64::new(55);
+        let points = random_points(100_000, 1 << 30, &mut rng);
+        let pair = closest_pair(&points).unwrap();
+        // a sample check: no point of a random subset is closer to any other
+        for i in (0..points.len()).step_by(997) {
+            for (j, other) in points.iter().enumerate() {
+                if i != j {
+                    assert!(points[i].distance_squared(other).sqrt() >= pair.distance);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,100 @@
+// Counting inversions, pairs i < j with list[i] > list[j], in O(n log n). Merge sort
+// the list: an inversion is either inside one half, counted by the recursion, or across
+// the halves, and those are counted while merging: whenever the next element comes from
+// the right half, it is smaller than everything still waiting in the left half.
+// The count says how far the list is from sorted: 0 when sorted, n(n-1)/2 reversed,
+// the number of swaps bubble or insertion sort would make.
+
+/// Every pair compared, O(n^2)
+pub fn count_inversions_naive<T: Ord>(list: &[T]) -> u64 {
+    let mut count = 0;
+    for i in 0..list.len() {
+        for j in i + 1..list.len() {
+            if list[i] > list[j] {
+                count += 1;
+            }
+        }
+    }
+    count
+}
+
+pub fn count_inversions<T: Ord + Clone>(list: &[T]) -> u64 {
+    fn sort_and_count<T: Ord + Clone>(list: &mut [T], buffer: &mut Vec<T>) -> u64 {
+        if list.len() < 2 {
+            return 0;
+        }
+        let mid = list.len() / 2;
+        let inside =
+            sort_and_count(&mut list[..mid], buffer) + sort_and_count(&mut list[mid..], buffer);
+        inside + merge_and_count(list, mid, buffer)
+    }
+
+    let mut sorted = list.to_vec();
+    let mut buffer = Vec::with_capacity(list.len() / 2 + 1);
+    sort_and_count(&mut sorted, &mut buffer)
+}
+
+/// Merges the sorted runs `list[..mid]` and `list[mid..]`, returning the pairs across them
+/// that were out of order. Ties go to the left run, equal elements are no inversion.
+fn merge_and_count<T: Ord + Clone>(list: &mut [T], mid: usize, buffer: &mut Vec<T>) -> u64 {
+    buffer.clear();
+    buffer.extend_from_slice(&list[..mid]);
+    let (mut i, mut j, mut k) = (0, mid, 0);
+    let mut count = 0;
+    while i < buffer.len() && j < list.len() {
+        if list[j] < buffer[i] {
+            list[k] = list[j].clone();
+            count += (buffer.len() - i) as u64;
+            j += 1;
+        } else {
+            list[k] = buffer[i].clone();
+            i += 1;
+        }
+        k += 1;
+    }
+    for value in &buffer[i..] {
+        list[k] = value.clone();
+        k += 1;
+    }
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algoritms::sorting::test_utils::random_values;
+
+    #[test]
+    fn test_examples() {
+        assert_eq!(count_inversions(&[2, 4, 1, 3, 5]), 3);
+        assert_eq!(count_inversions(&[1, 2, 3]), 0);
+        assert_eq!(count_inversions::<i32>(&[]), 0);
+        assert_eq!(count_inversions(&[2, 2, 1, 1]), 4);
+        let reversed: Vec<u32> = (0..1000).rev().collect();
+        assert_eq!(count_inversions(&reversed), 1000 * 999 / 2);
+        assert_eq!(count_inversions(&["whale", "ahab", "ishmael"]), 2);
+    }
+
+    #[test]
+    fn test_against_naive() {
+        for seed in 0..200 {
+            let len = (seed as usize * 7) % 60;
+            let list = random_values(len, 1 + seed % 20, seed + 1);
+            assert_eq!(
+                count_inversions(&list),
+                count_inversions_naive(&list),
+                "{list:?}"
+            );
+        }
+        let list = random_values(3000, 1_000_000, 58);
+        assert_eq!(count_inversions(&list), count_inversions_naive(&list));
+    }
+
+    #[test]
+    fn test_large() {
+        // a sorted list with the halves swapped: every pair across them is inverted
+        let n = 1_000_000u64;
+        let list: Vec<u64> = (n / 2..n).chain(0..n / 2).collect();
+        assert_eq!(count_inversions(&list), (n / 2) * (n / 2));
+    }
+}
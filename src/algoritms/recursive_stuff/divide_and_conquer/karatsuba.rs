@@ -0,0 +1,289 @@
+// Karatsuba multiplication of big natural numbers. Splitting both numbers in half,
+// a * b = z2 * B^2m + z1 * B^m + z0 needs the products of the high halves (z2) and the
+// low halves (z0), and z1 = (a1 + a0)(b1 + b0) - z2 - z0: three half size products
+// instead of four, so O(n^1.585) instead of the schoolbook O(n^2). Below a few dozen
+// limbs the schoolbook method is faster and takes over.
+//
+// Limbs are base 10^9, little-endian, so printing and parsing decimal is direct.
+
+use std::cmp::Ordering;
+use std::fmt::{self, Display};
+
+const BASE: u64 = 1_000_000_000;
+const BASE_DIGITS: usize = 9;
+/// Below this many limbs in the shorter factor, multiply the schoolbook way
+const THRESHOLD: usize = 32;
+
+/// A natural number of any size
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct BigNat {
+    /// base 10^9 digits, least significant first, no leading zero limbs (zero is empty)
+    limbs: Vec<u64>,
+}
+
+impl BigNat {
+    pub fn zero() -> Self {
+        BigNat { limbs: Vec::new() }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    /// Reads a string of decimal digits
+    pub fn parse(text: &str) -> Result<BigNat, String> {
+        if text.is_empty() || !text.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(format!("not a natural number: {text:?}"));
+        }
+        let mut limbs = Vec::with_capacity(text.len() / BASE_DIGITS + 1);
+        let mut end = text.len();
+        while end > 0 {
+            let start = end.saturating_sub(BASE_DIGITS);
+            limbs.push(text[start..end].parse().unwrap());
+            end = start;
+        }
+        Ok(BigNat::from_limbs(limbs))
+    }
+
+    fn from_limbs(mut limbs: Vec<u64>) -> Self {
+        trim(&mut limbs);
+        BigNat { limbs }
+    }
+
+    /// Number of decimal digits, 1 for zero
+    pub fn digits(&self) -> usize {
+        match self.limbs.last() {
+            None => 1,
+            Some(top) => (self.limbs.len() - 1) * BASE_DIGITS + top.to_string().len(),
+        }
+    }
+
+    /// The product by Karatsuba's method
+    pub fn mul_karatsuba(&self, other: &BigNat) -> BigNat {
+        BigNat::from_limbs(karatsuba(&self.limbs, &other.limbs))
+    }
+
+    /// The product the schoolbook way, every limb by every limb
+    pub fn mul_schoolbook(&self, other: &BigNat) -> BigNat {
+        BigNat::from_limbs(schoolbook(&self.limbs, &other.limbs))
+    }
+
+    pub fn add(&self, other: &BigNat) -> BigNat {
+        BigNat::from_limbs(add(&self.limbs, &other.limbs))
+    }
+}
+
+impl From<u64> for BigNat {
+    fn from(mut n: u64) -> Self {
+        let mut limbs = Vec::new();
+        while n > 0 {
+            limbs.push(n % BASE);
+            n /= BASE;
+        }
+        BigNat { limbs }
+    }
+}
+
+impl Display for BigNat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some((top, rest)) = self.limbs.split_last() else {
+            return write!(f, "0");
+        };
+        write!(f, "{top}")?;
+        for limb in rest.iter().rev() {
+            write!(f, "{limb:09}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Ord for BigNat {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.limbs
+            .len()
+            .cmp(&other.limbs.len())
+            .then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()))
+    }
+}
+
+impl PartialOrd for BigNat {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+fn trim(limbs: &mut Vec<u64>) {
+    while limbs.last() == Some(&0) {
+        limbs.pop();
+    }
+}
+
+fn add(a: &[u64], b: &[u64]) -> Vec<u64> {
+    let mut sum = a.to_vec();
+    add_shifted(&mut sum, b, 0);
+    sum
+}
+
+/// `target += value * BASE^shift`, growing `target` as needed
+fn add_shifted(target: &mut Vec<u64>, value: &[u64], shift: usize) {
+    if target.len() < shift + value.len() {
+        target.resize(shift + value.len(), 0);
+    }
+    let mut carry = 0;
+    let mut i = shift;
+    for &limb in value {
+        let sum = target[i] + limb + carry;
+        target[i] = sum % BASE;
+        carry = sum / BASE;
+        i += 1;
+    }
+    while carry > 0 {
+        if i == target.len() {
+            target.push(0);
+        }
+        let sum = target[i] + carry;
+        target[i] = sum % BASE;
+        carry = sum / BASE;
+        i += 1;
+    }
+}
+
+/// `target -= value`, which must not be larger
+fn sub_in_place(target: &mut Vec<u64>, value: &[u64]) {
+    let mut borrow = 0;
+    for (i, limb) in target.iter_mut().enumerate() {
+        if i >= value.len() && borrow == 0 {
+            break;
+        }
+        let subtrahend = value.get(i).copied().unwrap_or(0) + borrow;
+        if *limb >= subtrahend {
+            *limb -= subtrahend;
+            borrow = 0;
+        } else {
+            *limb = *limb + BASE - subtrahend;
+            borrow = 1;
+        }
+    }
+    debug_assert_eq!(borrow, 0, "subtracting a larger number");
+    trim(target);
+}
+
+fn schoolbook(a: &[u64], b: &[u64]) -> Vec<u64> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    let mut product = vec![0; a.len() + b.len()];
+    for (i, &x) in a.iter().enumerate() {
+        let mut carry = 0;
+        for (j, &y) in b.iter().enumerate() {
+            // < BASE^2 + 2 * BASE, fits in a u64
+            let current = product[i + j] + x * y + carry;
+            product[i + j] = current % BASE;
+            carry = current / BASE;
+        }
+        product[i + b.len()] = carry;
+    }
+    trim(&mut product);
+    product
+}
+
+fn karatsuba(a: &[u64], b: &[u64]) -> Vec<u64> {
+    if a.len().min(b.len()) < THRESHOLD {
+        return schoolbook(a, b);
+    }
+    let half = a.len().max(b.len()) / 2;
+    let (a0, a1) = a.split_at(half.min(a.len()));
+    let (b0, b1) = b.split_at(half.min(b.len()));
+
+    let z0 = karatsuba(a0, b0);
+    let z2 = karatsuba(a1, b1);
+    let mut z1 = karatsuba(&add(a0, a1), &add(b0, b1));
+    sub_in_place(&mut z1, &z0);
+    sub_in_place(&mut z1, &z2);
+
+    let mut product = z0;
+    add_shifted(&mut product, &z1, half);
+    add_shifted(&mut product, &z2, 2 * half);
+    trim(&mut product);
+    product
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algoritms::sorting::quick_sort::XorShift64;
+
+    fn random_number(digits: usize, rng: &mut XorShift64) -> BigNat {
+        let text: String = (0..digits)
+            .map(|_| char::from(b'0' + rng.next_below(10) as u8))
+            .collect();
+        BigNat::parse(&text).unwrap()
+    }
+
+    #[test]
+    fn test_parse_and_display() {
+        for text in [
+            "0",
+            "7",
+            "999999999",
+            "1000000000",
+            "123456789012345678901234567890",
+        ] {
+            assert_eq!(BigNat::parse(text).unwrap().to_string(), text);
+        }
+        assert_eq!(BigNat::parse("000120").unwrap().to_string(), "120");
+        assert!(BigNat::parse("").is_err());
+        assert!(BigNat::parse("12a").is_err());
+        assert_eq!(BigNat::from(u64::MAX).to_string(), u64::MAX.to_string());
+        assert_eq!(BigNat::parse("1000000000").unwrap().digits(), 10);
+        assert!(BigNat::parse("99").unwrap() < BigNat::parse("100").unwrap());
+    }
+
+    #[test]
+    fn test_small_products() {
+        let mut rng = XorShift64::new(50);
+        for _ in 0..200 {
+            let (x, y) = (rng.next_u64(), rng.next_u64() >> rng.next_below(64));
+            let expected = (x as u128 * y as u128).to_string();
+            let (a, b) = (BigNat::from(x), BigNat::from(y));
+            assert_eq!(a.mul_karatsuba(&b).to_string(), expected);
+            assert_eq!(a.mul_schoolbook(&b).to_string(), expected);
+        }
+        assert!(BigNat::zero().mul_karatsuba(&BigNat::from(5)).is_zero());
+    }
+
+    #[test]
+    fn test_against_schoolbook() {
+        let mut rng = XorShift64::new(51);
+        // past the threshold, balanced and lopsided, with runs of zero limbs
+        for (x_digits, y_digits) in [(300, 300), (1000, 999), (2000, 350), (5, 1500)] {
+            let a = random_number(x_digits, &mut rng);
+            let b = random_number(y_digits, &mut rng);
+            assert_eq!(a.mul_karatsuba(&b), a.mul_schoolbook(&b));
+            assert_eq!(a.mul_karatsuba(&b), b.mul_karatsuba(&a));
+        }
+        let power = BigNat::parse(&format!("1{}", "0".repeat(900))).unwrap();
+        let product = power.mul_karatsuba(&power);
+        assert_eq!(product.to_string(), format!("1{}", "0".repeat(1800)));
+        let nines = BigNat::parse(&"9".repeat(700)).unwrap();
+        // (10^700 - 1)^2 = 10^1400 - 2 * 10^700 + 1
+        let expected = format!("{}8{}1", "9".repeat(699), "0".repeat(699));
+        assert_eq!(nines.mul_karatsuba(&nines).to_string(), expected);
+    }
+
+    #[test]
+    fn test_factorial() {
+        let factorial = (1..=300u64).fold(BigNat::from(1), |product, n| {
+            product.mul_karatsuba(&BigNat::from(n))
+        });
+        assert_eq!(factorial.digits(), 615);
+        assert!(factorial.to_string().starts_with("30605751221644063603"));
+        // 300! ends in 74 zeros, one for each factor of 5
+        let text = factorial.to_string();
+        assert_eq!(text.len() - text.trim_end_matches('0').len(), 74);
+
+        let square = factorial.mul_karatsuba(&factorial);
+        assert_eq!(square, factorial.mul_schoolbook(&factorial));
+        assert_eq!(square.add(&BigNat::zero()), square);
+    }
+}
@@ -0,0 +1,194 @@
+// Maximum subarray: the non-empty run of consecutive elements with the largest sum.
+// - divide and conquer, O(n log n): the best run is in the left half, in the right half,
+//   or crosses the middle, and then it is the best run ending at the middle plus the
+//   best one starting there
+// - Kadane, O(n): the best run ending at i is either the element alone or the best run
+//   ending at i - 1 extended by it
+// Both keep the earliest, then shortest, run on a tie, like the naive version.
+
+use std::ops::Range;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Subarray {
+    pub range: Range<usize>,
+    pub sum: i64,
+}
+
+impl Subarray {
+    /// Larger sum first, then earlier start, then shorter
+    fn better_than(&self, other: &Subarray) -> bool {
+        (
+            self.sum,
+            std::cmp::Reverse(self.range.start),
+            std::cmp::Reverse(self.range.end),
+        ) > (
+            other.sum,
+            std::cmp::Reverse(other.range.start),
+            std::cmp::Reverse(other.range.end),
+        )
+    }
+
+    fn best(self, other: Subarray) -> Subarray {
+        if other.better_than(&self) {
+            other
+        } else {
+            self
+        }
+    }
+}
+
+/// Every run tried, O(n^2). `None` for an empty list.
+pub fn max_subarray_naive(list: &[i64]) -> Option<Subarray> {
+    let mut best: Option<Subarray> = None;
+    for start in 0..list.len() {
+        let mut sum = 0;
+        for end in start + 1..=list.len() {
+            sum += list[end - 1];
+            let run = Subarray {
+                range: start..end,
+                sum,
+            };
+            best = Some(best.map_or(run.clone(), |best| best.best(run)));
+        }
+    }
+    best
+}
+
+/// `None` for an empty list
+pub fn max_subarray(list: &[i64]) -> Option<Subarray> {
+    fn best_in(list: &[i64], range: Range<usize>) -> Subarray {
+        if range.len() == 1 {
+            return Subarray {
+                sum: list[range.start],
+                range,
+            };
+        }
+        let mid = range.start + range.len() / 2;
+        let left = best_in(list, range.start..mid);
+        let right = best_in(list, mid..range.end);
+
+        // the best run ending at mid, taking the earliest start on a tie
+        let (mut sum, mut best_sum, mut start) = (0, i64::MIN, mid);
+        for i in (range.start..mid).rev() {
+            sum += list[i];
+            if sum >= best_sum {
+                (best_sum, start) = (sum, i);
+            }
+        }
+        let left_sum = best_sum;
+        // and the best starting at mid, the shortest on a tie
+        let (mut sum, mut best_sum, mut end) = (0, i64::MIN, mid);
+        for (i, &value) in list.iter().enumerate().take(range.end).skip(mid) {
+            sum += value;
+            if sum > best_sum {
+                (best_sum, end) = (sum, i + 1);
+            }
+        }
+        let crossing = Subarray {
+            range: start..end,
+            sum: left_sum + best_sum,
+        };
+        left.best(crossing).best(right)
+    }
+
+    if list.is_empty() {
+        return None;
+    }
+    Some(best_in(list, 0..list.len()))
+}
+
+/// Kadane's algorithm. `None` for an empty list.
+pub fn max_subarray_kadane(list: &[i64]) -> Option<Subarray> {
+    let (&first, _) = list.split_first()?;
+    let mut best = Subarray {
+        range: 0..1,
+        sum: first,
+    };
+    // the best run ending at the current element
+    let mut current = best.clone();
+    for (i, &value) in list.iter().enumerate().skip(1) {
+        // starting over ties with extending, extending keeps the earlier start
+        if current.sum < 0 {
+            current = Subarray {
+                range: i..i + 1,
+                sum: value,
+            };
+        } else {
+            current.range.end = i + 1;
+            current.sum += value;
+        }
+        best = best.best(current.clone());
+    }
+    Some(best)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algoritms::sorting::quick_sort::XorShift64;
+
+    #[test]
+    fn test_examples() {
+        let list = [-2, 1, -3, 4, -1, 2, 1, -5, 4];
+        let expected = Subarray {
+            range: 3..7,
+            sum: 6,
+        };
+        assert_eq!(max_subarray(&list), Some(expected.clone()));
+        assert_eq!(max_subarray_kadane(&list), Some(expected.clone()));
+        assert_eq!(max_subarray_naive(&list), Some(expected));
+
+        // all negative: the largest element alone
+        let negative = [-8, -3, -6, -2, -5, -4];
+        for found in [max_subarray(&negative), max_subarray_kadane(&negative)] {
+            assert_eq!(
+                found,
+                Some(Subarray {
+                    range: 3..4,
+                    sum: -2
+                })
+            );
+        }
+        assert_eq!(max_subarray(&[]), None);
+        assert_eq!(max_subarray_kadane(&[]), None);
+    }
+
+    #[test]
+    fn test_ties() {
+        // [3] and [0, 3] and [3, 0, -3, 3] etc. all sum to 3
+        let list = [0, 3, 0, -3, 3];
+        let expected = Some(Subarray {
+            range: 0..2,
+            sum: 3,
+        });
+        assert_eq!(max_subarray_naive(&list), expected);
+        assert_eq!(max_subarray(&list), expected);
+        assert_eq!(max_subarray_kadane(&list), expected);
+    }
+
+    #[test]
+    fn test_against_naive() {
+        let mut rng = XorShift64::new(56);
+        for _ in 0..500 {
+            let len = 1 + rng.next_below(40);
+            let spread = 1 + rng.next_below(20);
+            let list: Vec<i64> = (0..len)
+                .map(|_| rng.next_below(2 * spread + 1) as i64 - spread as i64)
+                .collect();
+            let expected = max_subarray_naive(&list);
+            assert_eq!(max_subarray(&list), expected, "{list:?}");
+            assert_eq!(max_subarray_kadane(&list), expected, "{list:?}");
+        }
+    }
+
+    #[test]
+    fn test_large() {
+        let mut rng = XorShift64::new(57);
+        let list: Vec<i64> = (0..200_000)
+            .map(|_| rng.next_below(2001) as i64 - 1000)
+            .collect();
+        let fast = max_subarray_kadane(&list).unwrap();
+        assert_eq!(max_subarray(&list).unwrap(), fast);
+        assert_eq!(list[fast.range.clone()].iter().sum::<i64>(), fast.sum);
+    }
+}
@@ -0,0 +1,20 @@
+// Divide and conquer: split the problem, solve the parts recursively, combine the
+// answers. Each algorithm comes with the naive method it improves on, which the tests
+// check it against.
+// - karatsuba.rs: big natural numbers multiplied in O(n^1.585)
+// - strassen.rs: matrices multiplied in O(n^2.807)
+// - closest_pair.rs: the closest two of n points in O(n log n)
+// - max_subarray.rs: the run with the largest sum in O(n log n), and Kadane's O(n)
+// - inversions.rs: out of order pairs counted in O(n log n) by merge sort
+
+pub mod closest_pair;
+pub mod inversions;
+pub mod karatsuba;
+pub mod max_subarray;
+pub mod strassen;
+
+pub use closest_pair::{ClosestPair, Point, closest_pair, closest_pair_naive};
+pub use inversions::{count_inversions, count_inversions_naive};
+pub use karatsuba::BigNat;
+pub use max_subarray::{Subarray, max_subarray, max_subarray_kadane, max_subarray_naive};
+pub use strassen::Matrix;
@@ -0,0 +1,254 @@
+// Strassen's matrix multiplication. Cut both matrices into 2 x 2 blocks: the product
+// takes eight block products the obvious way, Strassen gets it from seven sums of
+// products, so O(n^2.807) instead of O(n^3). The extra additions only pay off for large
+// blocks, below `THRESHOLD` the naive triple loop takes over.
+// Matrices of any shape are padded with zeros to a square power of two first.
+
+use std::ops::{Add, Sub};
+
+const THRESHOLD: usize = 64;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Matrix {
+    rows: usize,
+    columns: usize,
+    /// row by row
+    data: Vec<i64>,
+}
+
+impl Matrix {
+    pub fn zeros(rows: usize, columns: usize) -> Self {
+        Matrix {
+            rows,
+            columns,
+            data: vec![0; rows * columns],
+        }
+    }
+
+    pub fn identity(n: usize) -> Self {
+        let mut matrix = Matrix::zeros(n, n);
+        for i in 0..n {
+            matrix[(i, i)] = 1;
+        }
+        matrix
+    }
+
+    /// Panics if the rows differ in length
+    pub fn from_rows(rows: &[Vec<i64>]) -> Self {
+        let columns = rows.first().map_or(0, Vec::len);
+        assert!(
+            rows.iter().all(|row| row.len() == columns),
+            "rows of different lengths"
+        );
+        Matrix {
+            rows: rows.len(),
+            columns,
+            data: rows.concat(),
+        }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    /// The product by the triple loop. Panics if the shapes don't fit.
+    pub fn mul_naive(&self, other: &Matrix) -> Matrix {
+        assert_eq!(self.columns, other.rows, "shapes don't fit");
+        let mut product = Matrix::zeros(self.rows, other.columns);
+        for i in 0..self.rows {
+            for k in 0..self.columns {
+                let a = self[(i, k)];
+                if a == 0 {
+                    continue;
+                }
+                for j in 0..other.columns {
+                    product[(i, j)] += a * other[(k, j)];
+                }
+            }
+        }
+        product
+    }
+
+    /// The product by Strassen's method. Panics if the shapes don't fit.
+    pub fn mul_strassen(&self, other: &Matrix) -> Matrix {
+        assert_eq!(self.columns, other.rows, "shapes don't fit");
+        let largest = self.rows.max(self.columns).max(other.columns);
+        let size = largest.next_power_of_two();
+        let product = strassen(&self.padded(size), &other.padded(size));
+        product.block(0, 0, self.rows, other.columns)
+    }
+
+    fn padded(&self, size: usize) -> Matrix {
+        let mut padded = Matrix::zeros(size, size);
+        for i in 0..self.rows {
+            for j in 0..self.columns {
+                padded[(i, j)] = self[(i, j)];
+            }
+        }
+        padded
+    }
+
+    /// The `rows` x `columns` block with its top left corner at (`row`, `column`)
+    fn block(&self, row: usize, column: usize, rows: usize, columns: usize) -> Matrix {
+        let mut block = Matrix::zeros(rows, columns);
+        for i in 0..rows {
+            let start = (row + i) * self.columns + column;
+            block.data[i * columns..(i + 1) * columns]
+                .copy_from_slice(&self.data[start..start + columns]);
+        }
+        block
+    }
+
+    fn set_block(&mut self, row: usize, column: usize, block: &Matrix) {
+        for i in 0..block.rows {
+            let start = (row + i) * self.columns + column;
+            self.data[start..start + block.columns]
+                .copy_from_slice(&block.data[i * block.columns..(i + 1) * block.columns]);
+        }
+    }
+}
+
+impl std::ops::Index<(usize, usize)> for Matrix {
+    type Output = i64;
+
+    fn index(&self, (row, column): (usize, usize)) -> &i64 {
+        &self.data[row * self.columns + column]
+    }
+}
+
+impl std::ops::IndexMut<(usize, usize)> for Matrix {
+    fn index_mut(&mut self, (row, column): (usize, usize)) -> &mut i64 {
+        &mut self.data[row * self.columns + column]
+    }
+}
+
+impl Add for &Matrix {
+    type Output = Matrix;
+
+    fn add(self, other: &Matrix) -> Matrix {
+        let data = self
+            .data
+            .iter()
+            .zip(&other.data)
+            .map(|(a, b)| a + b)
+            .collect();
+        Matrix { data, ..*self }
+    }
+}
+
+impl Sub for &Matrix {
+    type Output = Matrix;
+
+    fn sub(self, other: &Matrix) -> Matrix {
+        let data = self
+            .data
+            .iter()
+            .zip(&other.data)
+            .map(|(a, b)| a - b)
+            .collect();
+        Matrix { data, ..*self }
+    }
+}
+
+/// Both square with the same power of two size
+fn strassen(a: &Matrix, b: &Matrix) -> Matrix {
+    let n = a.rows;
+    if n <= THRESHOLD {
+        return a.mul_naive(b);
+    }
+    let h = n / 2;
+    let quarters = |m: &Matrix| {
+        [
+            m.block(0, 0, h, h),
+            m.block(0, h, h, h),
+            m.block(h, 0, h, h),
+            m.block(h, h, h, h),
+        ]
+    };
+    let [a11, a12, a21, a22] = quarters(a);
+    let [b11, b12, b21, b22] = quarters(b);
+
+    let m1 = strassen(&(&a11 + &a22), &(&b11 + &b22));
+    let m2 = strassen(&(&a21 + &a22), &b11);
+    let m3 = strassen(&a11, &(&b12 - &b22));
+    let m4 = strassen(&a22, &(&b21 - &b11));
+    let m5 = strassen(&(&a11 + &a12), &b22);
+    let m6 = strassen(&(&a21 - &a11), &(&b11 + &b12));
+    let m7 = strassen(&(&a12 - &a22), &(&b21 + &b22));
+
+    let mut product = Matrix::zeros(n, n);
+    product.set_block(0, 0, &(&(&(&m1 + &m4) - &m5) + &m7));
+    product.set_block(0, h, &(&m3 + &m5));
+    product.set_block(h, 0, &(&m2 + &m4));
+    product.set_block(h, h, &(&(&(&m1 - &m2) + &m3) + &m6));
+    product
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algoritms::sorting::quick_sort::XorShift64;
+
+    fn random_matrix(rows: usize, columns: usize, rng: &mut XorShift64) -> Matrix {
+        let mut matrix = Matrix::zeros(rows, columns);
+        for value in &mut matrix.data {
+            *value = rng.next_below(21) as i64 - 10;
+        }
+        matrix
+    }
+
+    #[test]
+    fn test_small() {
+        let a = Matrix::from_rows(&[vec![1, 2], vec![3, 4]]);
+        let b = Matrix::from_rows(&[vec![5, 6], vec![7, 8]]);
+        let expected = Matrix::from_rows(&[vec![19, 22], vec![43, 50]]);
+        assert_eq!(a.mul_naive(&b), expected);
+        assert_eq!(a.mul_strassen(&b), expected);
+
+        // 2 x 3 by 3 x 1
+        let a = Matrix::from_rows(&[vec![1, 0, 2], vec![-1, 3, 1]]);
+        let b = Matrix::from_rows(&[vec![3], vec![2], vec![1]]);
+        let product = a.mul_strassen(&b);
+        assert_eq!((product.rows(), product.columns()), (2, 1));
+        assert_eq!(product, Matrix::from_rows(&[vec![5], vec![4]]));
+    }
+
+    #[test]
+    fn test_against_naive() {
+        let mut rng = XorShift64::new(52);
+        // below, at and past the threshold, squares and odd shapes
+        for (n, m, p) in [
+            (5, 7, 3),
+            (64, 64, 64),
+            (65, 65, 65),
+            (128, 128, 128),
+            (100, 70, 130),
+        ] {
+            let a = random_matrix(n, m, &mut rng);
+            let b = random_matrix(m, p, &mut rng);
+            assert_eq!(
+                a.mul_strassen(&b),
+                a.mul_naive(&b),
+                "{n} x {m} by {m} x {p}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_identity() {
+        let mut rng = XorShift64::new(53);
+        let a = random_matrix(200, 200, &mut rng);
+        assert_eq!(a.mul_strassen(&Matrix::identity(200)), a);
+        assert_eq!(Matrix::identity(200).mul_strassen(&a), a);
+    }
+
+    #[test]
+    #[should_panic(expected = "shapes don't fit")]
+    fn test_shape_mismatch() {
+        Matrix::zeros(2, 3).mul_strassen(&Matrix::zeros(2, 3));
+    }
+}
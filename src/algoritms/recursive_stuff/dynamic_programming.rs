@@ -0,0 +1,915 @@
+// Dynamic programming: recursions whose subproblems overlap, solved once each. Every
+// problem comes twice, top-down (the recursion as written, with a memo table) and
+// bottom-up (the table filled in an order where every lookup is already done). Both
+// give the same optimal value; on ties they may pick different solutions. Where the
+// recursion is as deep as the amount itself (unbounded knapsack, coin change) the
+// top-down version keeps its pending subproblems on a Vec rather than the call stack.
+//
+// The recurrences are over suffixes (what is left from index i on), so the solution is
+// read off the finished table walking forwards: `*_walk` takes the table as a lookup
+// function and is shared by both versions.
+
+use std::fmt::{self, Display};
+
+// Longest common subsequence
+
+/// A longest sequence that is a subsequence of both `a` and `b`. O(n * m).
+pub fn lcs<T: Eq + Clone>(a: &[T], b: &[T]) -> Vec<T> {
+    // length[i][j]: lcs length of a[i..] and b[j..]
+    let mut length = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            length[i][j] = if a[i] == b[j] {
+                length[i + 1][j + 1] + 1
+            } else {
+                length[i + 1][j].max(length[i][j + 1])
+            };
+        }
+    }
+    lcs_walk(a, b, |i, j| length[i][j])
+}
+
+pub fn lcs_memoized<T: Eq + Clone>(a: &[T], b: &[T]) -> Vec<T> {
+    fn length<T: Eq>(
+        a: &[T],
+        b: &[T],
+        i: usize,
+        j: usize,
+        memo: &mut [Vec<Option<usize>>],
+    ) -> usize {
+        if i == a.len() || j == b.len() {
+            return 0;
+        }
+        if let Some(known) = memo[i][j] {
+            return known;
+        }
+        let result = if a[i] == b[j] {
+            length(a, b, i + 1, j + 1, memo) + 1
+        } else {
+            length(a, b, i + 1, j, memo).max(length(a, b, i, j + 1, memo))
+        };
+        memo[i][j] = Some(result);
+        result
+    }
+
+    let mut memo = vec![vec![None; b.len()]; a.len()];
+    lcs_walk(a, b, |i, j| length(a, b, i, j, &mut memo))
+}
+
+fn lcs_walk<T: Eq + Clone>(
+    a: &[T],
+    b: &[T],
+    mut length: impl FnMut(usize, usize) -> usize,
+) -> Vec<T> {
+    let mut result = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        if a[i] == b[j] {
+            result.push(a[i].clone());
+            i += 1;
+            j += 1;
+        } else if length(i + 1, j) >= length(i, j + 1) {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    result
+}
+
+// Edit distance
+
+/// One step of turning `a` into `b`, by index into `a` and `b`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditStep {
+    Match(usize, usize),
+    Substitute(usize, usize),
+    Delete(usize),
+    Insert(usize),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Alignment {
+    pub distance: usize,
+    pub steps: Vec<EditStep>,
+}
+
+/// Levenshtein distance with the edits that achieve it. O(n * m).
+pub fn edit_distance<T: Eq>(a: &[T], b: &[T]) -> Alignment {
+    let (n, m) = (a.len(), b.len());
+    // distance[i][j]: from a[i..] to b[j..]
+    let mut distance = vec![vec![0; m + 1]; n + 1];
+    for i in (0..=n).rev() {
+        for j in (0..=m).rev() {
+            distance[i][j] = if i == n {
+                m - j
+            } else if j == m {
+                n - i
+            } else if a[i] == b[j] {
+                distance[i + 1][j + 1]
+            } else {
+                1 + distance[i + 1][j + 1]
+                    .min(distance[i + 1][j])
+                    .min(distance[i][j + 1])
+            };
+        }
+    }
+    edit_walk(a, b, |i, j| distance[i][j])
+}
+
+pub fn edit_distance_memoized<T: Eq>(a: &[T], b: &[T]) -> Alignment {
+    fn distance<T: Eq>(
+        a: &[T],
+        b: &[T],
+        i: usize,
+        j: usize,
+        memo: &mut [Vec<Option<usize>>],
+    ) -> usize {
+        if i == a.len() {
+            return b.len() - j;
+        }
+        if j == b.len() {
+            return a.len() - i;
+        }
+        if let Some(known) = memo[i][j] {
+            return known;
+        }
+        let result = if a[i] == b[j] {
+            distance(a, b, i + 1, j + 1, memo)
+        } else {
+            1 + distance(a, b, i + 1, j + 1, memo)
+                .min(distance(a, b, i + 1, j, memo))
+                .min(distance(a, b, i, j + 1, memo))
+        };
+        memo[i][j] = Some(result);
+        result
+    }
+
+    let mut memo = vec![vec![None; b.len()]; a.len()];
+    edit_walk(a, b, |i, j| distance(a, b, i, j, &mut memo))
+}
+
+fn edit_walk<T: Eq>(
+    a: &[T],
+    b: &[T],
+    mut distance: impl FnMut(usize, usize) -> usize,
+) -> Alignment {
+    let total = distance(0, 0);
+    let mut steps = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() || j < b.len() {
+        let here = distance(i, j);
+        let step = if i == a.len() {
+            EditStep::Insert(j)
+        } else if j == b.len() {
+            EditStep::Delete(i)
+        } else if a[i] == b[j] {
+            EditStep::Match(i, j)
+        } else if distance(i + 1, j + 1) + 1 == here {
+            EditStep::Substitute(i, j)
+        } else if distance(i + 1, j) + 1 == here {
+            EditStep::Delete(i)
+        } else {
+            EditStep::Insert(j)
+        };
+        match step {
+            EditStep::Match(..) | EditStep::Substitute(..) => (i, j) = (i + 1, j + 1),
+            EditStep::Delete(_) => i += 1,
+            EditStep::Insert(_) => j += 1,
+        }
+        steps.push(step);
+    }
+    Alignment {
+        distance: total,
+        steps,
+    }
+}
+
+// Knapsack
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Item {
+    pub weight: usize,
+    pub value: u64,
+}
+
+/// What goes in the knapsack: indices into the items (repeated when an item is taken more
+/// than once, in increasing order) and their total value and weight
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Packing {
+    pub value: u64,
+    pub weight: usize,
+    pub items: Vec<usize>,
+}
+
+impl Packing {
+    fn new(items: &[Item], mut chosen: Vec<usize>) -> Self {
+        chosen.sort_unstable();
+        Packing {
+            value: chosen.iter().map(|&i| items[i].value).sum(),
+            weight: chosen.iter().map(|&i| items[i].weight).sum(),
+            items: chosen,
+        }
+    }
+}
+
+/// The most valuable set of items, each taken at most once, within `capacity`.
+/// O(n * capacity), pseudo-polynomial: fine for small capacities.
+pub fn knapsack_01(items: &[Item], capacity: usize) -> Packing {
+    let n = items.len();
+    // best[i][c]: the most value from items[i..] within capacity c
+    let mut best = vec![vec![0; capacity + 1]; n + 1];
+    for i in (0..n).rev() {
+        for c in 0..=capacity {
+            best[i][c] = best[i + 1][c];
+            if items[i].weight <= c {
+                best[i][c] = best[i][c].max(items[i].value + best[i + 1][c - items[i].weight]);
+            }
+        }
+    }
+    knapsack_01_walk(items, capacity, |i, c| best[i][c])
+}
+
+pub fn knapsack_01_memoized(items: &[Item], capacity: usize) -> Packing {
+    fn best(items: &[Item], i: usize, c: usize, memo: &mut [Vec<Option<u64>>]) -> u64 {
+        if i == items.len() {
+            return 0;
+        }
+        if let Some(known) = memo[i][c] {
+            return known;
+        }
+        let mut result = best(items, i + 1, c, memo);
+        if items[i].weight <= c {
+            result = result.max(items[i].value + best(items, i + 1, c - items[i].weight, memo));
+        }
+        memo[i][c] = Some(result);
+        result
+    }
+
+    let mut memo = vec![vec![None; capacity + 1]; items.len()];
+    knapsack_01_walk(items, capacity, |i, c| best(items, i, c, &mut memo))
+}
+
+fn knapsack_01_walk(
+    items: &[Item],
+    capacity: usize,
+    mut best: impl FnMut(usize, usize) -> u64,
+) -> Packing {
+    let mut chosen = Vec::new();
+    let mut c = capacity;
+    for (i, item) in items.iter().enumerate() {
+        // taking item i is what made the difference
+        if best(i, c) != best(i + 1, c) {
+            chosen.push(i);
+            c -= item.weight;
+        }
+    }
+    Packing::new(items, chosen)
+}
+
+/// Same as [`knapsack_01`], but any item can be taken any number of times. Panics on an
+/// item of weight 0, there would be no best packing.
+pub fn knapsack_unbounded(items: &[Item], capacity: usize) -> Packing {
+    assert_positive_weights(items);
+    // best[c]: the most value within capacity c
+    let mut best = vec![0; capacity + 1];
+    for c in 1..=capacity {
+        best[c] = items
+            .iter()
+            .filter(|item| item.weight <= c)
+            .map(|item| item.value + best[c - item.weight])
+            .max()
+            .unwrap_or(0);
+    }
+    knapsack_unbounded_walk(items, capacity, |c| best[c])
+}
+
+/// Top-down like the rest, but the recursion is as deep as `capacity` over the lightest
+/// weight, so it runs on [`memoized_on_stack`]
+pub fn knapsack_unbounded_memoized(items: &[Item], capacity: usize) -> Packing {
+    assert_positive_weights(items);
+    let fits = move |c: usize| items.iter().filter(move |item| item.weight <= c);
+    let mut memo = vec![None; capacity + 1];
+    knapsack_unbounded_walk(items, capacity, |c| {
+        memoized_on_stack(
+            c,
+            &mut memo,
+            |c| fits(c).map(move |item| c - item.weight),
+            |c, memo| {
+                fits(c)
+                    .map(|item| item.value + memo[c - item.weight].unwrap())
+                    .max()
+                    .unwrap_or(0)
+            },
+        )
+    })
+}
+
+/// Top-down memoization with the pending subproblems on a Vec instead of the call stack.
+/// `needs(n)` are the smaller subproblems `n` depends on, `solve(n, memo)` combines their
+/// answers once they are all in `memo`.
+fn memoized_on_stack<R: Clone, I: Iterator<Item = usize>>(
+    n: usize,
+    memo: &mut [Option<R>],
+    needs: impl Fn(usize) -> I,
+    solve: impl Fn(usize, &[Option<R>]) -> R,
+) -> R {
+    let mut pending = vec![n];
+    while let Some(&top) = pending.last() {
+        if memo[top].is_some() {
+            pending.pop();
+            continue;
+        }
+        let before = pending.len();
+        pending.extend(needs(top).filter(|&smaller| memo[smaller].is_none()));
+        if pending.len() == before {
+            memo[top] = Some(solve(top, memo));
+            pending.pop();
+        }
+    }
+    memo[n].clone().unwrap()
+}
+
+fn assert_positive_weights(items: &[Item]) {
+    assert!(
+        items.iter().all(|item| item.weight > 0),
+        "items must have a positive weight"
+    );
+}
+
+fn knapsack_unbounded_walk(
+    items: &[Item],
+    capacity: usize,
+    mut best: impl FnMut(usize) -> u64,
+) -> Packing {
+    let mut chosen = Vec::new();
+    let mut c = capacity;
+    loop {
+        let here = best(c);
+        let next = (0..items.len()).find(|&i| {
+            items[i].weight <= c
+                && items[i].value > 0
+                && items[i].value + best(c - items[i].weight) == here
+        });
+        let Some(i) = next else {
+            break;
+        };
+        chosen.push(i);
+        c -= items[i].weight;
+    }
+    Packing::new(items, chosen)
+}
+
+// Coin change
+
+/// The fewest coins adding up to `amount`, largest first, `None` if no combination does.
+/// Every coin can be used any number of times. O(amount * coins).
+pub fn coin_change(coins: &[u64], amount: u64) -> Option<Vec<u64>> {
+    assert_positive_coins(coins);
+    let amount = amount as usize;
+    // fewest[a]: the fewest coins for amount a
+    let mut fewest: Vec<Option<usize>> = vec![None; amount + 1];
+    fewest[0] = Some(0);
+    for a in 1..=amount {
+        fewest[a] = coins
+            .iter()
+            .filter(|&&coin| coin as usize <= a)
+            .filter_map(|&coin| fewest[a - coin as usize])
+            .min()
+            .map(|count| count + 1);
+    }
+    coin_change_walk(coins, amount, |a| fewest[a])
+}
+
+/// Runs on [`memoized_on_stack`], the recursion is `amount` over the smallest coin deep
+pub fn coin_change_memoized(coins: &[u64], amount: u64) -> Option<Vec<u64>> {
+    assert_positive_coins(coins);
+    let amount = amount as usize;
+    let fits = move |a: usize| {
+        coins
+            .iter()
+            .map(|&coin| coin as usize)
+            .filter(move |&coin| coin <= a)
+    };
+    let mut memo = vec![None; amount + 1];
+    memo[0] = Some(Some(0));
+    coin_change_walk(coins, amount, |a| {
+        memoized_on_stack(
+            a,
+            &mut memo,
+            |a| fits(a).map(move |coin| a - coin),
+            |a, memo| {
+                fits(a)
+                    .filter_map(|coin| memo[a - coin].unwrap())
+                    .min()
+                    .map(|count| count + 1)
+            },
+        )
+    })
+}
+
+fn assert_positive_coins(coins: &[u64]) {
+    assert!(coins.iter().all(|&coin| coin > 0), "coins must be positive");
+}
+
+fn coin_change_walk(
+    coins: &[u64],
+    amount: usize,
+    mut fewest: impl FnMut(usize) -> Option<usize>,
+) -> Option<Vec<u64>> {
+    let mut left = fewest(amount)?;
+    let mut a = amount;
+    let mut result = Vec::with_capacity(left);
+    while left > 0 {
+        let coin = coins
+            .iter()
+            .copied()
+            .find(|&coin| coin as usize <= a && fewest(a - coin as usize) == Some(left - 1))
+            .unwrap();
+        result.push(coin);
+        a -= coin as usize;
+        left -= 1;
+    }
+    result.sort_unstable_by(|x, y| y.cmp(x));
+    Some(result)
+}
+
+// Longest increasing subsequence
+
+/// A longest strictly increasing subsequence, the earliest one on a tie. O(n^2).
+pub fn longest_increasing_subsequence<T: Ord + Clone>(list: &[T]) -> Vec<T> {
+    // longest[i]: the longest increasing subsequence starting at list[i]
+    let mut longest = vec![1; list.len()];
+    for i in (0..list.len()).rev() {
+        for j in i + 1..list.len() {
+            if list[j] > list[i] {
+                longest[i] = longest[i].max(longest[j] + 1);
+            }
+        }
+    }
+    lis_walk(list, |i| longest[i])
+}
+
+pub fn longest_increasing_subsequence_memoized<T: Ord + Clone>(list: &[T]) -> Vec<T> {
+    fn longest<T: Ord>(list: &[T], i: usize, memo: &mut [Option<usize>]) -> usize {
+        if let Some(known) = memo[i] {
+            return known;
+        }
+        let mut result = 1;
+        for j in i + 1..list.len() {
+            if list[j] > list[i] {
+                result = result.max(longest(list, j, memo) + 1);
+            }
+        }
+        memo[i] = Some(result);
+        result
+    }
+
+    let mut memo = vec![None; list.len()];
+    lis_walk(list, |i| longest(list, i, &mut memo))
+}
+
+fn lis_walk<T: Ord + Clone>(list: &[T], mut longest: impl FnMut(usize) -> usize) -> Vec<T> {
+    let Some(mut i) = (0..list.len()).max_by_key(|&i| (longest(i), std::cmp::Reverse(i))) else {
+        return Vec::new();
+    };
+    let mut result = vec![list[i].clone()];
+    while longest(i) > 1 {
+        let wanted = longest(i) - 1;
+        i = (i + 1..list.len())
+            .find(|&j| list[j] > list[i] && longest(j) == wanted)
+            .unwrap();
+        result.push(list[i].clone());
+    }
+    result
+}
+
+// Matrix-chain multiplication
+
+/// How to bracket a chain of matrix products, matrices numbered from 0
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Parenthesization {
+    Matrix(usize),
+    Product(Box<Parenthesization>, Box<Parenthesization>),
+}
+
+impl Parenthesization {
+    /// Scalar multiplications this order takes, matrix `i` being `dims[i]` by `dims[i + 1]`
+    pub fn cost(&self, dims: &[usize]) -> u64 {
+        self.cost_and_range(dims).0
+    }
+
+    fn cost_and_range(&self, dims: &[usize]) -> (u64, usize, usize) {
+        match self {
+            Parenthesization::Matrix(i) => (0, *i, *i),
+            Parenthesization::Product(left, right) => {
+                let (left_cost, first, _) = left.cost_and_range(dims);
+                let (right_cost, split, last) = right.cost_and_range(dims);
+                let product = (dims[first] * dims[split] * dims[last + 1]) as u64;
+                (left_cost + right_cost + product, first, last)
+            }
+        }
+    }
+}
+
+impl Display for Parenthesization {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Parenthesization::Matrix(i) => write!(f, "A{i}"),
+            Parenthesization::Product(left, right) => write!(f, "({left} {right})"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MatrixChain {
+    pub cost: u64,
+    pub order: Parenthesization,
+}
+
+/// The cheapest order to multiply a chain of matrices, matrix `i` being `dims[i]` by
+/// `dims[i + 1]`. Panics on fewer than two dims (no matrices). O(n^3).
+pub fn matrix_chain_order(dims: &[usize]) -> MatrixChain {
+    assert!(dims.len() >= 2, "a chain needs at least one matrix");
+    let n = dims.len() - 1;
+    // cost[i][j]: cheapest way to multiply matrices i..=j
+    let mut cost = vec![vec![0u64; n]; n];
+    for len in 2..=n {
+        for i in 0..=n - len {
+            let j = i + len - 1;
+            cost[i][j] = (i..j)
+                .map(|k| cost[i][k] + cost[k + 1][j] + split_cost(dims, i, k, j))
+                .min()
+                .unwrap();
+        }
+    }
+    matrix_chain_walk(dims, |i, j| cost[i][j])
+}
+
+pub fn matrix_chain_order_memoized(dims: &[usize]) -> MatrixChain {
+    fn cost(dims: &[usize], i: usize, j: usize, memo: &mut [Vec<Option<u64>>]) -> u64 {
+        if i == j {
+            return 0;
+        }
+        if let Some(known) = memo[i][j] {
+            return known;
+        }
+        let mut result = u64::MAX;
+        for k in i..j {
+            let split =
+                cost(dims, i, k, memo) + cost(dims, k + 1, j, memo) + split_cost(dims, i, k, j);
+            result = result.min(split);
+        }
+        memo[i][j] = Some(result);
+        result
+    }
+
+    assert!(dims.len() >= 2, "a chain needs at least one matrix");
+    let n = dims.len() - 1;
+    let mut memo = vec![vec![None; n]; n];
+    matrix_chain_walk(dims, |i, j| cost(dims, i, j, &mut memo))
+}
+
+/// Multiplying the result of `i..=k` by the result of `k + 1..=j`
+fn split_cost(dims: &[usize], i: usize, k: usize, j: usize) -> u64 {
+    (dims[i] * dims[k + 1] * dims[j + 1]) as u64
+}
+
+fn matrix_chain_walk(dims: &[usize], mut cost: impl FnMut(usize, usize) -> u64) -> MatrixChain {
+    fn order(
+        dims: &[usize],
+        i: usize,
+        j: usize,
+        cost: &mut impl FnMut(usize, usize) -> u64,
+    ) -> Parenthesization {
+        if i == j {
+            return Parenthesization::Matrix(i);
+        }
+        let best = cost(i, j);
+        let k = (i..j)
+            .find(|&k| cost(i, k) + cost(k + 1, j) + split_cost(dims, i, k, j) == best)
+            .unwrap();
+        Parenthesization::Product(
+            Box::new(order(dims, i, k, cost)),
+            Box::new(order(dims, k + 1, j, cost)),
+        )
+    }
+
+    let n = dims.len() - 1;
+    MatrixChain {
+        cost: cost(0, n - 1),
+        order: order(dims, 0, n - 1, &mut cost),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algoritms::sorting::quick_sort::XorShift64;
+
+    fn random_list(len: usize, max: usize, rng: &mut XorShift64) -> Vec<u8> {
+        (0..len).map(|_| rng.next_below(max) as u8).collect()
+    }
+
+    fn is_subsequence<T: Eq>(sub: &[T], list: &[T]) -> bool {
+        let mut rest = list.iter();
+        sub.iter().all(|x| rest.any(|y| y == x))
+    }
+
+    /// Every subset of `0..n` as a bit mask
+    fn subsets(n: usize) -> impl Iterator<Item = Vec<usize>> {
+        (0u32..1 << n).map(move |mask| (0..n).filter(|&i| mask & (1 << i) != 0).collect())
+    }
+
+    #[test]
+    fn test_lcs() {
+        assert_eq!(lcs(b"ABCBDAB", b"BDCABA").len(), 4);
+        assert_eq!(lcs_memoized(b"AGGTAB", b"GXTXAYB"), b"GTAB");
+        assert!(lcs::<u8>(b"", b"abc").is_empty());
+
+        let mut rng = XorShift64::new(46);
+        for _ in 0..200 {
+            let a = random_list(rng.next_below(9), 3, &mut rng);
+            let b = random_list(rng.next_below(9), 3, &mut rng);
+            // the longest subset of a that is a subsequence of b
+            let expected = subsets(a.len())
+                .map(|keep| keep.iter().map(|&i| a[i]).collect::<Vec<u8>>())
+                .filter(|sub| is_subsequence(sub, &b))
+                .map(|sub| sub.len())
+                .max()
+                .unwrap();
+            for found in [lcs(&a, &b), lcs_memoized(&a, &b)] {
+                assert_eq!(found.len(), expected);
+                assert!(is_subsequence(&found, &a) && is_subsequence(&found, &b));
+            }
+        }
+    }
+
+    fn apply_steps(a: &[u8], b: &[u8], alignment: &Alignment) -> Vec<u8> {
+        let mut result = Vec::new();
+        let mut edits = 0;
+        for step in &alignment.steps {
+            match *step {
+                EditStep::Match(i, j) => {
+                    assert_eq!(a[i], b[j]);
+                    result.push(a[i]);
+                }
+                EditStep::Substitute(_, j) | EditStep::Insert(j) => {
+                    result.push(b[j]);
+                    edits += 1;
+                }
+                EditStep::Delete(_) => edits += 1,
+            }
+        }
+        assert_eq!(edits, alignment.distance);
+        result
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        let alignment = edit_distance(b"kitten", b"sitting");
+        assert_eq!(alignment.distance, 3);
+        assert_eq!(alignment.steps[0], EditStep::Substitute(0, 0));
+        assert_eq!(apply_steps(b"kitten", b"sitting", &alignment), b"sitting");
+
+        let mut rng = XorShift64::new(47);
+        for _ in 0..300 {
+            let a = random_list(rng.next_below(10), 3, &mut rng);
+            let b = random_list(rng.next_below(10), 3, &mut rng);
+            let expected = crate::algoritms::search::text::levenshtein(&a, &b);
+            for alignment in [edit_distance(&a, &b), edit_distance_memoized(&a, &b)] {
+                assert_eq!(alignment.distance, expected);
+                assert_eq!(apply_steps(&a, &b, &alignment), b);
+            }
+        }
+    }
+
+    fn random_items(n: usize, rng: &mut XorShift64) -> Vec<Item> {
+        (0..n)
+            .map(|_| Item {
+                weight: 1 + rng.next_below(10),
+                value: rng.next_below(20) as u64,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_knapsack_01() {
+        let items = [
+            Item {
+                weight: 10,
+                value: 60,
+            },
+            Item {
+                weight: 20,
+                value: 100,
+            },
+            Item {
+                weight: 30,
+                value: 120,
+            },
+        ];
+        let packing = knapsack_01(&items, 50);
+        assert_eq!(packing.value, 220);
+        assert_eq!(packing.items, [1, 2]);
+        assert_eq!(knapsack_01_memoized(&items, 50), packing);
+        assert_eq!(knapsack_01(&items, 5).items, []);
+
+        let mut rng = XorShift64::new(48);
+        for _ in 0..100 {
+            let items = random_items(rng.next_below(9), &mut rng);
+            let capacity = rng.next_below(30);
+            let expected = subsets(items.len())
+                .filter(|set| set.iter().map(|&i| items[i].weight).sum::<usize>() <= capacity)
+                .map(|set| set.iter().map(|&i| items[i].value).sum::<u64>())
+                .max()
+                .unwrap();
+            for packing in [
+                knapsack_01(&items, capacity),
+                knapsack_01_memoized(&items, capacity),
+            ] {
+                assert_eq!(packing.value, expected);
+                assert!(packing.weight <= capacity);
+                assert!(packing.items.windows(2).all(|w| w[0] < w[1]));
+            }
+        }
+    }
+
+    #[test]
+    fn test_knapsack_unbounded() {
+        let items = [
+            Item {
+                weight: 5,
+                value: 10,
+            },
+            Item {
+                weight: 10,
+                value: 30,
+            },
+            Item {
+                weight: 15,
+                value: 20,
+            },
+        ];
+        let packing = knapsack_unbounded(&items, 100);
+        assert_eq!(packing.value, 300);
+        assert_eq!(packing.items, [1; 10]);
+        assert_eq!(knapsack_unbounded_memoized(&items, 100).value, 300);
+
+        let mut rng = XorShift64::new(49);
+        for _ in 0..100 {
+            let items = random_items(1 + rng.next_below(4), &mut rng);
+            let capacity = rng.next_below(25);
+            // every way to fill the capacity, one item at a time
+            let mut best = vec![0; capacity + 1];
+            for c in 1..=capacity {
+                best[c] = best[c - 1];
+                for item in items.iter().filter(|item| item.weight <= c) {
+                    best[c] = best[c].max(best[c - item.weight] + item.value);
+                }
+            }
+            for packing in [
+                knapsack_unbounded(&items, capacity),
+                knapsack_unbounded_memoized(&items, capacity),
+            ] {
+                assert_eq!(packing.value, best[capacity]);
+                assert!(packing.weight <= capacity);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "positive weight")]
+    fn test_knapsack_unbounded_weightless_item() {
+        knapsack_unbounded(
+            &[Item {
+                weight: 0,
+                value: 1,
+            }],
+            10,
+        );
+    }
+
+    #[test]
+    fn test_coin_change() {
+        assert_eq!(
+            coin_change(&[1, 5, 10, 25], 63),
+            Some(vec![25, 25, 10, 1, 1, 1])
+        );
+        // greedy would take 4 + 1 + 1
+        assert_eq!(coin_change(&[1, 3, 4], 6), Some(vec![3, 3]));
+        assert_eq!(coin_change_memoized(&[1, 3, 4], 6), Some(vec![3, 3]));
+        assert_eq!(coin_change(&[2], 3), None);
+        assert_eq!(coin_change_memoized(&[5, 7], 1), None);
+        assert_eq!(coin_change(&[3], 0), Some(vec![]));
+
+        let mut rng = XorShift64::new(50);
+        for _ in 0..100 {
+            let coins: Vec<u64> = (0..1 + rng.next_below(3))
+                .map(|_| 1 + rng.next_below(9) as u64)
+                .collect();
+            let amount = rng.next_below(40) as u64;
+            let bottom_up = coin_change(&coins, amount);
+            let memoized = coin_change_memoized(&coins, amount);
+            assert_eq!(
+                bottom_up.as_ref().map(Vec::len),
+                memoized.as_ref().map(Vec::len)
+            );
+            for found in [bottom_up, memoized].into_iter().flatten() {
+                assert_eq!(found.iter().sum::<u64>(), amount);
+                assert!(found.iter().all(|coin| coins.contains(coin)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_memoized_deep_recursion() {
+        // one coin per level: 100_000 levels would be far past the call stack
+        let coins = coin_change_memoized(&[1], 100_000).unwrap();
+        assert_eq!(coins.len(), 100_000);
+        assert_eq!(
+            coin_change_memoized(&[3, 7], 100_001).map(|coins| coins.len()),
+            coin_change(&[3, 7], 100_001).map(|coins| coins.len())
+        );
+
+        let items = [
+            Item {
+                weight: 1,
+                value: 1,
+            },
+            Item {
+                weight: 5,
+                value: 6,
+            },
+        ];
+        let packing = knapsack_unbounded_memoized(&items, 100_003);
+        assert_eq!(packing.value, knapsack_unbounded(&items, 100_003).value);
+        assert_eq!(packing.value, 20_000 * 6 + 3);
+    }
+
+    #[test]
+    fn test_longest_increasing_subsequence() {
+        let list = [10, 9, 2, 5, 3, 7, 101, 18];
+        assert_eq!(longest_increasing_subsequence(&list), [2, 5, 7, 101]);
+        assert_eq!(
+            longest_increasing_subsequence_memoized(&list),
+            [2, 5, 7, 101]
+        );
+        assert!(longest_increasing_subsequence::<i32>(&[]).is_empty());
+        assert_eq!(longest_increasing_subsequence(&[3, 3, 3]), [3]);
+
+        let mut rng = XorShift64::new(51);
+        for _ in 0..100 {
+            let list = random_list(rng.next_below(12), 6, &mut rng);
+            let expected = subsets(list.len())
+                .filter(|set| set.windows(2).all(|w| list[w[0]] < list[w[1]]))
+                .map(|set| set.len())
+                .max()
+                .unwrap();
+            for found in [
+                longest_increasing_subsequence(&list),
+                longest_increasing_subsequence_memoized(&list),
+            ] {
+                assert_eq!(found.len(), expected);
+                assert!(found.windows(2).all(|w| w[0] < w[1]));
+                assert!(is_subsequence(&found, &list));
+            }
+        }
+    }
+
+    #[test]
+    fn test_matrix_chain_order() {
+        // CLRS 15.2: 30x35, 35x15, 15x5, 5x10, 10x20, 20x25
+        let dims = [30, 35, 15, 5, 10, 20, 25];
+        let chain = matrix_chain_order(&dims);
+        assert_eq!(chain.cost, 15125);
+        assert_eq!(chain.order.to_string(), "((A0 (A1 A2)) ((A3 A4) A5))");
+        assert_eq!(chain.order.cost(&dims), 15125);
+        assert_eq!(matrix_chain_order_memoized(&dims), chain);
+
+        let single = matrix_chain_order(&[4, 7]);
+        assert_eq!(single.cost, 0);
+        assert_eq!(single.order, Parenthesization::Matrix(0));
+
+        let mut rng = XorShift64::new(52);
+        for _ in 0..50 {
+            let dims: Vec<usize> = (0..2 + rng.next_below(7))
+                .map(|_| 1 + rng.next_below(20))
+                .collect();
+            let bottom_up = matrix_chain_order(&dims);
+            let memoized = matrix_chain_order_memoized(&dims);
+            assert_eq!(bottom_up.cost, memoized.cost);
+            assert_eq!(bottom_up.order.cost(&dims), bottom_up.cost);
+            assert_eq!(memoized.order.cost(&dims), memoized.cost);
+            // multiplying left to right is one of the orders, so it can't be cheaper
+            let left_to_right =
+                (1..dims.len() - 1).fold(Parenthesization::Matrix(0), |order, i| {
+                    Parenthesization::Product(
+                        Box::new(order),
+                        Box::new(Parenthesization::Matrix(i)),
+                    )
+                });
+            assert!(bottom_up.cost <= left_to_right.cost(&dims));
+        }
+    }
+}
@@ -0,0 +1,203 @@
+// The recursions in this module's parent, for any type instead of i32 and without the
+// panic on an empty list. Two shapes of recursion:
+// - `fold`: one element at a time from the left, for any step function. Written as tail
+//   calls on a `Trampoline`, so it works on iterators of any length.
+// - `reduce`: split the slice in half, reduce both halves, combine. Only right for an
+//   associative combine, but the recursion is just log2(n) deep.
+// `sum`, `product`, `count`, `max` and `min` are built on `reduce` for slices and on
+// `fold` for iterators.
+
+use std::ops::{Add, Mul};
+
+use super::trampoline::Trampoline;
+
+/// `f(...f(f(init, x0), x1)..., xn)`
+pub fn fold_iter<'a, I, A, F>(iter: I, init: A, f: F) -> A
+where
+    I: IntoIterator,
+    I::IntoIter: 'a,
+    A: 'a,
+    F: FnMut(A, I::Item) -> A + 'a,
+{
+    fn step<'a, I, A, F>(mut iter: I, acc: A, mut f: F) -> Trampoline<'a, A>
+    where
+        I: Iterator + 'a,
+        A: 'a,
+        F: FnMut(A, I::Item) -> A + 'a,
+    {
+        match iter.next() {
+            None => Trampoline::Done(acc),
+            Some(item) => {
+                let acc = f(acc, item);
+                Trampoline::more(move || step(iter, acc, f))
+            }
+        }
+    }
+    step(iter.into_iter(), init, f).run()
+}
+
+pub fn fold<'a, T, A: 'a>(list: &'a [T], init: A, f: impl FnMut(A, &'a T) -> A + 'a) -> A {
+    fold_iter(list, init, f)
+}
+
+/// Maps every element with `leaf` and combines the results pairwise, halving the slice at
+/// each level. `combine` must be associative. `None` for an empty slice.
+pub fn reduce<'a, T, R>(
+    list: &'a [T],
+    leaf: impl Fn(&'a T) -> R,
+    combine: impl Fn(R, R) -> R,
+) -> Option<R> {
+    fn halve<'a, T, R>(
+        list: &'a [T],
+        leaf: &impl Fn(&'a T) -> R,
+        combine: &impl Fn(R, R) -> R,
+    ) -> R {
+        match list {
+            [single] => leaf(single),
+            _ => {
+                let (left, right) = list.split_at(list.len() / 2);
+                combine(halve(left, leaf, combine), halve(right, leaf, combine))
+            }
+        }
+    }
+
+    if list.is_empty() {
+        return None;
+    }
+    Some(halve(list, &leaf, &combine))
+}
+
+pub fn sum<T: Copy + Add<Output = T> + From<u8>>(list: &[T]) -> T {
+    reduce(list, |&x| x, |a, b| a + b).unwrap_or(T::from(0))
+}
+
+pub fn product<T: Copy + Mul<Output = T> + From<u8>>(list: &[T]) -> T {
+    reduce(list, |&x| x, |a, b| a * b).unwrap_or(T::from(1))
+}
+
+pub fn count<T>(list: &[T]) -> usize {
+    reduce(list, |_| 1, |a, b| a + b).unwrap_or(0)
+}
+
+/// The largest element, the last of them on a tie like `Iterator::max`
+pub fn max<T: Ord>(list: &[T]) -> Option<&T> {
+    reduce(list, |x| x, |a, b| a.max(b))
+}
+
+/// The smallest element, the first of them on a tie like `Iterator::min`
+pub fn min<T: Ord>(list: &[T]) -> Option<&T> {
+    reduce(list, |x| x, |a, b| a.min(b))
+}
+
+pub fn sum_iter<'a, T>(iter: impl IntoIterator<Item = T> + 'a) -> T
+where
+    T: Add<Output = T> + From<u8> + 'a,
+{
+    fold_iter(iter, T::from(0), |a, b| a + b)
+}
+
+pub fn product_iter<'a, T>(iter: impl IntoIterator<Item = T> + 'a) -> T
+where
+    T: Mul<Output = T> + From<u8> + 'a,
+{
+    fold_iter(iter, T::from(1), |a, b| a * b)
+}
+
+pub fn count_iter<'a, T>(iter: impl IntoIterator<Item = T> + 'a) -> usize {
+    fold_iter(iter, 0, |counted, _| counted + 1)
+}
+
+pub fn max_iter<'a, T: Ord + 'a>(iter: impl IntoIterator<Item = T> + 'a) -> Option<T> {
+    fold_iter(iter, None, |largest: Option<T>, x| match largest {
+        None => Some(x),
+        Some(largest) => Some(largest.max(x)),
+    })
+}
+
+pub fn min_iter<'a, T: Ord + 'a>(iter: impl IntoIterator<Item = T> + 'a) -> Option<T> {
+    fold_iter(iter, None, |smallest: Option<T>, x| match smallest {
+        None => Some(x),
+        Some(smallest) => Some(smallest.min(x)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algoritms::sorting::quick_sort::XorShift64;
+
+    #[test]
+    fn test_agrees_with_std() {
+        let mut rng = XorShift64::new(48);
+        for len in [0, 1, 2, 3, 10, 101] {
+            let list: Vec<i64> = (0..len).map(|_| rng.next_below(20) as i64 - 10).collect();
+            assert_eq!(sum(&list), list.iter().sum::<i64>());
+            assert_eq!(product(&list), list.iter().product::<i64>());
+            assert_eq!(count(&list), len);
+            assert_eq!(max(&list), list.iter().max());
+            assert_eq!(min(&list), list.iter().min());
+
+            assert_eq!(sum_iter(list.iter().copied()), list.iter().sum::<i64>());
+            assert_eq!(product_iter(list.clone()), list.iter().product::<i64>());
+            assert_eq!(count_iter(&list), len);
+            assert_eq!(max_iter(&list), list.iter().max());
+            assert_eq!(min_iter(list.iter()), list.iter().min());
+        }
+    }
+
+    #[test]
+    fn test_empty() {
+        let empty: [u32; 0] = [];
+        assert_eq!(sum(&empty), 0);
+        assert_eq!(product(&empty), 1);
+        assert_eq!(count(&empty), 0);
+        assert_eq!(max(&empty), None);
+        assert_eq!(min_iter(empty), None);
+        assert_eq!(reduce(&empty, |&x| x, |a, b| a + b), None);
+    }
+
+    #[test]
+    fn test_other_types() {
+        assert_eq!(sum(&[0.5, 1.25, 2.0]), 3.75);
+        assert_eq!(product_iter([1.5f64, 2.0, 4.0]), 12.0);
+        assert_eq!(max(&["whale", "ahab", "starbuck"]), Some(&"whale"));
+        assert_eq!(
+            max_iter("call me ishmael".split(' ').map(str::len)),
+            Some(7)
+        );
+        // fold keeps the order, even for a step that doesn't commute
+        let words = ["call", "me", "ishmael"];
+        assert_eq!(
+            fold(&words, String::new(), |text, word| text + word + " "),
+            "call me ishmael "
+        );
+        assert_eq!(fold_iter(1..=5, 0, |a, b| a * 10 + b), 12345);
+    }
+
+    #[test]
+    fn test_ties() {
+        let pairs = [(1, 'a'), (3, 'b'), (3, 'c'), (0, 'd'), (0, 'e')];
+        let max_by_first = reduce(&pairs, |pair| pair, |a, b| if b.0 >= a.0 { b } else { a });
+        assert_eq!(max_by_first, Some(&(3, 'c')));
+        let keys: Vec<i32> = pairs.iter().map(|pair| pair.0).collect();
+        // max and min pick the same element as the std ones, by address
+        assert!(std::ptr::eq(
+            max(&keys).unwrap(),
+            keys.iter().max().unwrap()
+        ));
+        assert!(std::ptr::eq(
+            min(&keys).unwrap(),
+            keys.iter().min().unwrap()
+        ));
+    }
+
+    #[test]
+    fn test_million_elements() {
+        let list: Vec<u64> = (1..=1_000_000).collect();
+        assert_eq!(sum(&list), 500_000_500_000);
+        assert_eq!(sum_iter(list.iter().copied()), 500_000_500_000);
+        assert_eq!(count_iter(0..1_000_000), 1_000_000);
+        assert_eq!(max_iter(list.iter().rev()), Some(&1_000_000));
+        assert_eq!(fold(&list, 0u64, |total, &x| total + x % 2), 500_000);
+    }
+}
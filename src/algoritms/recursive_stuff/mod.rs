@@ -1,3 +1,12 @@
+// Recursion on lists, written out by hand for i32. The generic versions are in fold.rs,
+// stack-safe ones for long inputs in trampoline.rs.
+
+pub mod backtracking;
+pub mod divide_and_conquer;
+pub mod dynamic_programming;
+pub mod fold;
+pub mod trampoline;
+
 pub fn sum_recursive(input_list: &[i32]) -> i32 {
     if input_list.is_empty() {
         return 0;
@@ -0,0 +1,288 @@
+// Recursion without the call stack. Rust has no tail call elimination, so the functions
+// in recursive_stuff overflow the stack somewhere past a hundred thousand elements.
+// - `Trampoline`: a tail call returns the next step as a closure instead of making it,
+//   and `run` loops over the steps. Non-tail recursions get there with an accumulator.
+// - `fold_tree`: recursions with several calls per step, like most tree functions, keep
+//   their pending work on a heap allocated stack instead.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::data_structures::bst::BSTNode;
+
+/// A computation that is either finished or has one more step to take
+pub enum Trampoline<'a, T> {
+    Done(T),
+    More(Box<dyn FnOnce() -> Trampoline<'a, T> + 'a>),
+}
+
+impl<'a, T> Trampoline<'a, T> {
+    pub fn more(step: impl FnOnce() -> Trampoline<'a, T> + 'a) -> Self {
+        Trampoline::More(Box::new(step))
+    }
+
+    /// Takes steps until done, in constant stack space
+    pub fn run(self) -> T {
+        let mut current = self;
+        loop {
+            match current {
+                Trampoline::Done(result) => return result,
+                Trampoline::More(step) => current = step(),
+            }
+        }
+    }
+}
+
+pub fn sum_trampolined(input_list: &[i32]) -> i32 {
+    fn sum(list: &[i32], total: i32) -> Trampoline<'_, i32> {
+        match list {
+            [] => Trampoline::Done(total),
+            [first, rest @ ..] => Trampoline::more(move || sum(rest, total + first)),
+        }
+    }
+    sum(input_list, 0).run()
+}
+
+pub fn count_trampolined(input_list: &[i32]) -> i32 {
+    fn count(list: &[i32], counted: i32) -> Trampoline<'_, i32> {
+        match list {
+            [] => Trampoline::Done(counted),
+            [_, rest @ ..] => Trampoline::more(move || count(rest, counted + 1)),
+        }
+    }
+    count(input_list, 0).run()
+}
+
+/// The largest value, `None` for an empty list
+pub fn max_value_trampolined(input_list: &[i32]) -> Option<i32> {
+    fn max(list: &[i32], largest: i32) -> Trampoline<'_, i32> {
+        match list {
+            [] => Trampoline::Done(largest),
+            [first, rest @ ..] => Trampoline::more(move || max(rest, largest.max(*first))),
+        }
+    }
+    let (first, rest) = input_list.split_first()?;
+    Some(max(rest, *first).run())
+}
+
+/// Post-order fold of a tree: `combine` gets each node with the results for its children,
+/// in the order `children` returned them. Any depth, the pending nodes live on the heap.
+pub fn fold_tree<N, R>(
+    root: N,
+    mut children: impl FnMut(&N) -> Vec<N>,
+    mut combine: impl FnMut(N, Vec<R>) -> R,
+) -> R {
+    enum Frame<N> {
+        Enter(N),
+        Exit(N, usize),
+    }
+
+    let mut frames = vec![Frame::Enter(root)];
+    let mut results = Vec::new();
+    while let Some(frame) = frames.pop() {
+        match frame {
+            Frame::Enter(node) => {
+                let below = children(&node);
+                let count = below.len();
+                frames.push(Frame::Exit(node, count));
+                // reversed, so the first child is folded first
+                frames.extend(below.into_iter().rev().map(Frame::Enter));
+            }
+            Frame::Exit(node, count) => {
+                let folded = results.split_off(results.len() - count);
+                results.push(combine(node, folded));
+            }
+        }
+    }
+    results.pop().unwrap()
+}
+
+// Bst helpers
+
+type NodeRef<K, V> = Rc<RefCell<BSTNode<K, V>>>;
+
+fn bst_children<K, V>(node: &NodeRef<K, V>) -> Vec<NodeRef<K, V>> {
+    let borrowed = node.borrow();
+    borrowed
+        .left
+        .iter()
+        .chain(borrowed.right.iter())
+        .cloned()
+        .collect()
+}
+
+/// Number of nodes below and including `root`
+pub fn tree_size<K, V>(root: &Option<NodeRef<K, V>>) -> usize {
+    root.as_ref().map_or(0, |root| {
+        fold_tree(root.clone(), bst_children, |_, sizes| {
+            1 + sizes.iter().sum::<usize>()
+        })
+    })
+}
+
+/// Nodes on the longest path from `root` down, 0 for an empty tree
+pub fn tree_height<K, V>(root: &Option<NodeRef<K, V>>) -> usize {
+    root.as_ref().map_or(0, |root| {
+        fold_tree(root.clone(), bst_children, |_, heights| {
+            1 + heights.into_iter().max().unwrap_or(0)
+        })
+    })
+}
+
+pub fn tree_sum<K, V: Copy + std::iter::Sum>(root: &Option<NodeRef<K, V>>) -> V {
+    match root {
+        None => std::iter::empty().sum(),
+        Some(root) => fold_tree(root.clone(), bst_children, |node, sums| {
+            std::iter::once(node.borrow().value).chain(sums).sum()
+        }),
+    }
+}
+
+/// The keys in order, walking down the left spines with an explicit stack
+pub fn in_order_keys<K: Clone, V>(root: &Option<NodeRef<K, V>>) -> Vec<K> {
+    let mut keys = Vec::new();
+    let mut stack = Vec::new();
+    let mut current = root.clone();
+    loop {
+        while let Some(node) = current {
+            current = node.borrow().left.clone();
+            stack.push(node);
+        }
+        let Some(node) = stack.pop() else {
+            return keys;
+        };
+        let borrowed = node.borrow();
+        keys.push(borrowed.key.clone());
+        current = borrowed.right.clone();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algoritms::recursive_stuff::{count_recursive, max_value_recursive, sum_recursive};
+    use crate::algoritms::sorting::quick_sort::XorShift64;
+    use crate::data_structures::bst::Bst;
+
+    const MILLION: usize = 1_000_000;
+
+    #[test]
+    fn test_agrees_with_recursive() {
+        let mut rng = XorShift64::new(47);
+        for len in [1, 2, 10, 500] {
+            let list: Vec<i32> = (0..len).map(|_| rng.next_below(200) as i32 - 100).collect();
+            assert_eq!(sum_trampolined(&list), sum_recursive(&list));
+            assert_eq!(count_trampolined(&list), count_recursive(&list));
+            assert_eq!(
+                max_value_trampolined(&list),
+                Some(max_value_recursive(&list))
+            );
+        }
+        assert_eq!(sum_trampolined(&[]), 0);
+        assert_eq!(count_trampolined(&[]), 0);
+        assert_eq!(max_value_trampolined(&[]), None);
+    }
+
+    #[test]
+    fn test_million_elements() {
+        let list: Vec<i32> = (0..MILLION as i32).map(|i| i % 7 - 3).collect();
+        assert_eq!(sum_trampolined(&list), list.iter().sum::<i32>());
+        assert_eq!(count_trampolined(&list), MILLION as i32);
+        assert_eq!(max_value_trampolined(&list), Some(3));
+    }
+
+    #[test]
+    fn test_fold_tree() {
+        // the tree of divisions by two: n has children n / 2 and n / 2 - 1
+        let node_count = fold_tree(
+            20u32,
+            |&n| {
+                if n < 2 {
+                    vec![]
+                } else {
+                    vec![n / 2, n / 2 - 1]
+                }
+            },
+            |_, counts: Vec<usize>| 1 + counts.iter().sum::<usize>(),
+        );
+        fn count(n: u32) -> usize {
+            if n < 2 {
+                1
+            } else {
+                1 + count(n / 2) + count(n / 2 - 1)
+            }
+        }
+        assert_eq!(node_count, count(20));
+
+        // children come back in order
+        let order = fold_tree(
+            0,
+            |&n| if n == 0 { vec![1, 2, 3] } else { vec![] },
+            |n, below: Vec<String>| format!("{n}{}", below.concat()),
+        );
+        assert_eq!(order, "0123");
+    }
+
+    #[test]
+    fn test_tree_helpers() {
+        let mut bst = Bst::new();
+        for key in [50, 30, 70, 20, 40, 60, 80, 45] {
+            bst.insert(key, key as u64);
+        }
+        assert_eq!(tree_size(&bst.root), 8);
+        assert_eq!(tree_height(&bst.root), 4);
+        assert_eq!(tree_sum(&bst.root), 395);
+        assert_eq!(in_order_keys(&bst.root), [20, 30, 40, 45, 50, 60, 70, 80]);
+
+        let empty: Bst<i32, u64> = Bst::new();
+        assert_eq!(tree_size(&empty.root), 0);
+        assert_eq!(tree_height(&empty.root), 0);
+        assert_eq!(tree_sum(&empty.root), 0);
+        assert!(in_order_keys(&empty.root).is_empty());
+    }
+
+    #[test]
+    fn test_million_deep_tree() {
+        // what inserting sorted keys gives, built directly: every node a right child
+        let mut root = None;
+        for key in (0..MILLION).rev() {
+            root = Some(Rc::new(RefCell::new(BSTNode {
+                key,
+                value: 1u64,
+                left: None,
+                right: root,
+            })));
+        }
+        let mut bst = Bst { root };
+        assert_eq!(tree_size(&bst.root), MILLION);
+        assert_eq!(tree_height(&bst.root), MILLION);
+        assert_eq!(tree_sum(&bst.root), MILLION as u64);
+        let keys = in_order_keys(&bst.root);
+        assert_eq!(keys.len(), MILLION);
+        assert!(keys.iter().enumerate().all(|(i, &key)| i == key));
+
+        // and the tree's own operations walk all the way down too
+        assert_eq!(bst.search(&(MILLION - 1)), Some(1));
+        assert!(!bst.contains(&MILLION));
+        bst.insert(MILLION + 1, 2);
+        bst.insert(MILLION, 3);
+        bst.insert(MILLION + 2, 4);
+        assert_eq!(tree_height(&bst.root), MILLION + 2);
+        // two children, one child, the root, and a key that isn't there
+        bst.delete(&(MILLION + 1));
+        bst.delete(&(MILLION / 2));
+        bst.delete(&0);
+        bst.delete(&(MILLION + 5));
+        assert_eq!(bst.search(&MILLION), Some(3));
+        assert_eq!(bst.search(&(MILLION + 2)), Some(4));
+        assert!(!bst.contains(&(MILLION + 1)));
+        assert!(!bst.contains(&(MILLION / 2)));
+        assert!(!bst.contains(&0));
+        assert!(bst.contains(&(MILLION / 2 + 1)));
+        assert_eq!(tree_sum(&bst.root), MILLION as u64 + 5);
+        let expected: Vec<usize> = (1..MILLION + 3)
+            .filter(|&key| key != MILLION / 2 && key != MILLION + 1)
+            .collect();
+        assert_eq!(in_order_keys(&bst.root), expected);
+    }
+}
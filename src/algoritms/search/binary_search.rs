@@ -1,23 +1,82 @@
-/// Binary search
-pub fn binary_search(input_list: &[i32], target: i32) -> Option<i32> {
-    let mut low: usize = 0;
-    let mut high: usize = input_list.len();
-    let mut count = 0;
-
-    while low < high {
-        let mid = (low + high) / 2;
-        let result: i32 = input_list[mid];
-        count += 1;
-        println!("count is {count}");
-        if result == target {
-            return Some(input_list[mid]);
-        } else if result < target {
-            low = mid + 1
-        } else {
-            high = mid
-        }
+// Binary search over sorted slices. Everything is built on `partition_point`, which
+// finds where a predicate flips from true to false, so all of them return indices and
+// the first of several equal elements.
+
+use std::cmp::Ordering;
+use std::ops::Range;
+
+use super::monotone::first_true;
+
+/// Index of the first element for which `pred` is false. The slice must be partitioned:
+/// `pred` true for a prefix and false for the rest.
+pub fn partition_point<T>(input_list: &[T], mut pred: impl FnMut(&T) -> bool) -> usize {
+    first_true(0..input_list.len(), |i| !pred(&input_list[i])).unwrap_or(input_list.len())
+}
+
+/// Binary search with a comparator that says how each element orders against the target.
+/// `Ok` holds the index of the first match, `Err` the index where the target would be
+/// inserted to keep the slice sorted.
+pub fn binary_search_by<T>(
+    input_list: &[T],
+    mut compare: impl FnMut(&T) -> Ordering,
+) -> Result<usize, usize> {
+    let index = partition_point(input_list, |x| compare(x) == Ordering::Less);
+    match input_list.get(index) {
+        Some(x) if compare(x) == Ordering::Equal => Ok(index),
+        _ => Err(index),
     }
-    None
+}
+
+/// Binary search on a slice sorted by `key`
+pub fn binary_search_by_key<T, K: Ord>(
+    input_list: &[T],
+    target: &K,
+    mut key: impl FnMut(&T) -> K,
+) -> Result<usize, usize> {
+    binary_search_by(input_list, |x| key(x).cmp(target))
+}
+
+/// Index of the first element equal to `target`
+pub fn binary_search<T: Ord>(input_list: &[T], target: &T) -> Option<usize> {
+    binary_search_by(input_list, |x| x.cmp(target)).ok()
+}
+
+/// Where a search ended up and how many elements it looked at, for comparing strategies
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SearchOutcome {
+    /// index of an element equal to the target
+    pub index: Option<usize>,
+    /// number of positions probed, i.e. elements compared with the target
+    pub probes: usize,
+}
+
+/// [`binary_search`] that also counts its probes
+pub fn binary_search_counted<T: Ord>(input_list: &[T], target: &T) -> SearchOutcome {
+    let mut probes = 0;
+    let index = binary_search_by(input_list, |x| {
+        probes += 1;
+        x.cmp(target)
+    })
+    .ok();
+    SearchOutcome { index, probes }
+}
+
+/// Index of the first element `>= target`, the length if there is none
+pub fn lower_bound<T: Ord>(input_list: &[T], target: &T) -> usize {
+    partition_point(input_list, |x| x < target)
+}
+
+/// Index of the first element `> target`, the length if there is none
+pub fn upper_bound<T: Ord>(input_list: &[T], target: &T) -> usize {
+    partition_point(input_list, |x| x <= target)
+}
+
+/// The indices of every element equal to `target`, an empty range at the insertion point
+/// if there are none
+pub fn equal_range<T: Ord>(input_list: &[T], target: &T) -> Range<usize> {
+    let start = lower_bound(input_list, target);
+    let end = start + upper_bound(&input_list[start..], target);
+    start..end
 }
 
 #[cfg(test)]
@@ -27,38 +86,120 @@ mod tests {
     #[test]
     fn test_found_in_middle() {
         let list = vec![1, 2, 3, 4, 5];
-        assert_eq!(binary_search(&list, 3), Some(3))
+        assert_eq!(binary_search(&list, &3), Some(2))
     }
     #[test]
     fn test_found_at_start() {
         let list = vec![1, 2, 3, 4, 5];
-        assert_eq!(binary_search(&list, 1), Some(1))
+        assert_eq!(binary_search(&list, &1), Some(0))
     }
     #[test]
     fn test_found_at_end() {
         let list = vec![1, 2, 3, 4, 5];
-        assert_eq!(binary_search(&list, 5), Some(5))
+        assert_eq!(binary_search(&list, &5), Some(4))
     }
     #[test]
     fn test_found_at_not_found() {
         let list = vec![1, 2, 3, 4, 5];
-        assert_eq!(binary_search(&list, 10), None)
+        assert_eq!(binary_search(&list, &10), None)
     }
     #[test]
     fn test_found_one_element() {
         let list = vec![42];
-        assert_eq!(binary_search(&list, 42), Some(42))
+        assert_eq!(binary_search(&list, &42), Some(0))
     }
 
     #[test]
     fn test_found_one_element_not_found() {
         let list = vec![42];
-        assert_eq!(binary_search(&list, 1), None)
+        assert_eq!(binary_search(&list, &1), None)
     }
 
     #[test]
     fn test_found_at_empty_list() {
         let list = vec![];
-        assert_eq!(binary_search(&list, 1), None)
+        assert_eq!(binary_search(&list, &1), None)
+    }
+
+    #[test]
+    fn test_first_of_duplicates() {
+        let list = vec![1, 2, 2, 2, 3];
+        assert_eq!(binary_search(&list, &2), Some(1));
+    }
+
+    #[test]
+    fn test_generic_types() {
+        let list = vec!["ant", "bear", "cat", "dog"];
+        assert_eq!(binary_search(&list, &"cat"), Some(2));
+        assert_eq!(binary_search(&list, &"cow"), None);
+    }
+
+    #[test]
+    fn test_binary_search_by() {
+        let list = vec![1, 3, 5, 7];
+        assert_eq!(binary_search_by(&list, |x| x.cmp(&5)), Ok(2));
+        assert_eq!(binary_search_by(&list, |x| x.cmp(&4)), Err(2));
+        assert_eq!(binary_search_by(&list, |x| x.cmp(&0)), Err(0));
+        assert_eq!(binary_search_by(&list, |x| x.cmp(&8)), Err(4));
+        assert_eq!(binary_search_by(&[] as &[i32], |x| x.cmp(&8)), Err(0));
+    }
+
+    #[test]
+    fn test_binary_search_by_key() {
+        let people = vec![("ann", 19), ("bob", 25), ("cid", 25), ("dan", 40)];
+        assert_eq!(binary_search_by_key(&people, &25, |p| p.1), Ok(1));
+        assert_eq!(binary_search_by_key(&people, &30, |p| p.1), Err(3));
+    }
+
+    #[test]
+    fn test_bounds_and_equal_range() {
+        let list = vec![1, 2, 2, 2, 5, 8];
+        assert_eq!(lower_bound(&list, &2), 1);
+        assert_eq!(upper_bound(&list, &2), 4);
+        assert_eq!(equal_range(&list, &2), 1..4);
+
+        assert_eq!(lower_bound(&list, &3), 4);
+        assert_eq!(upper_bound(&list, &3), 4);
+        assert_eq!(equal_range(&list, &3), 4..4);
+
+        assert_eq!(equal_range(&list, &0), 0..0);
+        assert_eq!(equal_range(&list, &9), 6..6);
+        assert_eq!(equal_range(&list, &8), 5..6);
+    }
+
+    #[test]
+    fn test_partition_point() {
+        let list = vec![2, 4, 6, 7, 9];
+        assert_eq!(partition_point(&list, |x| x % 2 == 0), 3);
+        assert_eq!(partition_point(&list, |_| true), 5);
+        assert_eq!(partition_point(&list, |_| false), 0);
+    }
+
+    #[test]
+    fn test_binary_search_counted() {
+        let list: Vec<i32> = (0..1024).collect();
+        let outcome = binary_search_counted(&list, &700);
+        assert_eq!(outcome.index, Some(700));
+        // ten halvings plus the equality check at the end
+        assert_eq!(outcome.probes, 11);
+    }
+
+    #[test]
+    fn test_matches_std() {
+        let list: Vec<i32> = (0..200).map(|i| i / 3 * 2).collect();
+        for target in -1..140 {
+            assert_eq!(
+                lower_bound(&list, &target),
+                list.partition_point(|x| *x < target)
+            );
+            assert_eq!(
+                upper_bound(&list, &target),
+                list.partition_point(|x| *x <= target)
+            );
+            assert_eq!(
+                binary_search(&list, &target).is_some(),
+                list.binary_search(&target).is_ok()
+            );
+        }
     }
 }
@@ -0,0 +1,99 @@
+// Exponential (galloping) search: probe positions 0, 2, 6, 14, 30, .. until the target is
+// passed, then binary search the last gap. O(log i) for a match at index i, so it beats
+// binary search when the target is near the front, and it doesn't need to know the length.
+
+use std::cmp::Ordering;
+
+use super::binary_search::SearchOutcome;
+
+/// Exponential search over a sorted slice, finds the first match
+pub fn exponential_search<T: Ord>(input_list: &[T], target: &T) -> SearchOutcome {
+    exponential_search_by(|i| input_list.get(i).map(|x| x.cmp(target)))
+}
+
+/// Exponential search over a sorted sequence of unknown (or unbounded) length.
+/// `compare(i)` orders element `i` against the target, `None` means past the end.
+pub fn exponential_search_by(mut compare: impl FnMut(usize) -> Option<Ordering>) -> SearchOutcome {
+    let mut probes = 0;
+    let mut check = |i| {
+        probes += 1;
+        compare(i).unwrap_or(Ordering::Greater)
+    };
+
+    // everything before `low` is less than the target, `high - 1` is not
+    let mut low = 0;
+    let mut high = 1;
+    let mut at_high = check(0);
+    while at_high == Ordering::Less {
+        low = high;
+        high = high * 2 + 1;
+        at_high = check(high - 1);
+    }
+
+    // binary search the gap, remembering the ordering at the upper end
+    let mut high = high - 1;
+    while low < high {
+        let mid = low + (high - low) / 2;
+        let ordering = check(mid);
+        if ordering == Ordering::Less {
+            low = mid + 1;
+        } else {
+            high = mid;
+            at_high = ordering;
+        }
+    }
+
+    SearchOutcome {
+        index: (at_high == Ordering::Equal).then_some(low),
+        probes,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algoritms::search::binary_search::binary_search_counted;
+
+    #[test]
+    fn test_exponential_search_finds_everything() {
+        for len in 0..70 {
+            let list: Vec<i32> = (0..len).map(|i| i * 2).collect();
+            for target in -1..=2 * len {
+                let expected = list.binary_search(&target).ok();
+                assert_eq!(
+                    exponential_search(&list, &target).index,
+                    expected,
+                    "len {len}, target {target}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_first_of_duplicates() {
+        let list = vec![1, 4, 4, 4, 4, 4, 9];
+        assert_eq!(exponential_search(&list, &4).index, Some(1));
+    }
+
+    #[test]
+    fn test_near_the_front_is_cheap() {
+        let list: Vec<i32> = (0..1_000_000).collect();
+        let exponential = exponential_search(&list, &5);
+        let binary = binary_search_counted(&list, &5);
+        assert_eq!(exponential.index, Some(5));
+        assert!(exponential.probes < 8, "{} probes", exponential.probes);
+        assert!(binary.probes >= 20);
+    }
+
+    #[test]
+    fn test_unbounded_sequence() {
+        // smallest square >= 10^12, without ever knowing where the sequence ends
+        let target: u64 = 1_000_000_000_000;
+        let outcome = exponential_search_by(|i| Some((i as u64 * i as u64).cmp(&target)));
+        assert_eq!(outcome.index, Some(1_000_000));
+        assert!(outcome.probes < 50);
+
+        let outcome = exponential_search_by(|i| Some((i as u64 * i as u64).cmp(&(target + 1))));
+        assert_eq!(outcome.index, None);
+    }
+}
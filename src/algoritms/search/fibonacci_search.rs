@@ -0,0 +1,107 @@
+// Fibonacci search: like binary search, but the range is split at Fibonacci numbers
+// instead of halves, so finding the next probe only takes additions and subtractions.
+// Makes about 1.44 log2(n) probes in the worst case, a little more than binary search.
+
+use std::cmp::Ordering;
+
+use super::binary_search::SearchOutcome;
+
+/// Fibonacci search over a sorted slice. Any match is returned, not necessarily the first
+/// of several equal elements.
+pub fn fibonacci_search<T: Ord>(input_list: &[T], target: &T) -> SearchOutcome {
+    let len = input_list.len();
+    let mut probes = 0;
+
+    // smallest Fibonacci number >= len, with the two before it
+    let (mut fib_2, mut fib_1) = (0, 1);
+    let mut fib = fib_1 + fib_2;
+    while fib < len {
+        fib_2 = fib_1;
+        fib_1 = fib;
+        fib = fib_1 + fib_2;
+    }
+
+    // everything before `start` is less than the target, the window is `fib` long
+    let mut start = 0;
+    while fib > 1 {
+        let i = (start + fib_2).min(len) - 1;
+        probes += 1;
+        match input_list[i].cmp(target) {
+            Ordering::Equal => {
+                return SearchOutcome {
+                    index: Some(i),
+                    probes,
+                };
+            }
+            // drop the first fib_2 elements, the window shrinks by two Fibonacci steps
+            Ordering::Less => {
+                fib = fib_1;
+                fib_1 = fib_2;
+                fib_2 = fib - fib_1;
+                start = i + 1;
+            }
+            // keep the first fib_2 - 1 elements, one step
+            Ordering::Greater => {
+                fib = fib_2;
+                fib_1 -= fib_2;
+                fib_2 = fib - fib_1;
+            }
+        }
+    }
+
+    // one candidate left
+    let index = if fib_1 == 1 && start < len {
+        probes += 1;
+        (input_list[start] == *target).then_some(start)
+    } else {
+        None
+    };
+    SearchOutcome { index, probes }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algoritms::search::binary_search::binary_search_counted;
+
+    #[test]
+    fn test_fibonacci_search_finds_everything() {
+        for len in 0..100 {
+            let list: Vec<i32> = (0..len).map(|i| i * 3).collect();
+            for target in -1..=3 * len {
+                let expected = list.binary_search(&target).ok();
+                assert_eq!(
+                    fibonacci_search(&list, &target).index,
+                    expected,
+                    "len {len}, target {target}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_duplicates() {
+        let list = vec!["a", "b", "b", "b", "c"];
+        let index = fibonacci_search(&list, &"b").index.unwrap();
+        assert_eq!(list[index], "b");
+    }
+
+    #[test]
+    fn test_probe_count_close_to_binary_search() {
+        let list: Vec<i32> = (0..100_000).collect();
+        let (mut fibonacci, mut binary, mut worst) = (0, 0, 0);
+        for target in (0..100_000).step_by(97) {
+            let outcome = fibonacci_search(&list, &target);
+            assert_eq!(outcome.index, Some(target as usize));
+            fibonacci += outcome.probes;
+            worst = worst.max(outcome.probes);
+            binary += binary_search_counted(&list, &target).probes;
+        }
+        // log2(100 000) is about 16.6
+        assert!(worst <= 26, "worst case {worst}");
+        assert!(
+            fibonacci < binary * 3 / 2,
+            "fibonacci {fibonacci}, binary {binary}"
+        );
+    }
+}
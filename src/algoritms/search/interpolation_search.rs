@@ -0,0 +1,142 @@
+// Interpolation search: guess the position from the key's value, the way you'd open a
+// phone book. O(log log n) probes on uniformly distributed keys, but O(n) when the keys
+// are spread very unevenly (a few huge outliers, exponential growth).
+
+use std::cmp::Ordering;
+
+use super::binary_search::SearchOutcome;
+
+/// Interpolation search over sorted integer keys. Any match is returned, not necessarily
+/// the first of several equal keys. Only the guesses count as probes, not the checks
+/// that the target is still between both ends of the range.
+pub fn interpolation_search<T: Copy + Ord + Into<i128>>(
+    input_list: &[T],
+    target: &T,
+) -> SearchOutcome {
+    let mut probes = 0;
+    if input_list.is_empty() {
+        return SearchOutcome {
+            index: None,
+            probes,
+        };
+    }
+
+    let target_key: i128 = (*target).into();
+    let mut low = 0;
+    let mut high = input_list.len() - 1;
+
+    while low <= high && input_list[low] <= *target && *target <= input_list[high] {
+        let (low_key, high_key): (i128, i128) = (input_list[low].into(), input_list[high].into());
+        let guess = if high_key == low_key {
+            low
+        } else {
+            // in i128 the product of a 64 bit key difference and a slice length fits, i128
+            // keys can overflow it and then get the middle of the range instead
+            let offset = target_key
+                .checked_sub(low_key)
+                .and_then(|distance| distance.checked_mul((high - low) as i128))
+                .zip(high_key.checked_sub(low_key))
+                .map(|(scaled, span)| (scaled / span) as usize);
+            low + offset.unwrap_or((high - low) / 2)
+        };
+
+        probes += 1;
+        match input_list[guess].cmp(target) {
+            Ordering::Equal => {
+                return SearchOutcome {
+                    index: Some(guess),
+                    probes,
+                };
+            }
+            Ordering::Less => low = guess + 1,
+            Ordering::Greater if guess == 0 => break,
+            Ordering::Greater => high = guess - 1,
+        }
+    }
+
+    SearchOutcome {
+        index: None,
+        probes,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algoritms::search::binary_search::binary_search_counted;
+    use crate::algoritms::sorting::quick_sort::XorShift64;
+
+    #[test]
+    fn test_interpolation_search_finds_everything() {
+        for len in 0..50 {
+            let list: Vec<i64> = (0..len).map(|i| i * i).collect();
+            for target in -1..=len * len {
+                let found = interpolation_search(&list, &target).index;
+                match list.binary_search(&target) {
+                    Ok(_) => assert_eq!(found.map(|i| list[i]), Some(target)),
+                    Err(_) => assert_eq!(found, None, "len {len}, target {target}"),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_duplicates_and_extremes() {
+        let list = vec![3u32, 3, 3, 3];
+        assert_eq!(
+            interpolation_search(&list, &3).index.map(|i| list[i]),
+            Some(3)
+        );
+        assert_eq!(interpolation_search(&list, &4).index, None);
+
+        let list = vec![i64::MIN, -1, 0, i64::MAX];
+        assert_eq!(interpolation_search(&list, &i64::MAX).index, Some(3));
+        assert_eq!(interpolation_search(&list, &-1).index, Some(1));
+        assert_eq!(interpolation_search(&list, &5).index, None);
+    }
+
+    #[test]
+    fn test_i128_extremes() {
+        let list = [i128::MIN, 0, i128::MAX];
+        for (i, target) in list.iter().enumerate() {
+            assert_eq!(interpolation_search(&list, target).index, Some(i));
+        }
+        assert_eq!(interpolation_search(&list, &-7).index, None);
+        assert_eq!(interpolation_search(&list, &(i128::MAX - 1)).index, None);
+
+        let list: Vec<i128> = (-50..50).map(|i| i * (i128::MAX / 64)).collect();
+        for (i, target) in list.iter().enumerate() {
+            assert_eq!(interpolation_search(&list, target).index, Some(i));
+        }
+    }
+
+    #[test]
+    fn test_uniform_keys_need_few_probes() {
+        let mut rng = XorShift64::new(12);
+        let mut list: Vec<u64> = (0..1_000_000).map(|_| rng.next_u64() >> 16).collect();
+        list.sort();
+
+        let (mut interpolation, mut binary) = (0, 0);
+        for i in (0..list.len()).step_by(9973) {
+            let target = list[i];
+            let outcome = interpolation_search(&list, &target);
+            assert_eq!(outcome.index.map(|i| list[i]), Some(target));
+            interpolation += outcome.probes;
+            binary += binary_search_counted(&list, &target).probes;
+        }
+        assert!(
+            interpolation * 3 < binary,
+            "interpolation {interpolation}, binary {binary}"
+        );
+    }
+
+    #[test]
+    fn test_skewed_keys_degrade() {
+        // one huge key makes every guess land right next to the low end
+        let mut list: Vec<i64> = (0..1000).collect();
+        list.push(i64::MAX);
+        let outcome = interpolation_search(&list, &998);
+        assert_eq!(outcome.index, Some(998));
+        assert!(outcome.probes > 900, "{} probes", outcome.probes);
+    }
+}
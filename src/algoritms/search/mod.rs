@@ -1,3 +1,18 @@
 pub mod binary_search;
+pub mod exponential_search;
+pub mod fibonacci_search;
+pub mod interpolation_search;
+pub mod monotone;
+pub mod text;
 
-pub use binary_search::binary_search;
+pub use binary_search::{
+    SearchOutcome, binary_search, binary_search_by, binary_search_by_key, binary_search_counted,
+    equal_range, lower_bound, partition_point, upper_bound,
+};
+pub use exponential_search::{exponential_search, exponential_search_by};
+pub use fibonacci_search::fibonacci_search;
+pub use interpolation_search::interpolation_search;
+pub use monotone::{
+    Minimum, first_true, first_true_f64, golden_section_search, last_true, ternary_search,
+    unimodal_min_int,
+};
@@ -0,0 +1,323 @@
+// Searching a function instead of a slice.
+// - `first_true` / `last_true`: binary search for where a monotone predicate flips,
+//   over any integer range or, with a tolerance, over the reals
+// - ternary and golden-section search: the minimum of a unimodal function (decreasing,
+//   then increasing). Golden section reuses one of its two probes every step, so it needs
+//   about half the evaluations of ternary search.
+// - `unimodal_min_int`: the minimum over an integer range, found with `first_true` as
+//   the first x where f stops decreasing, so O(log n) like a binary search
+
+use std::ops::Range;
+
+/// Integers `first_true` can search over
+pub trait SearchInt: Copy + Ord {
+    /// The midpoint of `low..high`, rounded down and without overflowing
+    fn midpoint(low: Self, high: Self) -> Self;
+
+    fn successor(self) -> Self;
+
+    fn predecessor(self) -> Self;
+}
+
+macro_rules! search_int {
+    ($($t:ty),*) => {$(
+        impl SearchInt for $t {
+            fn midpoint(low: Self, high: Self) -> Self {
+                ((low as i128 + high as i128).div_euclid(2)) as $t
+            }
+
+            fn successor(self) -> Self {
+                self + 1
+            }
+
+            fn predecessor(self) -> Self {
+                self - 1
+            }
+        }
+    )*};
+}
+
+search_int!(i8, i16, i32, i64, u8, u16, u32, u64, usize, isize);
+
+/// Smallest `x` in `range` for which `pred(x)` is true, `None` if there is none.
+/// `pred` must be monotone: false up to some point and true from there on.
+pub fn first_true<T: SearchInt>(range: Range<T>, mut pred: impl FnMut(T) -> bool) -> Option<T> {
+    let mut low = range.start;
+    let mut high = range.end;
+
+    while low < high {
+        let mid = T::midpoint(low, high);
+        if pred(mid) {
+            high = mid
+        } else {
+            low = mid.successor()
+        }
+    }
+    (low < range.end).then_some(low)
+}
+
+/// Largest `x` in `range` for which `pred(x)` is true, `None` if there is none.
+/// `pred` must be true up to some point and false from there on.
+pub fn last_true<T: SearchInt>(range: Range<T>, mut pred: impl FnMut(T) -> bool) -> Option<T> {
+    if range.is_empty() {
+        return None;
+    }
+    match first_true(range.clone(), |x| !pred(x)) {
+        Some(first_false) if first_false == range.start => None,
+        Some(first_false) => Some(first_false.predecessor()),
+        None => Some(range.end.predecessor()),
+    }
+}
+
+/// Where a monotone predicate over the reals turns true, to within `tolerance`: the
+/// returned `x` has `pred(x)` true and `pred(x - tolerance)` false (or is `low`).
+/// `None` if `pred(high)` is false.
+pub fn first_true_f64(
+    low: f64,
+    high: f64,
+    tolerance: f64,
+    mut pred: impl FnMut(f64) -> bool,
+) -> Option<f64> {
+    assert!(
+        tolerance > 0.0,
+        "tolerance must be positive, got {tolerance}"
+    );
+    if !pred(high) {
+        return None;
+    }
+    if pred(low) {
+        return Some(low);
+    }
+
+    // pred(low) is false and pred(high) true from here on
+    let (mut low, mut high) = (low, high);
+    while high - low > tolerance {
+        let mid = low + (high - low) / 2.0;
+        // out of float precision before reaching the tolerance
+        if mid <= low || mid >= high {
+            break;
+        }
+        if pred(mid) { high = mid } else { low = mid }
+    }
+    Some(high)
+}
+
+/// The minimum a search found, and how many times it called the function
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Minimum {
+    pub x: f64,
+    pub value: f64,
+    pub evaluations: usize,
+}
+
+/// Minimum of a unimodal `f` on `[low, high]` to within `tolerance`, by cutting the
+/// interval in thirds and dropping the third that can't hold the minimum
+pub fn ternary_search(
+    low: f64,
+    high: f64,
+    tolerance: f64,
+    mut f: impl FnMut(f64) -> f64,
+) -> Minimum {
+    assert!(
+        tolerance > 0.0,
+        "tolerance must be positive, got {tolerance}"
+    );
+    let (mut low, mut high) = (low, high);
+    let mut evaluations = 0;
+
+    while high - low > tolerance {
+        let third = (high - low) / 3.0;
+        let (a, b) = (low + third, high - third);
+        // out of float precision before reaching the tolerance
+        if a <= low || b >= high {
+            break;
+        }
+        evaluations += 2;
+        if f(a) < f(b) { high = b } else { low = a }
+    }
+
+    let x = low + (high - low) / 2.0;
+    Minimum {
+        x,
+        value: f(x),
+        evaluations: evaluations + 1,
+    }
+}
+
+/// Same as [`ternary_search`] but the probes sit at the golden ratio, so the one that
+/// survives is exactly where the next step needs a probe
+pub fn golden_section_search(
+    low: f64,
+    high: f64,
+    tolerance: f64,
+    mut f: impl FnMut(f64) -> f64,
+) -> Minimum {
+    assert!(
+        tolerance > 0.0,
+        "tolerance must be positive, got {tolerance}"
+    );
+    // 1 / phi, about 0.618
+    let inverse_phi = (5f64.sqrt() - 1.0) / 2.0;
+    let (mut low, mut high) = (low, high);
+
+    let mut a = high - inverse_phi * (high - low);
+    let mut b = low + inverse_phi * (high - low);
+    let (mut f_a, mut f_b) = (f(a), f(b));
+    let mut evaluations = 2;
+
+    while high - low > tolerance {
+        // out of float precision before reaching the tolerance
+        if a <= low || b >= high {
+            break;
+        }
+        if f_a < f_b {
+            high = b;
+            (b, f_b) = (a, f_a);
+            a = high - inverse_phi * (high - low);
+            f_a = f(a);
+        } else {
+            low = a;
+            (a, f_a) = (b, f_b);
+            b = low + inverse_phi * (high - low);
+            f_b = f(b);
+        }
+        evaluations += 1;
+    }
+
+    let x = low + (high - low) / 2.0;
+    Minimum {
+        x,
+        value: f(x),
+        evaluations: evaluations + 1,
+    }
+}
+
+/// Index of the minimum of a unimodal function over an integer range, the first one if
+/// the minimum is a plateau at the bottom. `None` for an empty range. A binary search on
+/// `f(x) <= f(x + 1)`, two evaluations per step.
+pub fn unimodal_min_int<K: PartialOrd>(
+    range: Range<i64>,
+    mut f: impl FnMut(i64) -> K,
+) -> Option<i64> {
+    if range.is_empty() {
+        return None;
+    }
+    // for a unimodal f the minimum is the first x where f stops decreasing
+    first_true(range.start..range.end - 1, |x| f(x) <= f(x + 1)).or(Some(range.end - 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_true() {
+        assert_eq!(first_true(0..100, |x| x * x >= 50), Some(8));
+        assert_eq!(first_true(0..100, |_| true), Some(0));
+        assert_eq!(first_true(0..100, |_| false), None);
+        assert_eq!(first_true(5..5, |_| true), None);
+        assert_eq!(first_true(-50i32..50, |x| x >= -7), Some(-7));
+    }
+
+    #[test]
+    fn test_first_true_extreme_ranges() {
+        assert_eq!(
+            first_true(i64::MIN..i64::MAX, |x| x >= i64::MAX - 3),
+            Some(i64::MAX - 3)
+        );
+        assert_eq!(
+            first_true(0..u64::MAX, |x| x > 1 << 62),
+            Some((1 << 62) + 1)
+        );
+        assert_eq!(first_true(0u8..255, |x| x >= 254), Some(254));
+    }
+
+    #[test]
+    fn test_last_true() {
+        // largest x with x^2 <= 1000
+        assert_eq!(last_true(0..1000, |x| x * x <= 1000), Some(31));
+        assert_eq!(last_true(0..10, |_| true), Some(9));
+        assert_eq!(last_true(0..10, |_| false), None);
+        assert_eq!(last_true(3..3, |_| true), None);
+    }
+
+    #[test]
+    fn test_smallest_capacity() {
+        // classic "minimum ship capacity to deliver in d days": monotone in the capacity
+        let weights = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
+        let days_needed = |capacity: i32| {
+            let (mut days, mut load) = (1, 0);
+            for &w in &weights {
+                if load + w > capacity {
+                    days += 1;
+                    load = 0;
+                }
+                load += w;
+            }
+            days
+        };
+        assert_eq!(first_true(10..56, |c| days_needed(c) <= 5), Some(15));
+    }
+
+    #[test]
+    fn test_first_true_f64() {
+        let sqrt2 = first_true_f64(0.0, 2.0, 1e-12, |x| x * x >= 2.0).unwrap();
+        assert!((sqrt2 - 2f64.sqrt()).abs() < 1e-11);
+        assert!(sqrt2 * sqrt2 >= 2.0);
+
+        assert_eq!(first_true_f64(0.0, 1.0, 1e-9, |x| x > 5.0), None);
+        assert_eq!(first_true_f64(3.0, 4.0, 1e-9, |_| true), Some(3.0));
+
+        // tolerance below float resolution still terminates
+        let x = first_true_f64(1.0, 2.0, 1e-300, |x| x >= 1.5).unwrap();
+        assert_eq!(x, 1.5);
+    }
+
+    #[test]
+    fn test_ternary_and_golden_section() {
+        let f = |x: f64| (x - 1.3).powi(2) + 4.0;
+        for search in [ternary_search, golden_section_search] {
+            let minimum = search(-10.0, 10.0, 1e-9, f);
+            assert!((minimum.x - 1.3).abs() < 1e-6, "{minimum:?}");
+            assert!((minimum.value - 4.0).abs() < 1e-9);
+        }
+
+        let ternary = ternary_search(-10.0, 10.0, 1e-9, f);
+        let golden = golden_section_search(-10.0, 10.0, 1e-9, f);
+        assert!(
+            golden.evaluations < ternary.evaluations * 2 / 3,
+            "golden {}, ternary {}",
+            golden.evaluations,
+            ternary.evaluations
+        );
+    }
+
+    #[test]
+    fn test_minimum_below_float_resolution() {
+        // tolerance below float resolution still terminates
+        let f = |x: f64| (x - 1.5) * (x - 1.5);
+        for search in [ternary_search, golden_section_search] {
+            let minimum = search(1.0, 2.0, 1e-300, f);
+            assert!((minimum.x - 1.5).abs() < 1e-12, "{minimum:?}");
+        }
+    }
+
+    #[test]
+    fn test_minimum_at_the_edge() {
+        let minimum = golden_section_search(0.0, 5.0, 1e-9, |x| x);
+        assert!(minimum.x < 1e-6);
+        let minimum = ternary_search(0.0, 5.0, 1e-9, |x| -x);
+        assert!(minimum.x > 5.0 - 1e-6);
+    }
+
+    #[test]
+    fn test_unimodal_min_int() {
+        let values = [9, 7, 4, 2, 2, 3, 8, 20];
+        let f = |i: i64| values[i as usize];
+        assert_eq!(unimodal_min_int(0..8, f), Some(3));
+        assert_eq!(unimodal_min_int(0..1, f), Some(0));
+        assert_eq!(unimodal_min_int(0..0, f), None);
+        assert_eq!(unimodal_min_int(0..3, f), Some(2));
+        assert_eq!(unimodal_min_int(-100..100, |x| (x - 17).abs()), Some(17));
+    }
+}
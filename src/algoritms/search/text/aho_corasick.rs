@@ -0,0 +1,599 @@
+// Aho–Corasick: a trie of all the patterns plus failure links (the longest proper suffix
+// of the current state that is also in the trie), so the text is scanned once for every
+// pattern together. O(n + total pattern length + number of matches).
+//
+// The scan keeps only the automaton state between bytes, so `Searcher` can be fed a
+// stream chunk by chunk and `stream` runs it over any `Read`.
+
+use std::collections::VecDeque;
+use std::io::{self, Read};
+
+/// Which matches to report
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MatchKind {
+    /// every occurrence of every pattern, even when they overlap
+    #[default]
+    Overlapping,
+    /// non-overlapping, scanning left to right: the match that starts first, and of those
+    /// the longest (the lowest pattern index on a tie)
+    LeftmostLongest,
+}
+
+/// A match of pattern number `pattern` at the bytes `start..end`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Match {
+    pub pattern: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+const ROOT: usize = 0;
+
+#[derive(Debug, Default)]
+struct State {
+    /// sorted by byte
+    transitions: Vec<(u8, usize)>,
+    fail: usize,
+    /// patterns that end exactly here
+    outputs: Vec<usize>,
+    /// nearest state on the failure chain that has outputs of its own
+    output_link: Option<usize>,
+    depth: usize,
+}
+
+/// Builder for [`AhoCorasick`], defaults to overlapping and case-sensitive matching
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AhoCorasickBuilder {
+    match_kind: MatchKind,
+    case_insensitive: bool,
+}
+
+impl AhoCorasickBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn match_kind(mut self, match_kind: MatchKind) -> Self {
+        self.match_kind = match_kind;
+        self
+    }
+
+    /// Fold ASCII letters, other bytes still have to match exactly
+    pub fn case_insensitive(mut self, yes: bool) -> Self {
+        self.case_insensitive = yes;
+        self
+    }
+
+    /// Empty patterns are kept (so pattern indices line up) but never match
+    pub fn build<P: AsRef<[u8]>>(self, patterns: impl IntoIterator<Item = P>) -> AhoCorasick {
+        let mut automaton = AhoCorasick {
+            states: vec![State::default()],
+            pattern_lengths: Vec::new(),
+            match_kind: self.match_kind,
+            case_insensitive: self.case_insensitive,
+        };
+        for pattern in patterns {
+            automaton.insert(pattern.as_ref());
+        }
+        automaton.link_failures();
+        automaton
+    }
+}
+
+#[derive(Debug)]
+pub struct AhoCorasick {
+    states: Vec<State>,
+    pattern_lengths: Vec<usize>,
+    match_kind: MatchKind,
+    case_insensitive: bool,
+}
+
+impl AhoCorasick {
+    /// Overlapping, case-sensitive automaton for `patterns`
+    pub fn new<P: AsRef<[u8]>>(patterns: impl IntoIterator<Item = P>) -> Self {
+        AhoCorasickBuilder::new().build(patterns)
+    }
+
+    pub fn builder() -> AhoCorasickBuilder {
+        AhoCorasickBuilder::new()
+    }
+
+    pub fn pattern_count(&self) -> usize {
+        self.pattern_lengths.len()
+    }
+
+    pub fn state_count(&self) -> usize {
+        self.states.len()
+    }
+
+    /// Every match in `text`, ordered by end position (overlapping) or start position
+    /// (leftmost-longest)
+    pub fn find_all(&self, text: &[u8]) -> Vec<Match> {
+        let mut matches = Vec::new();
+        let mut searcher = self.searcher();
+        searcher.feed(text, |m| matches.push(m));
+        searcher.finish(|m| matches.push(m));
+        matches
+    }
+
+    /// Scans everything `reader` produces, calling `on_match` as matches are found.
+    /// Offsets count from the start of the stream.
+    pub fn stream<R: Read>(
+        &self,
+        mut reader: R,
+        mut on_match: impl FnMut(Match),
+    ) -> io::Result<()> {
+        let mut buffer = vec![0; 64 * 1024];
+        let mut searcher = self.searcher();
+        loop {
+            let read = match reader.read(&mut buffer) {
+                Ok(0) => break,
+                Ok(read) => read,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            };
+            searcher.feed(&buffer[..read], &mut on_match);
+        }
+        searcher.finish(on_match);
+        Ok(())
+    }
+
+    /// Incremental search, for feeding the text in pieces
+    pub fn searcher(&self) -> Searcher<'_> {
+        Searcher {
+            automaton: self,
+            state: ROOT,
+            position: 0,
+            candidates: Vec::new(),
+            resume_at: 0,
+        }
+    }
+
+    fn insert(&mut self, pattern: &[u8]) {
+        let id = self.pattern_lengths.len();
+        self.pattern_lengths.push(pattern.len());
+        if pattern.is_empty() {
+            return;
+        }
+
+        let mut state = ROOT;
+        for &byte in pattern {
+            let byte = self.fold(byte);
+            state = match self.goto(state, byte) {
+                Some(next) => next,
+                None => {
+                    let next = self.states.len();
+                    self.states.push(State {
+                        depth: self.states[state].depth + 1,
+                        ..State::default()
+                    });
+                    let transitions = &mut self.states[state].transitions;
+                    let at = transitions.partition_point(|&(b, _)| b < byte);
+                    transitions.insert(at, (byte, next));
+                    next
+                }
+            };
+        }
+        self.states[state].outputs.push(id);
+    }
+
+    /// Breadth first, so a state's failure target is always done before the state
+    fn link_failures(&mut self) {
+        let mut queue = VecDeque::new();
+        for &(_, child) in &self.states[ROOT].transitions {
+            queue.push_back(child);
+        }
+
+        while let Some(state) = queue.pop_front() {
+            for i in 0..self.states[state].transitions.len() {
+                let (byte, child) = self.states[state].transitions[i];
+                queue.push_back(child);
+
+                let mut fail = self.states[state].fail;
+                let target = loop {
+                    if let Some(next) = self.goto(fail, byte) {
+                        break next;
+                    }
+                    if fail == ROOT {
+                        break ROOT;
+                    }
+                    fail = self.states[fail].fail;
+                };
+
+                let output_link = if self.states[target].outputs.is_empty() {
+                    self.states[target].output_link
+                } else {
+                    Some(target)
+                };
+                let child = &mut self.states[child];
+                child.fail = target;
+                child.output_link = output_link;
+            }
+        }
+    }
+
+    fn goto(&self, state: usize, byte: u8) -> Option<usize> {
+        let transitions = &self.states[state].transitions;
+        transitions
+            .binary_search_by_key(&byte, |&(b, _)| b)
+            .ok()
+            .map(|i| transitions[i].1)
+    }
+
+    fn next_state(&self, mut state: usize, byte: u8) -> usize {
+        loop {
+            if let Some(next) = self.goto(state, byte) {
+                return next;
+            }
+            if state == ROOT {
+                return ROOT;
+            }
+            state = self.states[state].fail;
+        }
+    }
+
+    fn fold(&self, byte: u8) -> u8 {
+        if self.case_insensitive {
+            byte.to_ascii_lowercase()
+        } else {
+            byte
+        }
+    }
+}
+
+/// A scan in progress, see [`AhoCorasick::searcher`]
+pub struct Searcher<'a> {
+    automaton: &'a AhoCorasick,
+    state: usize,
+    /// bytes fed so far
+    position: usize,
+    /// leftmost-longest: matches found but not yet known to be leftmost-longest
+    candidates: Vec<Match>,
+    /// leftmost-longest: matches must start here or later
+    resume_at: usize,
+}
+
+impl Searcher<'_> {
+    /// Scans the next piece of the text
+    pub fn feed(&mut self, chunk: &[u8], mut on_match: impl FnMut(Match)) {
+        let automaton = self.automaton;
+        for &byte in chunk {
+            self.state = automaton.next_state(self.state, automaton.fold(byte));
+            self.position += 1;
+
+            // own outputs first, then the shorter ones along the output links
+            let mut state = Some(self.state);
+            while let Some(s) = state {
+                for &pattern in &automaton.states[s].outputs {
+                    let m = Match {
+                        pattern,
+                        start: self.position - automaton.pattern_lengths[pattern],
+                        end: self.position,
+                    };
+                    match automaton.match_kind {
+                        MatchKind::Overlapping => on_match(m),
+                        MatchKind::LeftmostLongest if m.start >= self.resume_at => {
+                            self.candidates.push(m)
+                        }
+                        MatchKind::LeftmostLongest => {}
+                    }
+                }
+                state = automaton.states[s].output_link;
+            }
+
+            // every later match starts inside the current state's string, so candidates
+            // starting before it can no longer be beaten
+            if !self.candidates.is_empty() {
+                let frontier = self.position - automaton.states[self.state].depth;
+                self.settle(frontier, &mut on_match);
+            }
+        }
+    }
+
+    /// Reports the matches still held back, call once the text has ended
+    pub fn finish(mut self, mut on_match: impl FnMut(Match)) {
+        self.settle(usize::MAX, &mut on_match);
+    }
+
+    /// Reports leftmost-longest candidates that start before `frontier`
+    fn settle(&mut self, frontier: usize, on_match: &mut impl FnMut(Match)) {
+        loop {
+            let best = self
+                .candidates
+                .iter()
+                .min_by_key(|m| (m.start, std::cmp::Reverse(m.end), m.pattern))
+                .copied();
+            match best {
+                Some(best) if best.start < frontier => {
+                    on_match(best);
+                    self.resume_at = best.end;
+                    self.candidates.retain(|m| m.start >= best.end);
+                }
+                _ => return,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+    use std::fs;
+
+    use super::*;
+    use crate::algoritms::search::text::two_way_find_all;
+    use crate::algoritms::sorting::quick_sort::XorShift64;
+
+    fn naive_overlapping(patterns: &[&[u8]], text: &[u8]) -> BTreeSet<(usize, usize, usize)> {
+        let mut matches = BTreeSet::new();
+        for (id, pattern) in patterns.iter().enumerate() {
+            for start in two_way_find_all(text, pattern) {
+                matches.insert((start, start + pattern.len(), id));
+            }
+        }
+        matches
+    }
+
+    fn naive_leftmost_longest(patterns: &[&[u8]], text: &[u8]) -> Vec<Match> {
+        let mut matches = Vec::new();
+        let mut start = 0;
+        while start < text.len() {
+            let best = patterns
+                .iter()
+                .enumerate()
+                .filter(|(_, p)| !p.is_empty() && text[start..].starts_with(p))
+                .max_by_key(|&(id, p)| (p.len(), std::cmp::Reverse(id)));
+            match best {
+                Some((pattern, p)) => {
+                    matches.push(Match {
+                        pattern,
+                        start,
+                        end: start + p.len(),
+                    });
+                    start += p.len();
+                }
+                None => start += 1,
+            }
+        }
+        matches
+    }
+
+    fn as_set(matches: &[Match]) -> BTreeSet<(usize, usize, usize)> {
+        matches
+            .iter()
+            .map(|m| (m.start, m.end, m.pattern))
+            .collect()
+    }
+
+    #[test]
+    fn test_classic_example() {
+        let patterns = ["he", "she", "his", "hers"];
+        let automaton = AhoCorasick::new(patterns);
+        let matches = automaton.find_all(b"ushers");
+        assert_eq!(
+            matches,
+            [
+                Match {
+                    pattern: 1,
+                    start: 1,
+                    end: 4
+                },
+                Match {
+                    pattern: 0,
+                    start: 2,
+                    end: 4
+                },
+                Match {
+                    pattern: 3,
+                    start: 2,
+                    end: 6
+                },
+            ]
+        );
+
+        let automaton = AhoCorasick::builder()
+            .match_kind(MatchKind::LeftmostLongest)
+            .build(patterns);
+        assert_eq!(
+            automaton.find_all(b"ushers"),
+            [Match {
+                pattern: 1,
+                start: 1,
+                end: 4
+            }]
+        );
+    }
+
+    #[test]
+    fn test_leftmost_longest_prefers_longer() {
+        let automaton = AhoCorasick::builder()
+            .match_kind(MatchKind::LeftmostLongest)
+            .build(["abc", "abcd", "b", "cde"]);
+        let matches = automaton.find_all(b"abcdex");
+        assert_eq!(
+            matches,
+            [Match {
+                pattern: 1,
+                start: 0,
+                end: 4
+            }]
+        );
+
+        // "bcd" starts later than "abcd"'s prefix "a..", but "abcd" never completes
+        let automaton = AhoCorasick::builder()
+            .match_kind(MatchKind::LeftmostLongest)
+            .build(["abcd", "bc"]);
+        assert_eq!(
+            automaton.find_all(b"abcx"),
+            [Match {
+                pattern: 1,
+                start: 1,
+                end: 3
+            }]
+        );
+    }
+
+    #[test]
+    fn test_leftmost_longest_inside_a_longer_prefix() {
+        // "abc" keeps the automaton deep while both shorter matches are found
+        let automaton = AhoCorasick::builder()
+            .match_kind(MatchKind::LeftmostLongest)
+            .build(["ab", "c", "abcdz"]);
+        assert_eq!(
+            automaton.find_all(b"abcdx"),
+            [
+                Match {
+                    pattern: 0,
+                    start: 0,
+                    end: 2
+                },
+                Match {
+                    pattern: 1,
+                    start: 2,
+                    end: 3
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_random_against_naive() {
+        let mut rng = XorShift64::new(41);
+        let word = |rng: &mut XorShift64, max_len: usize| -> Vec<u8> {
+            (0..rng.next_below(max_len))
+                .map(|_| b'a' + rng.next_below(3) as u8)
+                .collect()
+        };
+
+        for _ in 0..300 {
+            let patterns: Vec<Vec<u8>> = (0..1 + rng.next_below(6))
+                .map(|_| word(&mut rng, 5))
+                .collect();
+            let patterns: Vec<&[u8]> = patterns.iter().map(|p| p.as_slice()).collect();
+            let text = word(&mut rng, 60);
+
+            let overlapping = AhoCorasick::new(&patterns).find_all(&text);
+            assert_eq!(as_set(&overlapping), naive_overlapping(&patterns, &text));
+
+            let leftmost = AhoCorasick::builder()
+                .match_kind(MatchKind::LeftmostLongest)
+                .build(&patterns)
+                .find_all(&text);
+            assert_eq!(
+                leftmost,
+                naive_leftmost_longest(&patterns, &text),
+                "{patterns:?} in {:?}",
+                String::from_utf8_lossy(&text)
+            );
+        }
+    }
+
+    #[test]
+    fn test_case_insensitive() {
+        let automaton = AhoCorasick::builder()
+            .case_insensitive(true)
+            .build(["whale", "AHAB"]);
+        let matches = automaton.find_all(b"Ahab saw the WHALE, ahab.");
+        let found: Vec<(usize, usize)> = matches.iter().map(|m| (m.pattern, m.start)).collect();
+        assert_eq!(found, [(1, 0), (0, 13), (1, 20)]);
+
+        let sensitive = AhoCorasick::new(["whale", "AHAB"]);
+        assert!(sensitive.find_all(b"Ahab saw the WHALE").is_empty());
+    }
+
+    #[test]
+    fn test_empty_and_duplicate_patterns() {
+        let automaton = AhoCorasick::new(["", "ab", "ab"]);
+        assert_eq!(automaton.pattern_count(), 3);
+        let matches = automaton.find_all(b"xab");
+        assert_eq!(as_set(&matches), BTreeSet::from([(1, 3, 1), (1, 3, 2)]));
+
+        let automaton = AhoCorasick::builder()
+            .match_kind(MatchKind::LeftmostLongest)
+            .build(["", "ab", "ab"]);
+        assert_eq!(
+            automaton.find_all(b"xab"),
+            [Match {
+                pattern: 1,
+                start: 1,
+                end: 3
+            }]
+        );
+        assert!(
+            AhoCorasick::new(Vec::<&str>::new())
+                .find_all(b"abc")
+                .is_empty()
+        );
+    }
+
+    /// Hands out a few bytes per read, so matches straddle the chunks
+    struct Trickle<'a> {
+        data: &'a [u8],
+        step: usize,
+    }
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let n = self.step.min(buf.len()).min(self.data.len());
+            buf[..n].copy_from_slice(&self.data[..n]);
+            self.data = &self.data[n..];
+            Ok(n)
+        }
+    }
+
+    #[test]
+    fn test_moby_dick_keywords() {
+        let text = fs::read("data/moby_dick.txt").unwrap();
+        let text_str = String::from_utf8_lossy(&text);
+
+        // every distinct word of five letters or more, a few thousand patterns
+        let words: BTreeSet<&str> = text_str
+            .split(|c: char| !c.is_ascii_alphabetic())
+            .filter(|w| w.len() >= 5)
+            .collect();
+        let words: Vec<&str> = words.into_iter().collect();
+        assert!(words.len() > 5000);
+
+        for kind in [MatchKind::Overlapping, MatchKind::LeftmostLongest] {
+            let automaton = AhoCorasick::builder().match_kind(kind).build(&words);
+            let in_memory = automaton.find_all(&text);
+
+            let mut streamed = Vec::new();
+            let reader = Trickle {
+                data: &text,
+                step: 7,
+            };
+            automaton.stream(reader, |m| streamed.push(m)).unwrap();
+            assert_eq!(in_memory, streamed);
+
+            // spot check against single pattern search
+            for pattern in ["whale", "Queequeg", "harpoon"] {
+                let id = words.binary_search(&pattern).unwrap();
+                let found: Vec<usize> = in_memory
+                    .iter()
+                    .filter(|m| m.pattern == id)
+                    .map(|m| m.start)
+                    .collect();
+                let expected = two_way_find_all(&text, pattern.as_bytes());
+                match kind {
+                    MatchKind::Overlapping => assert_eq!(found, expected),
+                    // some are swallowed by longer words ("whales", "whaleman")
+                    MatchKind::LeftmostLongest => {
+                        assert!(!found.is_empty() && found.len() <= expected.len())
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_stream_from_file() {
+        let automaton = AhoCorasick::builder()
+            .case_insensitive(true)
+            .build(["moby dick", "ishmael"]);
+        let file = fs::File::open("data/moby_dick.txt").unwrap();
+        let mut counts = [0; 2];
+        automaton.stream(file, |m| counts[m.pattern] += 1).unwrap();
+        assert!(counts[0] > 0 && counts[1] > 0, "{counts:?}");
+    }
+}
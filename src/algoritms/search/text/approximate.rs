@@ -0,0 +1,294 @@
+// Approximate search: every place in the text where the pattern occurs with at most k
+// edits (insertions, deletions, substitutions). The reference is Sellers' dynamic
+// program, the edit-distance table with a free start anywhere in the text, O(n * m).
+// Both bit-parallel versions pack a column of that table into one u64, so patterns are
+// at most 64 bytes:
+// - Bitap (Wu–Manber): one bit mask per error count, O(n * k)
+// - Myers: the column as +1/-1 deltas, O(n) whatever k is
+//
+// A match is reported by where it ends, the start is ambiguous ("whale" within one edit
+// of "whales" could end before or after the "s").
+
+/// An approximate match ending right before byte `end`, `distance` edits from the pattern
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ApproximateMatch {
+    pub end: usize,
+    pub distance: usize,
+}
+
+/// Longest pattern the bit-parallel searches take
+pub const MAX_PATTERN_LEN: usize = u64::BITS as usize;
+
+/// Every end position where `pattern` matches `text` within `k` edits, with the fewest
+/// edits it takes there. Myers for patterns that fit a word, Sellers otherwise.
+pub fn approximate_find_all(text: &[u8], pattern: &[u8], k: usize) -> Vec<ApproximateMatch> {
+    if pattern.len() <= MAX_PATTERN_LEN {
+        myers_find_all(text, pattern, k)
+    } else {
+        sellers_find_all(text, pattern, k)
+    }
+}
+
+/// Dynamic programming over one column of the edit-distance table at a time: row `i` is
+/// the distance of `pattern[..i]` to the best substring ending at the current byte
+pub fn sellers_find_all(text: &[u8], pattern: &[u8], k: usize) -> Vec<ApproximateMatch> {
+    let mut matches = Vec::new();
+    if pattern.is_empty() {
+        return matches;
+    }
+    let mut column: Vec<usize> = (0..=pattern.len()).collect();
+
+    for (j, &byte) in text.iter().enumerate() {
+        // row 0 stays 0: a match can start anywhere
+        let mut diagonal = 0;
+        for i in 1..=pattern.len() {
+            let above = column[i - 1];
+            let substitute = diagonal + usize::from(pattern[i - 1] != byte);
+            diagonal = column[i];
+            column[i] = substitute.min(column[i] + 1).min(above + 1);
+        }
+        let distance = column[pattern.len()];
+        if distance <= k {
+            matches.push(ApproximateMatch {
+                end: j + 1,
+                distance,
+            });
+        }
+    }
+    matches
+}
+
+/// Bitap with errors: bit `i` of `states[d]` says `pattern[..=i]` matches a substring
+/// ending at the current byte with at most `d` edits. Panics on patterns longer than
+/// [`MAX_PATTERN_LEN`].
+pub fn bitap_find_all(text: &[u8], pattern: &[u8], k: usize) -> Vec<ApproximateMatch> {
+    let mut matches = Vec::new();
+    let m = pattern.len();
+    if m == 0 {
+        return matches;
+    }
+    assert!(
+        m <= MAX_PATTERN_LEN,
+        "pattern is {m} bytes, at most {MAX_PATTERN_LEN} are supported"
+    );
+    // with m edits anything matches, more can't help
+    let k = k.min(m);
+
+    let masks = byte_masks(pattern);
+    let last = 1u64 << (m - 1);
+    // before any text, the first d pattern bytes can be deleted
+    let mut states: Vec<u64> = (0..=k).map(low_bits).collect();
+
+    for (j, &byte) in text.iter().enumerate() {
+        let mask = masks[byte as usize];
+        let mut previous_old = states[0];
+        states[0] = ((states[0] << 1) | 1) & mask;
+        for d in 1..=k {
+            let old = states[d];
+            states[d] = (((old << 1) | 1) & mask)
+                // the byte is an extra one in the text
+                | previous_old
+                // the byte replaces a pattern byte
+                | (previous_old << 1) | 1
+                // a pattern byte is missing from the text
+                | (states[d - 1] << 1);
+            previous_old = old;
+        }
+        if let Some(distance) = states.iter().position(|state| state & last != 0) {
+            matches.push(ApproximateMatch {
+                end: j + 1,
+                distance,
+            });
+        }
+    }
+    matches
+}
+
+/// Myers' bit-vector algorithm: the column of the edit-distance table as vertical deltas,
+/// `positive` and `negative` mark rows one more and one less than the row above, and
+/// `score` tracks the last row. Panics on patterns longer than [`MAX_PATTERN_LEN`].
+pub fn myers_find_all(text: &[u8], pattern: &[u8], k: usize) -> Vec<ApproximateMatch> {
+    let mut matches = Vec::new();
+    let m = pattern.len();
+    if m == 0 {
+        return matches;
+    }
+    assert!(
+        m <= MAX_PATTERN_LEN,
+        "pattern is {m} bytes, at most {MAX_PATTERN_LEN} are supported"
+    );
+
+    let masks = byte_masks(pattern);
+    let last = 1u64 << (m - 1);
+    // the first column is 0, 1, 2, ..., m: every delta is +1
+    let mut positive = u64::MAX;
+    let mut negative = 0u64;
+    let mut score = m;
+
+    for (j, &byte) in text.iter().enumerate() {
+        let equal = masks[byte as usize];
+        let vertical = equal | negative;
+        let horizontal = (((equal & positive).wrapping_add(positive)) ^ positive) | equal;
+        let mut horizontal_positive = negative | !(horizontal | positive);
+        let mut horizontal_negative = positive & horizontal;
+
+        if horizontal_positive & last != 0 {
+            score += 1;
+        } else if horizontal_negative & last != 0 {
+            score -= 1;
+        }
+
+        // row 0 is 0 in every column, so nothing is shifted in at the top
+        horizontal_positive <<= 1;
+        horizontal_negative <<= 1;
+        positive = horizontal_negative | !(vertical | horizontal_positive);
+        negative = horizontal_positive & vertical;
+
+        if score <= k {
+            matches.push(ApproximateMatch {
+                end: j + 1,
+                distance: score,
+            });
+        }
+    }
+    matches
+}
+
+/// Bit `i` of `masks[b]` is set where `pattern[i] == b`
+fn byte_masks(pattern: &[u8]) -> [u64; 256] {
+    let mut masks = [0u64; 256];
+    for (i, &byte) in pattern.iter().enumerate() {
+        masks[byte as usize] |= 1 << i;
+    }
+    masks
+}
+
+fn low_bits(count: usize) -> u64 {
+    if count >= MAX_PATTERN_LEN {
+        u64::MAX
+    } else {
+        (1 << count) - 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::algoritms::search::text::distance::levenshtein;
+    use crate::algoritms::search::text::tests::random_bytes;
+    use crate::algoritms::sorting::quick_sort::XorShift64;
+
+    type Search = fn(&[u8], &[u8], usize) -> Vec<ApproximateMatch>;
+
+    const SEARCHES: [(&str, Search); 3] = [
+        ("sellers", sellers_find_all),
+        ("bitap", bitap_find_all),
+        ("myers", myers_find_all),
+    ];
+
+    /// The smallest edit distance of `pattern` to any substring ending at `end`
+    fn brute_force_distance(text: &[u8], pattern: &[u8], end: usize) -> usize {
+        (0..=end)
+            .map(|start| levenshtein(&text[start..end], pattern))
+            .min()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_matches_brute_force() {
+        let mut rng = XorShift64::new(45);
+        for alphabet in [2, 4] {
+            for _ in 0..150 {
+                let text = random_bytes(rng.next_below(40), alphabet, &mut rng);
+                let pattern = random_bytes(1 + rng.next_below(6), alphabet, &mut rng);
+                let k = rng.next_below(4);
+                let expected: Vec<ApproximateMatch> = (1..=text.len())
+                    .map(|end| ApproximateMatch {
+                        end,
+                        distance: brute_force_distance(&text, &pattern, end),
+                    })
+                    .filter(|found| found.distance <= k)
+                    .collect();
+                for (name, search) in SEARCHES {
+                    assert_eq!(
+                        search(&text, &pattern, k),
+                        expected,
+                        "{name} on {:?} / {:?}, k {k}",
+                        String::from_utf8_lossy(&text),
+                        String::from_utf8_lossy(&pattern)
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_long_patterns() {
+        let mut rng = XorShift64::new(46);
+        let text = random_bytes(3000, 4, &mut rng);
+        for len in [63, 64] {
+            let mut pattern = text[1000..1000 + len].to_vec();
+            pattern[10] = b'z';
+            pattern.remove(30);
+            let expected = sellers_find_all(&text, &pattern, 3);
+            assert!(expected.iter().any(|found| found.distance == 2));
+            assert_eq!(bitap_find_all(&text, &pattern, 3), expected);
+            assert_eq!(myers_find_all(&text, &pattern, 3), expected);
+        }
+        // too long for a word: falls back to sellers
+        let pattern = &text[500..600];
+        let found = approximate_find_all(&text, pattern, 0);
+        assert_eq!(
+            found,
+            [ApproximateMatch {
+                end: 600,
+                distance: 0
+            }]
+        );
+    }
+
+    #[test]
+    fn test_exact_search_and_edge_cases() {
+        for (_, search) in SEARCHES {
+            let ends: Vec<usize> = search(b"abcabcab", b"cab", 0)
+                .iter()
+                .map(|found| found.end)
+                .collect();
+            assert_eq!(ends, [5, 8]);
+            assert!(search(b"abc", b"", 1).is_empty());
+            assert!(search(b"", b"abc", 1).is_empty());
+            // with as many edits as pattern bytes, everything matches
+            assert_eq!(search(b"xyz", b"ab", 5).len(), 3);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "pattern is 65 bytes")]
+    fn test_bitap_pattern_too_long() {
+        bitap_find_all(b"abc", &[b'a'; 65], 1);
+    }
+
+    #[test]
+    fn test_typos_in_moby_dick() {
+        let text = fs::read("data/moby_dick.txt").unwrap();
+        let exact = approximate_find_all(&text, b"Ishmael", 0).len();
+        assert!(exact > 0);
+
+        for typo in [&b"Ishmeal"[..], b"Ishmal", b"Ishmaael", b"Ishnael"] {
+            let found = approximate_find_all(&text, typo, 2);
+            // one of the ends of every real occurrence is an exact-but-for-the-typo match
+            let ends_at_ishmael = found
+                .iter()
+                .filter(|found| text[..found.end].ends_with(b"Ishmael"))
+                .count();
+            assert_eq!(ends_at_ishmael, exact, "{}", String::from_utf8_lossy(typo));
+            assert!(found.iter().all(|found| found.distance <= 2));
+        }
+        assert_eq!(
+            bitap_find_all(&text, b"leviathan", 1),
+            myers_find_all(&text, b"leviathan", 1)
+        );
+    }
+}
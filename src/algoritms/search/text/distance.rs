@@ -0,0 +1,222 @@
+// Edit distances between two sequences. Generic over the element type, so they work on
+// bytes, on `chars().collect()`, or on whole words.
+// - Hamming: substitutions only, equal lengths
+// - Levenshtein: insertions, deletions and substitutions, O(n * m) time and O(m) space;
+//   `levenshtein_within` only fills a band of width 2k + 1 and stops early, O(k * n)
+// - optimal string alignment: Levenshtein plus swapping two adjacent elements, but no
+//   substring may be edited twice
+// - Damerau–Levenshtein: adjacent swaps without that restriction, O(n * m) time and space
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Number of positions where `a` and `b` differ, `None` if their lengths differ
+pub fn hamming<T: PartialEq>(a: &[T], b: &[T]) -> Option<usize> {
+    (a.len() == b.len()).then(|| a.iter().zip(b).filter(|(x, y)| x != y).count())
+}
+
+/// Fewest insertions, deletions and substitutions turning `a` into `b`
+pub fn levenshtein<T: PartialEq>(a: &[T], b: &[T]) -> usize {
+    // previous[j] is the distance between the a prefix of the last row and b[..j]
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for (i, x) in a.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, y) in b.iter().enumerate() {
+            let substitute = previous[j] + usize::from(x != y);
+            current[j + 1] = substitute.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
+
+/// The Levenshtein distance if it is at most `k`, else `None`. Cells more than `k` off
+/// the diagonal can't be on a path of cost `k`, so only a band of them is filled.
+pub fn levenshtein_within<T: PartialEq>(a: &[T], b: &[T], k: usize) -> Option<usize> {
+    if a.len().abs_diff(b.len()) > k {
+        return None;
+    }
+    // anything over k is as good as k + 1, which keeps the band's edges from overflowing
+    let over = k + 1;
+    let mut previous: Vec<usize> = (0..=b.len()).map(|j| j.min(over)).collect();
+    let mut current = vec![over; b.len() + 1];
+
+    for (i, x) in a.iter().enumerate() {
+        let row = i + 1;
+        let low = row.saturating_sub(k);
+        let high = (row + k).min(b.len());
+        current.fill(over);
+        if low == 0 {
+            current[0] = row.min(over);
+        }
+        let mut best = current[0];
+        for j in low.max(1)..=high {
+            let substitute = previous[j - 1] + usize::from(*x != b[j - 1]);
+            current[j] = substitute
+                .min(previous[j] + 1)
+                .min(current[j - 1] + 1)
+                .min(over);
+            best = best.min(current[j]);
+        }
+        if best > k {
+            return None;
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    let distance = previous[b.len()];
+    (distance <= k).then_some(distance)
+}
+
+/// Levenshtein plus transposing two adjacent elements, as long as no substring is edited
+/// more than once: "ca" to "abc" is 3, not 2 as with [`damerau_levenshtein`]
+pub fn optimal_string_alignment<T: PartialEq>(a: &[T], b: &[T]) -> usize {
+    // three rows: a transposition looks two rows back
+    let mut before: Vec<usize> = vec![0; b.len() + 1];
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for i in 0..a.len() {
+        current[0] = i + 1;
+        for j in 0..b.len() {
+            let substitute = previous[j] + usize::from(a[i] != b[j]);
+            let mut distance = substitute.min(previous[j + 1] + 1).min(current[j] + 1);
+            if i > 0 && j > 0 && a[i] == b[j - 1] && a[i - 1] == b[j] {
+                distance = distance.min(before[j - 1] + 1);
+            }
+            current[j + 1] = distance;
+        }
+        std::mem::swap(&mut before, &mut previous);
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
+
+/// Fewest insertions, deletions, substitutions and transpositions of adjacent elements
+/// turning `a` into `b` (Lowrance–Wagner), a true metric unlike
+/// [`optimal_string_alignment`]
+pub fn damerau_levenshtein<T: Eq + Hash>(a: &[T], b: &[T]) -> usize {
+    let (n, m) = (a.len(), b.len());
+    let infinity = n + m;
+    // shifted by one row and column so row 0 and column 0 can hold the `infinity` border
+    let width = m + 2;
+    let mut table = vec![0; (n + 2) * width];
+    let at = |i: usize, j: usize| i * width + j;
+
+    table[at(0, 0)] = infinity;
+    for i in 0..=n {
+        table[at(i + 1, 0)] = infinity;
+        table[at(i + 1, 1)] = i;
+    }
+    for j in 0..=m {
+        table[at(0, j + 1)] = infinity;
+        table[at(1, j + 1)] = j;
+    }
+
+    // the last row of a each element was seen in
+    let mut last_row: HashMap<&T, usize> = HashMap::new();
+    for i in 1..=n {
+        // the last column in this row where a[i - 1] matched
+        let mut last_match_column = 0;
+        for j in 1..=m {
+            let k = last_row.get(&b[j - 1]).copied().unwrap_or(0);
+            let l = last_match_column;
+            let cost = if a[i - 1] == b[j - 1] {
+                last_match_column = j;
+                0
+            } else {
+                1
+            };
+            table[at(i + 1, j + 1)] = (table[at(i, j)] + cost)
+                .min(table[at(i + 1, j)] + 1)
+                .min(table[at(i, j + 1)] + 1)
+                // transpose, with everything between the swapped pair deleted or inserted
+                .min(table[at(k, l)] + (i - k - 1) + 1 + (j - l - 1));
+        }
+        last_row.insert(&a[i - 1], i);
+    }
+    table[at(n + 1, m + 1)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algoritms::search::text::tests::random_bytes;
+    use crate::algoritms::sorting::quick_sort::XorShift64;
+
+    #[test]
+    fn test_hamming() {
+        assert_eq!(hamming(b"karolin", b"kathrin"), Some(3));
+        assert_eq!(hamming(b"", b""), Some(0));
+        assert_eq!(hamming(b"abc", b"ab"), None);
+        assert_eq!(hamming(&[1, 0, 1, 1], &[1, 1, 1, 0]), Some(2));
+    }
+
+    #[test]
+    fn test_levenshtein() {
+        assert_eq!(levenshtein(b"kitten", b"sitting"), 3);
+        assert_eq!(levenshtein(b"flaw", b"lawn"), 2);
+        assert_eq!(levenshtein(b"", b"abc"), 3);
+        assert_eq!(levenshtein(b"abc", b""), 3);
+        assert_eq!(levenshtein(b"same", b"same"), 0);
+
+        let a: Vec<char> = "Ishmæl".chars().collect();
+        let b: Vec<char> = "Ishmael".chars().collect();
+        assert_eq!(levenshtein(&a, &b), 2);
+
+        let a = ["call", "me", "ishmael"];
+        let b = ["call", "him", "ishmael"];
+        assert_eq!(levenshtein(&a, &b), 1);
+    }
+
+    #[test]
+    fn test_levenshtein_within_agrees() {
+        let mut rng = XorShift64::new(43);
+        for _ in 0..2000 {
+            let a = random_bytes(rng.next_below(12), 3, &mut rng);
+            let b = random_bytes(rng.next_below(12), 3, &mut rng);
+            let distance = levenshtein(&a, &b);
+            for k in 0..6 {
+                let expected = (distance <= k).then_some(distance);
+                assert_eq!(levenshtein_within(&a, &b, k), expected, "{a:?} {b:?} {k}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_transpositions() {
+        assert_eq!(levenshtein(b"ab", b"ba"), 2);
+        assert_eq!(optimal_string_alignment(b"ab", b"ba"), 1);
+        assert_eq!(damerau_levenshtein(b"ab", b"ba"), 1);
+
+        // the classic case where the restricted version is not a metric
+        assert_eq!(optimal_string_alignment(b"ca", b"abc"), 3);
+        assert_eq!(damerau_levenshtein(b"ca", b"abc"), 2);
+
+        assert_eq!(damerau_levenshtein(b"", b"ab"), 2);
+        assert_eq!(damerau_levenshtein(b"whale", b"hwael"), 2);
+        assert_eq!(damerau_levenshtein(b"kitten", b"sitting"), 3);
+    }
+
+    #[test]
+    fn test_distance_bounds() {
+        let mut rng = XorShift64::new(44);
+        for _ in 0..1000 {
+            let a = random_bytes(rng.next_below(10), 3, &mut rng);
+            let b = random_bytes(rng.next_below(10), 3, &mut rng);
+            let (lev, osa, dl) = (
+                levenshtein(&a, &b),
+                optimal_string_alignment(&a, &b),
+                damerau_levenshtein(&a, &b),
+            );
+            assert!(dl <= osa && osa <= lev, "{a:?} {b:?}");
+            assert!(lev >= a.len().abs_diff(b.len()));
+            assert_eq!(lev, levenshtein(&b, &a));
+            assert_eq!(dl, damerau_levenshtein(&b, &a));
+            if let Some(hamming) = hamming(&a, &b) {
+                assert!(lev <= hamming);
+            }
+        }
+    }
+}
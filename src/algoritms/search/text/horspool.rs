@@ -0,0 +1,48 @@
+// Boyer–Moore–Horspool. Compares the pattern right to left and, on a mismatch, shifts by
+// how far the text byte under the pattern's last position is from the pattern's end.
+// Sublinear on typical text (long shifts for long patterns), O(n * m) in the worst case.
+
+/// Byte offsets of every (possibly overlapping) occurrence of `pattern` in `text`
+pub fn horspool_find_all(text: &[u8], pattern: &[u8]) -> Vec<usize> {
+    let mut matches = Vec::new();
+    let m = pattern.len();
+    if m == 0 || m > text.len() {
+        return matches;
+    }
+
+    // bytes that don't occur in the pattern (before its last byte) allow a full shift
+    let mut shift = [m; 256];
+    for (i, &byte) in pattern[..m - 1].iter().enumerate() {
+        shift[byte as usize] = m - 1 - i;
+    }
+
+    let mut start = 0;
+    while start + m <= text.len() {
+        let window = &text[start..start + m];
+        if window
+            .iter()
+            .rev()
+            .zip(pattern.iter().rev())
+            .all(|(a, b)| a == b)
+        {
+            matches.push(start);
+        }
+        start += shift[window[m - 1] as usize];
+    }
+    matches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_horspool_find_all() {
+        assert_eq!(horspool_find_all(b"abababa", b"aba"), [0, 2, 4]);
+        assert_eq!(
+            horspool_find_all(b"here is a simple example", b"example"),
+            [17]
+        );
+        assert_eq!(horspool_find_all(b"aaaa", b"b"), [] as [usize; 0]);
+    }
+}
@@ -0,0 +1,61 @@
+// Knuth–Morris–Pratt. The failure table says how much of the pattern is still matched
+// after a mismatch, so the text is read exactly once: O(n + m) in the worst case.
+
+/// Byte offsets of every (possibly overlapping) occurrence of `pattern` in `text`
+pub fn kmp_find_all(text: &[u8], pattern: &[u8]) -> Vec<usize> {
+    let mut matches = Vec::new();
+    if pattern.is_empty() || pattern.len() > text.len() {
+        return matches;
+    }
+
+    let failure = failure_table(pattern);
+    let mut matched = 0;
+    for (i, &byte) in text.iter().enumerate() {
+        while matched > 0 && pattern[matched] != byte {
+            matched = failure[matched - 1];
+        }
+        if pattern[matched] == byte {
+            matched += 1;
+        }
+        if matched == pattern.len() {
+            matches.push(i + 1 - matched);
+            matched = failure[matched - 1];
+        }
+    }
+    matches
+}
+
+/// `failure[i]` is the length of the longest proper prefix of `pattern[..=i]` that is
+/// also a suffix of it
+pub(crate) fn failure_table(pattern: &[u8]) -> Vec<usize> {
+    let mut failure = vec![0; pattern.len()];
+    let mut len = 0;
+    for i in 1..pattern.len() {
+        while len > 0 && pattern[i] != pattern[len] {
+            len = failure[len - 1];
+        }
+        if pattern[i] == pattern[len] {
+            len += 1;
+        }
+        failure[i] = len;
+    }
+    failure
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_failure_table() {
+        assert_eq!(failure_table(b"abacabab"), [0, 0, 1, 0, 1, 2, 3, 2]);
+        assert_eq!(failure_table(b"aaaa"), [0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_kmp_find_all() {
+        assert_eq!(kmp_find_all(b"abababa", b"aba"), [0, 2, 4]);
+        assert_eq!(kmp_find_all(b"hello", b"xyz"), [] as [usize; 0]);
+        assert_eq!(kmp_find_all(b"ab", b"abc"), [] as [usize; 0]);
+    }
+}
@@ -0,0 +1,251 @@
+// Substring search. Every algorithm works on bytes and returns the byte offset of every
+// match, overlapping ones included; `find_all` runs one on a `&str` and adds char
+// offsets. An empty pattern has no matches.
+//
+// `AhoCorasick` searches for many patterns in one pass, `SuffixArray` indexes one text
+// for many queries. `approximate_find_all` allows up to k edits, and `distance` has the
+// edit distances behind it.
+
+pub mod aho_corasick;
+pub mod approximate;
+pub mod distance;
+pub mod horspool;
+pub mod kmp;
+pub mod rabin_karp;
+pub mod suffix_array;
+pub mod two_way;
+
+pub use aho_corasick::{AhoCorasick, AhoCorasickBuilder, Match, MatchKind, Searcher};
+pub use approximate::{
+    ApproximateMatch, approximate_find_all, bitap_find_all, myers_find_all, sellers_find_all,
+};
+pub use distance::{
+    damerau_levenshtein, hamming, levenshtein, levenshtein_within, optimal_string_alignment,
+};
+pub use horspool::horspool_find_all;
+pub use kmp::kmp_find_all;
+pub use rabin_karp::rabin_karp_find_all;
+pub use suffix_array::{SuffixArray, longest_common_substring};
+pub use two_way::two_way_find_all;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Algorithm {
+    Kmp,
+    Horspool,
+    RabinKarp,
+    TwoWay,
+}
+
+impl Algorithm {
+    pub const ALL: [Algorithm; 4] = [
+        Algorithm::Kmp,
+        Algorithm::Horspool,
+        Algorithm::RabinKarp,
+        Algorithm::TwoWay,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Algorithm::Kmp => "knuth-morris-pratt",
+            Algorithm::Horspool => "boyer-moore-horspool",
+            Algorithm::RabinKarp => "rabin-karp",
+            Algorithm::TwoWay => "two-way",
+        }
+    }
+
+    /// Byte offsets of every occurrence of `pattern` in `text`
+    pub fn find_all(&self, text: &[u8], pattern: &[u8]) -> Vec<usize> {
+        match self {
+            Algorithm::Kmp => kmp_find_all(text, pattern),
+            Algorithm::Horspool => horspool_find_all(text, pattern),
+            Algorithm::RabinKarp => rabin_karp_find_all(text, pattern),
+            Algorithm::TwoWay => two_way_find_all(text, pattern),
+        }
+    }
+}
+
+/// Where a match starts, in bytes and in chars
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TextMatch {
+    pub byte: usize,
+    pub char: usize,
+}
+
+/// Every occurrence of `pattern` in `text` with `algorithm`
+pub fn find_all(text: &str, pattern: &str, algorithm: Algorithm) -> Vec<TextMatch> {
+    let bytes = algorithm.find_all(text.as_bytes(), pattern.as_bytes());
+    let chars = char_offsets(text, &bytes);
+    bytes
+        .into_iter()
+        .zip(chars)
+        .map(|(byte, char)| TextMatch { byte, char })
+        .collect()
+}
+
+/// Converts ascending byte offsets into char offsets in one pass over `text`. The offsets
+/// must be on char boundaries, which matches of a `&str` pattern always are.
+pub fn char_offsets(text: &str, byte_offsets: &[usize]) -> Vec<usize> {
+    let mut result = Vec::with_capacity(byte_offsets.len());
+    let (mut byte, mut char) = (0, 0);
+    let mut chars = text.chars();
+
+    for &offset in byte_offsets {
+        while byte < offset {
+            byte += chars
+                .next()
+                .expect("offset past the end of the text")
+                .len_utf8();
+            char += 1;
+        }
+        assert_eq!(
+            byte, offset,
+            "byte offset {offset} is not on a char boundary"
+        );
+        result.push(char);
+    }
+    result
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::fs;
+    use std::time::Instant;
+
+    use super::*;
+    use crate::algoritms::sorting::quick_sort::XorShift64;
+
+    fn naive_find_all(text: &[u8], pattern: &[u8]) -> Vec<usize> {
+        if pattern.is_empty() || pattern.len() > text.len() {
+            return Vec::new();
+        }
+        (0..=text.len() - pattern.len())
+            .filter(|&i| &text[i..i + pattern.len()] == pattern)
+            .collect()
+    }
+
+    /// `len` bytes from the first `alphabet` lowercase letters, for the other text tests too
+    pub(crate) fn random_bytes(len: usize, alphabet: u8, rng: &mut XorShift64) -> Vec<u8> {
+        (0..len)
+            .map(|_| b'a' + rng.next_below(alphabet as usize) as u8)
+            .collect()
+    }
+
+    #[test]
+    fn test_all_algorithms_match_naive_search() {
+        let mut rng = XorShift64::new(40);
+        for alphabet in [1, 2, 4, 26] {
+            for _ in 0..300 {
+                let text = random_bytes(rng.next_below(200), alphabet, &mut rng);
+                let pattern = random_bytes(1 + rng.next_below(8), alphabet, &mut rng);
+                let expected = naive_find_all(&text, &pattern);
+                for algorithm in Algorithm::ALL {
+                    assert_eq!(
+                        algorithm.find_all(&text, &pattern),
+                        expected,
+                        "{} on {:?} / {:?}",
+                        algorithm.name(),
+                        String::from_utf8_lossy(&text),
+                        String::from_utf8_lossy(&pattern)
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_periodic_patterns() {
+        let text = b"abaabaabaababaabaabaab".repeat(5);
+        for pattern in [&b"abaaba"[..], b"abaabaab", b"aab", b"abab", b"aaaa"] {
+            let expected = naive_find_all(&text, pattern);
+            for algorithm in Algorithm::ALL {
+                assert_eq!(
+                    algorithm.find_all(&text, pattern),
+                    expected,
+                    "{}",
+                    algorithm.name()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_edge_cases() {
+        for algorithm in Algorithm::ALL {
+            assert!(algorithm.find_all(b"abc", b"").is_empty());
+            assert!(algorithm.find_all(b"", b"a").is_empty());
+            assert!(algorithm.find_all(b"ab", b"abc").is_empty());
+            assert_eq!(algorithm.find_all(b"abc", b"abc"), [0]);
+            assert_eq!(algorithm.find_all(b"\x00\xff\x00\xff", b"\xff\x00"), [1]);
+        }
+    }
+
+    #[test]
+    fn test_char_offsets() {
+        let text = "æøå and æøå, 世界 and 世界";
+        for algorithm in Algorithm::ALL {
+            let matches = find_all(text, "and", algorithm);
+            let expected: Vec<TextMatch> = text
+                .match_indices("and")
+                .map(|(byte, _)| TextMatch {
+                    byte,
+                    char: text[..byte].chars().count(),
+                })
+                .collect();
+            assert_eq!(matches, expected);
+            assert_eq!(matches[0], TextMatch { byte: 7, char: 4 });
+
+            let matches = find_all(text, "世界", algorithm);
+            assert_eq!(matches.iter().map(|m| m.char).collect::<Vec<_>>(), [13, 20]);
+        }
+    }
+
+    #[test]
+    fn test_moby_dick() {
+        let text = fs::read_to_string("data/moby_dick.txt").unwrap();
+        for pattern in ["Ahab", "white whale", "Call me Ishmael"] {
+            let expected: Vec<usize> = text.match_indices(pattern).map(|(i, _)| i).collect();
+            assert!(!expected.is_empty());
+            for algorithm in Algorithm::ALL {
+                assert_eq!(
+                    algorithm.find_all(text.as_bytes(), pattern.as_bytes()),
+                    expected
+                );
+            }
+        }
+    }
+
+    // cargo test --release bench_text_search -- --ignored --nocapture
+    #[test]
+    #[ignore]
+    fn bench_text_search() {
+        let text = fs::read_to_string("data/moby_dick.txt").unwrap();
+        let text = text.as_bytes();
+        println!("{} bytes", text.len());
+
+        for pattern in [
+            "a",
+            "the",
+            "whale",
+            "Queequeg",
+            "the Pequod was",
+            "zzzz not there at all",
+        ] {
+            println!("{pattern:?}");
+            let start = Instant::now();
+            let expected = naive_find_all(text, pattern.as_bytes());
+            println!("  {:<22} {:?}", "naive", start.elapsed());
+
+            for algorithm in Algorithm::ALL {
+                let start = Instant::now();
+                let matches = algorithm.find_all(text, pattern.as_bytes());
+                let elapsed = start.elapsed();
+                println!(
+                    "  {:<22} {elapsed:?} ({} matches)",
+                    algorithm.name(),
+                    matches.len()
+                );
+                assert_eq!(matches, expected);
+            }
+        }
+    }
+}
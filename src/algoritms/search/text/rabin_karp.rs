@@ -0,0 +1,49 @@
+// Rabin–Karp. Keeps a rolling hash of the current window and only compares bytes when
+// the hashes agree. O(n + m) expected, and the same rolling hash extends to searching for
+// many patterns of one length at once.
+
+const BASE: u64 = 256;
+/// prime modulus, small enough that `hash * BASE` can't overflow a u64
+const MODULUS: u64 = 1_000_000_007;
+
+/// Byte offsets of every (possibly overlapping) occurrence of `pattern` in `text`
+pub fn rabin_karp_find_all(text: &[u8], pattern: &[u8]) -> Vec<usize> {
+    let mut matches = Vec::new();
+    let m = pattern.len();
+    if m == 0 || m > text.len() {
+        return matches;
+    }
+
+    let hash = |bytes: &[u8]| {
+        bytes
+            .iter()
+            .fold(0, |h, &b| (h * BASE + b as u64) % MODULUS)
+    };
+    // BASE^(m - 1), the weight of the byte leaving the window
+    let leading = (1..m).fold(1, |p, _| p * BASE % MODULUS);
+
+    let target = hash(pattern);
+    let mut window = hash(&text[..m]);
+    for start in 0..=text.len() - m {
+        if window == target && &text[start..start + m] == pattern {
+            matches.push(start);
+        }
+        if start + m < text.len() {
+            let outgoing = text[start] as u64 * leading % MODULUS;
+            window = ((window + MODULUS - outgoing) * BASE + text[start + m] as u64) % MODULUS;
+        }
+    }
+    matches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rabin_karp_find_all() {
+        assert_eq!(rabin_karp_find_all(b"abababa", b"aba"), [0, 2, 4]);
+        assert_eq!(rabin_karp_find_all(b"\xff\xff\xff", b"\xff\xff"), [0, 1]);
+        assert_eq!(rabin_karp_find_all(b"abc", b"abc"), [0]);
+    }
+}
@@ -0,0 +1,410 @@
+// Suffix array: the start of every suffix of the text, in sorted order. Built with SA-IS
+// (Nong, Zhang and Chan) in O(n), plus the LCP array (Kasai et al.) in O(n), where
+// `lcp[i]` is the length of the common prefix of suffixes `i - 1` and `i` in sorted order.
+//
+// Once built, every occurrence of a pattern is one contiguous block of the array, found
+// with two binary searches in O(m log n). Repeats show up as large LCP values between
+// neighbours, which gives the longest repeated substring and, on two texts joined by a
+// separator, the longest common substring.
+
+use std::cmp::Ordering;
+use std::ops::Range;
+
+use crate::algoritms::search::partition_point;
+
+const EMPTY: usize = usize::MAX;
+
+pub struct SuffixArray<'t> {
+    text: &'t [u8],
+    suffixes: Vec<usize>,
+    lcp: Vec<usize>,
+}
+
+impl<'t> SuffixArray<'t> {
+    pub fn new(text: &'t [u8]) -> Self {
+        let symbols: Vec<usize> = text.iter().map(|&b| b as usize).collect();
+        let suffixes = sa_is(&symbols, u8::MAX as usize);
+        let lcp = kasai(&symbols, &suffixes);
+        Self {
+            text,
+            suffixes,
+            lcp,
+        }
+    }
+
+    pub fn text(&self) -> &'t [u8] {
+        self.text
+    }
+
+    /// Start of every suffix, in sorted order
+    pub fn suffixes(&self) -> &[usize] {
+        &self.suffixes
+    }
+
+    /// `lcp[i]` is the common prefix length of the suffixes at `i - 1` and `i`, `lcp[0]` is 0
+    pub fn lcp(&self) -> &[usize] {
+        &self.lcp
+    }
+
+    /// The block of the suffix array whose suffixes start with `pattern`
+    pub fn range(&self, pattern: &[u8]) -> Range<usize> {
+        // compares only the first pattern.len() bytes, so every suffix the pattern is a
+        // prefix of compares equal
+        let prefix_cmp = |&start: &usize| {
+            let end = (start + pattern.len()).min(self.text.len());
+            self.text[start..end].cmp(pattern)
+        };
+        let start = partition_point(&self.suffixes, |s| prefix_cmp(s) == Ordering::Less);
+        let end = start
+            + partition_point(&self.suffixes[start..], |s| {
+                prefix_cmp(s) == Ordering::Equal
+            });
+        start..end
+    }
+
+    /// How many times `pattern` occurs, overlapping ones included. An empty pattern has no
+    /// occurrences, as with the other searches in this module.
+    pub fn count(&self, pattern: &[u8]) -> usize {
+        if pattern.is_empty() {
+            return 0;
+        }
+        self.range(pattern).len()
+    }
+
+    /// Byte offsets of every occurrence of `pattern`, in increasing order
+    pub fn locate(&self, pattern: &[u8]) -> Vec<usize> {
+        if pattern.is_empty() {
+            return Vec::new();
+        }
+        let mut positions = self.suffixes[self.range(pattern)].to_vec();
+        positions.sort_unstable();
+        positions
+    }
+
+    /// The longest substring that occurs at least twice (the occurrences may overlap), as
+    /// the range of its first occurrence. `None` if no byte repeats.
+    pub fn longest_repeated_substring(&self) -> Option<Range<usize>> {
+        // the longest repeat is the longest prefix two neighbouring suffixes share
+        let (i, &len) = self
+            .lcp
+            .iter()
+            .enumerate()
+            .max_by_key(|&(i, &len)| (len, std::cmp::Reverse(i)))?;
+        if len == 0 {
+            return None;
+        }
+        let start = self.suffixes[self.range(&self.text[self.suffixes[i]..][..len])]
+            .iter()
+            .copied()
+            .min()
+            .unwrap();
+        Some(start..start + len)
+    }
+}
+
+/// The longest substring of both `a` and `b`, as its range in `a` and its range in `b`.
+/// `None` if they have no byte in common.
+pub fn longest_common_substring(a: &[u8], b: &[u8]) -> Option<(Range<usize>, Range<usize>)> {
+    // a, then a separator that occurs nowhere else, then b: a common prefix of a suffix
+    // of a and a suffix of b can't run past the separator
+    let separator = u8::MAX as usize + 1;
+    let symbols: Vec<usize> = a
+        .iter()
+        .map(|&x| x as usize)
+        .chain([separator])
+        .chain(b.iter().map(|&x| x as usize))
+        .collect();
+    let suffixes = sa_is(&symbols, separator);
+    let lcp = kasai(&symbols, &suffixes);
+
+    let in_a = |start: usize| start < a.len();
+    let (i, len) = (1..suffixes.len())
+        .filter(|&i| in_a(suffixes[i - 1]) != in_a(suffixes[i]))
+        .map(|i| (i, lcp[i]))
+        .max_by_key(|&(i, len)| (len, std::cmp::Reverse(i)))?;
+    if len == 0 {
+        return None;
+    }
+
+    let (x, y) = (suffixes[i - 1], suffixes[i]);
+    let (start_a, start_b) = if in_a(x) { (x, y) } else { (y, x) };
+    let start_b = start_b - a.len() - 1;
+    Some((start_a..start_a + len, start_b..start_b + len))
+}
+
+/// Suffix array of `s`, whose symbols are all in `0..=upper`
+fn sa_is(s: &[usize], upper: usize) -> Vec<usize> {
+    let n = s.len();
+    match n {
+        0 => return Vec::new(),
+        1 => return vec![0],
+        2 => return if s[0] < s[1] { vec![0, 1] } else { vec![1, 0] },
+        _ => {}
+    }
+
+    // is_s[i]: the suffix at i is smaller than the one at i + 1 (S-type), else L-type
+    let mut is_s = vec![false; n];
+    for i in (0..n - 1).rev() {
+        is_s[i] = if s[i] == s[i + 1] {
+            is_s[i + 1]
+        } else {
+            s[i] < s[i + 1]
+        };
+    }
+
+    // each symbol's bucket holds its L-type suffixes first, then its S-type ones:
+    // bucket_l[c] is where the L-type part of c starts, bucket_s[c] the S-type part
+    let mut bucket_l = vec![0; upper + 2];
+    let mut bucket_s = vec![0; upper + 2];
+    for i in 0..n {
+        if is_s[i] {
+            bucket_l[s[i] + 1] += 1;
+        } else {
+            bucket_s[s[i]] += 1;
+        }
+    }
+    for c in 0..=upper {
+        bucket_s[c] += bucket_l[c];
+        bucket_l[c + 1] += bucket_s[c];
+    }
+
+    // places the LMS suffixes in the given order, then induces the L-type suffixes left
+    // to right and the S-type ones right to left
+    let induce = |lms: &[usize], sa: &mut Vec<usize>| {
+        sa.clear();
+        sa.resize(n, EMPTY);
+        let mut next = bucket_s.clone();
+        for &d in lms {
+            sa[next[s[d]]] = d;
+            next[s[d]] += 1;
+        }
+
+        let mut next = bucket_l.clone();
+        sa[next[s[n - 1]]] = n - 1;
+        next[s[n - 1]] += 1;
+        for i in 0..n {
+            let v = sa[i];
+            if v != EMPTY && v >= 1 && !is_s[v - 1] {
+                sa[next[s[v - 1]]] = v - 1;
+                next[s[v - 1]] += 1;
+            }
+        }
+
+        let mut next = bucket_l.clone();
+        for i in (0..n).rev() {
+            let v = sa[i];
+            if v != EMPTY && v >= 1 && is_s[v - 1] {
+                next[s[v - 1] + 1] -= 1;
+                sa[next[s[v - 1] + 1]] = v - 1;
+            }
+        }
+    };
+
+    // leftmost S-type positions: an S-type suffix right after an L-type one
+    let lms: Vec<usize> = (1..n).filter(|&i| !is_s[i - 1] && is_s[i]).collect();
+    let mut lms_index = vec![EMPTY; n];
+    for (k, &i) in lms.iter().enumerate() {
+        lms_index[i] = k;
+    }
+
+    let mut sa = Vec::with_capacity(n);
+    induce(&lms, &mut sa);
+
+    if !lms.is_empty() {
+        // the induced order sorts the LMS substrings (from one LMS position to the next);
+        // name them by rank and sort the LMS suffixes by recursing on the names
+        let mut sorted_lms: Vec<usize> = sa
+            .iter()
+            .copied()
+            .filter(|&v| lms_index[v] != EMPTY)
+            .collect();
+        let lms_end = |i: usize| lms.get(lms_index[i] + 1).copied().unwrap_or(n);
+
+        let mut names = vec![0; lms.len()];
+        let mut name = 0;
+        for pair in sorted_lms.windows(2) {
+            let (l, r) = (pair[0], pair[1]);
+            let (end_l, end_r) = (lms_end(l), lms_end(r));
+            let same =
+                end_l - l == end_r - r && end_l < n && end_r < n && s[l..=end_l] == s[r..=end_r];
+            if !same {
+                name += 1;
+            }
+            names[lms_index[r]] = name;
+        }
+
+        let sorted_names = sa_is(&names, name);
+        for (slot, &k) in sorted_lms.iter_mut().zip(&sorted_names) {
+            *slot = lms[k];
+        }
+        induce(&sorted_lms, &mut sa);
+    }
+    sa
+}
+
+/// LCP array of `s` given its suffix array, in O(n): the common prefix with the previous
+/// suffix in sorted order shrinks by at most one when moving from suffix i to i + 1
+fn kasai(s: &[usize], suffixes: &[usize]) -> Vec<usize> {
+    let n = s.len();
+    let mut rank = vec![0; n];
+    for (i, &start) in suffixes.iter().enumerate() {
+        rank[start] = i;
+    }
+
+    let mut lcp = vec![0; n];
+    let mut len: usize = 0;
+    for start in 0..n {
+        if rank[start] == 0 {
+            len = 0;
+            continue;
+        }
+        let previous = suffixes[rank[start] - 1];
+        while start + len < n && previous + len < n && s[start + len] == s[previous + len] {
+            len += 1;
+        }
+        lcp[rank[start]] = len;
+        len = len.saturating_sub(1);
+    }
+    lcp
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::algoritms::search::text::tests::random_bytes;
+    use crate::algoritms::sorting::quick_sort::XorShift64;
+
+    fn naive_suffix_array(text: &[u8]) -> Vec<usize> {
+        let mut suffixes: Vec<usize> = (0..text.len()).collect();
+        suffixes.sort_by_key(|&i| &text[i..]);
+        suffixes
+    }
+
+    fn common_prefix(a: &[u8], b: &[u8]) -> usize {
+        a.iter().zip(b).take_while(|(x, y)| x == y).count()
+    }
+
+    #[test]
+    fn test_matches_naive_construction() {
+        let mut rng = XorShift64::new(42);
+        for alphabet in [1, 2, 3, 26] {
+            for _ in 0..200 {
+                let text = random_bytes(rng.next_below(150), alphabet, &mut rng);
+                let index = SuffixArray::new(&text);
+                let expected = naive_suffix_array(&text);
+                assert_eq!(index.suffixes(), expected, "{:?}", text);
+
+                for i in 1..text.len() {
+                    let lcp = common_prefix(&text[expected[i - 1]..], &text[expected[i]..]);
+                    assert_eq!(index.lcp()[i], lcp);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_banana() {
+        let index = SuffixArray::new(b"banana");
+        assert_eq!(index.suffixes(), [5, 3, 1, 0, 4, 2]);
+        assert_eq!(index.lcp(), [0, 1, 3, 0, 0, 2]);
+        assert_eq!(index.count(b"ana"), 2);
+        assert_eq!(index.locate(b"a"), [1, 3, 5]);
+        assert_eq!(index.locate(b"nab"), []);
+        assert_eq!(index.count(b""), 0);
+        assert_eq!(index.longest_repeated_substring(), Some(1..4));
+
+        assert!(SuffixArray::new(b"").suffixes().is_empty());
+        assert_eq!(SuffixArray::new(b"abc").longest_repeated_substring(), None);
+    }
+
+    #[test]
+    fn test_count_and_locate_match_naive_search() {
+        let mut rng = XorShift64::new(7);
+        for alphabet in [2, 4] {
+            let text = random_bytes(2000, alphabet, &mut rng);
+            let index = SuffixArray::new(&text);
+            for _ in 0..300 {
+                let pattern = random_bytes(1 + rng.next_below(8), alphabet, &mut rng);
+                let expected: Vec<usize> = (0..=text.len() - pattern.len())
+                    .filter(|&i| text[i..].starts_with(&pattern))
+                    .collect();
+                assert_eq!(index.locate(&pattern), expected);
+                assert_eq!(index.count(&pattern), expected.len());
+            }
+        }
+    }
+
+    #[test]
+    fn test_longest_repeated_substring() {
+        let mut rng = XorShift64::new(3);
+        for _ in 0..100 {
+            let text = random_bytes(1 + rng.next_below(60), 3, &mut rng);
+            let index = SuffixArray::new(&text);
+            // the longest length that has a substring occurring twice
+            let longest = (1..text.len())
+                .rev()
+                .find(|&len| (0..=text.len() - len).any(|i| index.count(&text[i..i + len]) >= 2))
+                .unwrap_or(0);
+            match index.longest_repeated_substring() {
+                Some(range) => {
+                    assert_eq!(range.len(), longest);
+                    assert!(index.count(&text[range]) >= 2);
+                }
+                None => assert_eq!(longest, 0),
+            }
+        }
+        // overlapping occurrences count
+        let index = SuffixArray::new(b"xaaaay");
+        assert_eq!(index.longest_repeated_substring(), Some(1..4));
+    }
+
+    #[test]
+    fn test_longest_common_substring() {
+        let (a, b) = longest_common_substring(b"xabcdey", b"zzbcdezz").unwrap();
+        assert_eq!((a, b), (2..6, 2..6));
+        assert_eq!(longest_common_substring(b"abc", b"xyz"), None);
+        assert_eq!(longest_common_substring(b"", b"abc"), None);
+
+        let mut rng = XorShift64::new(11);
+        for _ in 0..100 {
+            let x = random_bytes(1 + rng.next_below(40), 3, &mut rng);
+            let y = random_bytes(1 + rng.next_below(40), 3, &mut rng);
+            let longest = (0..x.len())
+                .flat_map(|i| (0..y.len()).map(move |j| (i, j)))
+                .map(|(i, j)| common_prefix(&x[i..], &y[j..]))
+                .max()
+                .unwrap();
+            let (in_x, in_y) = longest_common_substring(&x, &y).unwrap();
+            assert_eq!(in_x.len(), longest);
+            assert_eq!(x[in_x], y[in_y]);
+        }
+    }
+
+    #[test]
+    fn test_moby_dick_differences() {
+        let original = fs::read("data/moby_dick.txt").unwrap();
+        let changed = fs::read("data/moby_dick2.txt").unwrap();
+        let index = SuffixArray::new(&original);
+
+        assert_eq!(index.locate(b"Call me Ishmael"), [28869]);
+        let text = String::from_utf8_lossy(&original);
+        assert_eq!(index.count(b"Ahab"), text.matches("Ahab").count());
+
+        // words of the changed copy that never occur in the original are where it differs
+        let changed_text = String::from_utf8_lossy(&changed);
+        let new_words: Vec<&str> = changed_text
+            .split_whitespace()
+            .filter(|word| index.count(word.as_bytes()) == 0)
+            .collect();
+        assert!(new_words.contains(&"STALT"));
+        assert!(new_words.contains(&"PLOJECT"));
+        assert!(!new_words.contains(&"whale"));
+
+        // the copies share long unchanged stretches
+        let (in_original, in_changed) = longest_common_substring(&original, &changed).unwrap();
+        assert!(in_original.len() > 1000, "{}", in_original.len());
+        assert_eq!(original[in_original], changed[in_changed]);
+    }
+}
@@ -0,0 +1,132 @@
+// Two-Way (Crochemore–Perrin), the algorithm behind glibc's memmem and Rust's
+// `str::find`. The pattern is split at a critical factorization u|v: v is matched left
+// to right, then u right to left. O(n + m) time and O(1) extra space.
+
+/// Byte offsets of every (possibly overlapping) occurrence of `pattern` in `text`
+pub fn two_way_find_all(text: &[u8], pattern: &[u8]) -> Vec<usize> {
+    let mut matches = Vec::new();
+    let (n, m) = (text.len(), pattern.len());
+    if m == 0 || m > n {
+        return matches;
+    }
+
+    // critical position: the longer of the two maximal suffixes (by < and by >)
+    let (suffix_less, period_less) = maximal_suffix(pattern, false);
+    let (suffix_greater, period_greater) = maximal_suffix(pattern, true);
+    let (ell, period) = if suffix_less > suffix_greater {
+        (suffix_less, period_less)
+    } else {
+        (suffix_greater, period_greater)
+    };
+    // u = pattern[..split], v = pattern[split..]
+    let split = (ell + 1) as usize;
+
+    if pattern[..split] == pattern[period..period + split] {
+        // periodic pattern: after a match or a mismatch in u, the first
+        // `memory` bytes of the next window are known to match already
+        let mut memory = 0;
+        let mut start = 0;
+        while start + m <= n {
+            let mut i = split.max(memory);
+            while i < m && pattern[i] == text[start + i] {
+                i += 1;
+            }
+            if i < m {
+                start += i + 1 - split;
+                memory = 0;
+                continue;
+            }
+
+            let mut i = split;
+            while i > memory && pattern[i - 1] == text[start + i - 1] {
+                i -= 1;
+            }
+            if i <= memory {
+                matches.push(start);
+            }
+            start += period;
+            memory = m - period;
+        }
+    } else {
+        // no long period: any shift below max(|u|, |v|) + 1 would skip nothing
+        let shift = split.max(m - split) + 1;
+        let mut start = 0;
+        while start + m <= n {
+            let mut i = split;
+            while i < m && pattern[i] == text[start + i] {
+                i += 1;
+            }
+            if i < m {
+                start += i + 1 - split;
+                continue;
+            }
+
+            let mut i = split;
+            while i > 0 && pattern[i - 1] == text[start + i - 1] {
+                i -= 1;
+            }
+            if i == 0 {
+                matches.push(start);
+            }
+            start += shift;
+        }
+    }
+    matches
+}
+
+/// Start of the lexicographically maximal suffix minus one (so -1 for the whole
+/// pattern), and the period of that suffix. `reversed` flips the byte order.
+fn maximal_suffix(pattern: &[u8], reversed: bool) -> (isize, usize) {
+    let m = pattern.len();
+    let mut suffix: isize = -1;
+    let (mut j, mut k, mut period) = (0, 1, 1);
+
+    while j + k < m {
+        let a = pattern[j + k];
+        let b = pattern[(suffix + k as isize) as usize];
+        let (a, b) = if reversed { (b, a) } else { (a, b) };
+        if a < b {
+            // suffix at j + k is smaller, the whole stretch so far is one period
+            j += k;
+            k = 1;
+            period = (j as isize - suffix) as usize;
+        } else if a == b {
+            if k == period {
+                j += period;
+                k = 1;
+            } else {
+                k += 1;
+            }
+        } else {
+            // found a larger suffix
+            suffix = j as isize;
+            j += 1;
+            k = 1;
+            period = 1;
+        }
+    }
+    (suffix, period)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_two_way_find_all() {
+        assert_eq!(two_way_find_all(b"abababa", b"aba"), [0, 2, 4]);
+        assert_eq!(two_way_find_all(b"aaaaa", b"aa"), [0, 1, 2, 3]);
+        assert_eq!(
+            two_way_find_all(b"GCATCGCAGAGAGTATACAGTACG", b"GCAGAGAG"),
+            [5]
+        );
+    }
+
+    #[test]
+    fn test_maximal_suffix() {
+        // "banana": largest suffix is "nana" at 2, period 2
+        assert_eq!(maximal_suffix(b"banana", false), (1, 2));
+        // with the byte order flipped it's "anana" at 1, "a" is a prefix so it loses
+        assert_eq!(maximal_suffix(b"banana", true), (0, 2));
+    }
+}
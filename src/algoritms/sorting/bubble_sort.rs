@@ -1,11 +1,22 @@
+use super::instrument::{Probe, SortObserver};
+
 pub fn bubble_sort<T: Clone + Ord>(input_list: &[T]) -> Vec<T> {
+    bubble_sort_observed(input_list, &mut ())
+}
+
+pub fn bubble_sort_observed<T: Clone + Ord, O: SortObserver<T>>(
+    input_list: &[T],
+    observer: &mut O,
+) -> Vec<T> {
+    let mut probe = Probe::new(observer);
     let mut result = input_list.to_vec();
     let n = result.len();
+    probe.alloc(n);
 
     for i in 0..n {
         for j in 0..(n - i - 1) {
-            if result[j] > result[j + 1] {
-                result.swap(j, j + 1);
+            if probe.less(&result, j + 1, j) {
+                probe.swap(&mut result, j, j + 1);
             }
         }
     }
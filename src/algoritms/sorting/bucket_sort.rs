@@ -0,0 +1,116 @@
+// Bucket sort for floats, expected O(n) when the values are spread evenly over their
+// range. Clustered data degrades towards insertion sort inside a few large buckets.
+
+use super::instrument::{Probe, SortObserver};
+
+/// Sorts finite floats by scattering them over `len` buckets between min and max.
+/// Panics on NaN, infinities would make every bucket width infinite.
+pub fn bucket_sort(input_list: &mut [f64]) {
+    bucket_sort_observed(input_list, &mut ());
+}
+
+/// Buckets are written back first and then insertion sorted in place, so comparisons
+/// and swaps are reported at their final positions
+pub fn bucket_sort_observed<O: SortObserver<f64>>(input_list: &mut [f64], observer: &mut O) {
+    let len = input_list.len();
+    if len < 2 {
+        return;
+    }
+    assert!(
+        input_list.iter().all(|x| x.is_finite()),
+        "bucket sort needs finite values"
+    );
+
+    let min = input_list.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = input_list.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    if min == max {
+        return;
+    }
+
+    let mut probe = Probe::new(observer);
+    let mut buckets: Vec<Vec<f64>> = vec![Vec::new(); len];
+    probe.alloc(len);
+    let width = max - min;
+    for &x in input_list.iter() {
+        // max itself would land one past the end
+        let index = (((x - min) / width) * len as f64) as usize;
+        buckets[index.min(len - 1)].push(x);
+    }
+
+    let mut k = 0;
+    for bucket in &buckets {
+        for value in bucket {
+            probe.write(input_list, k, value);
+            k += 1;
+        }
+    }
+
+    // buckets are tiny on average, insertion sort is the cheapest way to finish them
+    let mut start = 0;
+    for bucket in &buckets {
+        let end = start + bucket.len();
+        let mut probe = probe.at(start);
+        let part = &mut input_list[start..end];
+        for i in 1..part.len() {
+            let mut j = i;
+            while j > 0 && {
+                probe.compared(j, j - 1);
+                part[j] < part[j - 1]
+            } {
+                probe.swap(part, j, j - 1);
+                j -= 1;
+            }
+        }
+        start = end;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algoritms::sorting::quick_sort::XorShift64;
+
+    #[test]
+    fn test_bucket_sort_basic() {
+        let mut list = vec![0.42, 0.32, 0.23, 0.52, 0.25, 0.47, 0.51];
+        bucket_sort(&mut list);
+        assert_eq!(list, [0.23, 0.25, 0.32, 0.42, 0.47, 0.51, 0.52]);
+    }
+
+    #[test]
+    fn test_bucket_sort_negative_and_duplicates() {
+        let mut list = vec![3.0, -1.5, 3.0, 0.0, -100.25, 2.5];
+        bucket_sort(&mut list);
+        assert_eq!(list, [-100.25, -1.5, 0.0, 2.5, 3.0, 3.0]);
+    }
+
+    #[test]
+    fn test_bucket_sort_all_same_and_empty() {
+        let mut same = vec![1.5; 10];
+        bucket_sort(&mut same);
+        assert_eq!(same, vec![1.5; 10]);
+
+        let mut empty: Vec<f64> = vec![];
+        bucket_sort(&mut empty);
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn test_bucket_sort_uniform_matches_std() {
+        let mut rng = XorShift64::new(31);
+        let mut list: Vec<f64> = (0..10_000)
+            .map(|_| (rng.next_u64() >> 11) as f64 / (1u64 << 53) as f64)
+            .collect();
+        let mut expected = list.clone();
+        expected.sort_by(f64::total_cmp);
+        bucket_sort(&mut list);
+        assert_eq!(list, expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "finite")]
+    fn test_bucket_sort_nan_panics() {
+        let mut list = vec![1.0, f64::NAN, 0.5];
+        bucket_sort(&mut list);
+    }
+}
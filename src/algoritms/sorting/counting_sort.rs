@@ -0,0 +1,157 @@
+// Counting sort, O(n + k) where k is the size of the key range. Only worth it when the
+// keys are small integers, the count table is allocated for the whole range. When the
+// range is far larger than the input, LSD radix sort takes over instead.
+
+use super::instrument::{Probe, SortObserver};
+use super::radix_sort::radix_sort_observed;
+
+/// Count slots per item above which radix sort is used instead
+const MAX_SLOTS_PER_ITEM: usize = 4;
+/// Ranges up to this size always get counted, the table is small either way
+const SMALL_RANGE: usize = 1 << 16;
+
+/// Sorts integers by counting how often each value between min and max occurs
+pub fn counting_sort(input_list: &mut [i32]) {
+    counting_sort_observed(input_list, &mut ());
+}
+
+pub fn counting_sort_observed<O: SortObserver<i32>>(input_list: &mut [i32], observer: &mut O) {
+    let (Some(&min), Some(&max)) = (input_list.iter().min(), input_list.iter().max()) else {
+        return;
+    };
+    let range = (max as i64 - min as i64) as usize + 1;
+    if range > SMALL_RANGE && range / MAX_SLOTS_PER_ITEM > input_list.len() {
+        radix_sort_observed(input_list, observer);
+        return;
+    }
+    let mut probe = Probe::new(observer);
+
+    let mut counts = vec![0usize; range];
+    probe.alloc(range);
+    for &x in input_list.iter() {
+        counts[(x as i64 - min as i64) as usize] += 1;
+    }
+
+    let mut k = 0;
+    for (offset, &count) in counts.iter().enumerate() {
+        let value = (min as i64 + offset as i64) as i32;
+        for _ in 0..count {
+            probe.write(input_list, k, &value);
+            k += 1;
+        }
+    }
+}
+
+/// Stable counting sort of arbitrary items by a key in `0..key_range`.
+/// Used as the per-digit pass of LSD radix sort.
+pub fn counting_sort_by_key<T: Clone>(
+    input_list: &mut [T],
+    key_range: usize,
+    key: impl Fn(&T) -> usize,
+) {
+    counting_sort_by_key_observed(input_list, key_range, key, &mut ());
+}
+
+pub fn counting_sort_by_key_observed<T: Clone, O: SortObserver<T>>(
+    input_list: &mut [T],
+    key_range: usize,
+    key: impl Fn(&T) -> usize,
+    observer: &mut O,
+) {
+    let mut probe = Probe::new(observer);
+
+    // prefix sums: starts[k] is where the first item with key k goes
+    let mut starts = vec![0usize; key_range + 1];
+    probe.alloc(key_range + 1);
+    for item in input_list.iter() {
+        starts[key(item) + 1] += 1;
+    }
+    for k in 1..starts.len() {
+        starts[k] += starts[k - 1];
+    }
+
+    let mut output = input_list.to_vec();
+    probe.alloc(output.len());
+    for item in input_list.iter() {
+        let k = key(item);
+        output[starts[k]] = item.clone();
+        starts[k] += 1;
+    }
+    for (i, item) in output.iter().enumerate() {
+        probe.write(input_list, i, item);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algoritms::sorting::test_utils::{Record, random_values};
+
+    #[test]
+    fn test_counting_sort_basic() {
+        let mut list = vec![3, 1, 4, 1, 5, 9, 2, 6];
+        counting_sort(&mut list);
+        assert_eq!(list, [1, 1, 2, 3, 4, 5, 6, 9]);
+    }
+
+    #[test]
+    fn test_counting_sort_negative_and_empty() {
+        let mut list = vec![0, -3, 5, -3, 2, -10];
+        counting_sort(&mut list);
+        assert_eq!(list, [-10, -3, -3, 0, 2, 5]);
+
+        let mut empty: Vec<i32> = vec![];
+        counting_sort(&mut empty);
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn test_counting_sort_extremes() {
+        let mut list = vec![i32::MAX, i32::MAX - 1, i32::MAX];
+        counting_sort(&mut list);
+        assert_eq!(list, [i32::MAX - 1, i32::MAX, i32::MAX]);
+    }
+
+    #[test]
+    fn test_counting_sort_huge_range() {
+        // a count table for the whole i32 range would be 32 GB
+        let mut list = vec![i32::MAX, i32::MIN];
+        counting_sort(&mut list);
+        assert_eq!(list, [i32::MIN, i32::MAX]);
+
+        let mut list: Vec<i32> = random_values(5000, 1 << 32, 5)
+            .iter()
+            .map(|&x| (x - (1 << 31)) as i32)
+            .collect();
+        let mut expected = list.clone();
+        expected.sort();
+        counting_sort(&mut list);
+        assert_eq!(list, expected);
+    }
+
+    #[test]
+    fn test_counting_sort_matches_std() {
+        let mut list: Vec<i32> = random_values(5000, 300, 4)
+            .iter()
+            .map(|&x| x as i32 - 150)
+            .collect();
+        let mut expected = list.clone();
+        expected.sort();
+        counting_sort(&mut list);
+        assert_eq!(list, expected);
+    }
+
+    #[test]
+    fn test_counting_sort_by_key_is_stable() {
+        let mut list: Vec<Record<usize>> = random_values(1000, 10, 6)
+            .iter()
+            .enumerate()
+            .map(|(i, &k)| Record::new(k as i32, i))
+            .collect();
+        counting_sort_by_key(&mut list, 10, |r| r.key as usize);
+
+        assert!(list.windows(2).all(|w| {
+            w[0].key < w[1].key || (w[0].key == w[1].key && w[0].payload < w[1].payload)
+        }));
+    }
+}
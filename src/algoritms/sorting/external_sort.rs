@@ -0,0 +1,442 @@
+// External merge sort for files larger than memory:
+// 1. read items until the memory budget is used up, sort them in memory, spill as a run
+// 2. k-way merge the runs through a min heap, in several passes if there are too many
+//    runs to keep open at once
+// Items are lines (split on '\n') or fixed-size binary records, compared as raw bytes.
+
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use super::string_sort::msd_radix_sort;
+use crate::data_structures::binary_heap::MoHeap;
+
+// unique run file names across sorts running in the same process
+static RUN_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ItemFormat {
+    /// '\n' terminated lines, the terminator is not part of the sort key
+    Lines,
+    /// records of exactly this many bytes
+    Records(usize),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SortStats {
+    pub items: usize,
+    /// sorted runs spilled in the first phase
+    pub runs: usize,
+    /// merge passes over the data, 0 when everything fit in memory
+    pub merge_passes: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct ExternalSort {
+    memory_budget: usize,
+    max_merge_width: usize,
+    temp_dir: PathBuf,
+}
+
+impl Default for ExternalSort {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ExternalSort {
+    /// 64 MiB budget, merges up to 64 runs at once, runs go to the system temp dir
+    pub fn new() -> Self {
+        Self {
+            memory_budget: 64 * 1024 * 1024,
+            max_merge_width: 64,
+            temp_dir: std::env::temp_dir(),
+        }
+    }
+
+    /// Approximate number of bytes of items held in memory while building runs
+    pub fn memory_budget(mut self, bytes: usize) -> Self {
+        self.memory_budget = bytes.max(1);
+        self
+    }
+
+    /// Most runs merged (and files kept open) at once
+    pub fn max_merge_width(mut self, width: usize) -> Self {
+        assert!(width >= 2, "merge width must be at least 2");
+        self.max_merge_width = width;
+        self
+    }
+
+    pub fn temp_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.temp_dir = dir.into();
+        self
+    }
+
+    pub fn sort_lines(&self, input: &Path, output: &Path) -> io::Result<SortStats> {
+        self.sort_file(input, output, ItemFormat::Lines)
+    }
+
+    pub fn sort_records(
+        &self,
+        input: &Path,
+        output: &Path,
+        record_size: usize,
+    ) -> io::Result<SortStats> {
+        assert!(record_size > 0, "record size must be non-zero");
+        self.sort_file(input, output, ItemFormat::Records(record_size))
+    }
+
+    pub fn sort_file(
+        &self,
+        input: &Path,
+        output: &Path,
+        format: ItemFormat,
+    ) -> io::Result<SortStats> {
+        let mut reader = BufReader::new(File::open(input)?);
+        let mut stats = SortStats {
+            items: 0,
+            runs: 0,
+            merge_passes: 0,
+        };
+
+        // phase 1: sorted runs
+        let mut runs = Vec::new();
+        let mut chunk: Vec<Vec<u8>> = Vec::new();
+        let mut chunk_bytes = 0;
+
+        while let Some(item) = read_item(&mut reader, format)? {
+            stats.items += 1;
+            chunk_bytes += item.len() + std::mem::size_of::<Vec<u8>>();
+            chunk.push(item);
+
+            if chunk_bytes >= self.memory_budget {
+                runs.push(self.spill(&mut chunk, format)?);
+                chunk_bytes = 0;
+            }
+        }
+        stats.runs = runs.len();
+
+        // everything fit in memory, no need to touch the disk twice
+        if runs.is_empty() {
+            msd_radix_sort(&mut chunk);
+            return write_items(output, &chunk, format).map(|_| stats);
+        }
+        if !chunk.is_empty() {
+            runs.push(self.spill(&mut chunk, format)?);
+            stats.runs += 1;
+        }
+
+        // phase 2: merge until the remaining runs fit in one final merge
+        while runs.len() > self.max_merge_width {
+            let mut merged = Vec::new();
+            for group in runs.chunks(self.max_merge_width) {
+                let run = self.new_run();
+                merge_runs(group, &run.path, format)?;
+                merged.push(run);
+            }
+            runs = merged;
+            stats.merge_passes += 1;
+        }
+        merge_runs(&runs, output, format)?;
+        stats.merge_passes += 1;
+
+        Ok(stats)
+    }
+
+    fn new_run(&self) -> TempRun {
+        let id = RUN_COUNTER.fetch_add(1, Ordering::Relaxed);
+        let name = format!("external-sort-{}-{}.run", std::process::id(), id);
+        TempRun {
+            path: self.temp_dir.join(name),
+        }
+    }
+
+    /// Sorts `chunk` and writes it to a new run file, leaves `chunk` empty
+    fn spill(&self, chunk: &mut Vec<Vec<u8>>, format: ItemFormat) -> io::Result<TempRun> {
+        msd_radix_sort(chunk);
+        let run = self.new_run();
+        write_items(&run.path, chunk, format)?;
+        chunk.clear();
+        Ok(run)
+    }
+}
+
+/// Run file that is deleted when it goes out of scope
+struct TempRun {
+    path: PathBuf,
+}
+
+impl Drop for TempRun {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+/// k-way merge of sorted runs into `output`. Heap entries carry the run index so equal
+/// items come out in run order.
+fn merge_runs(runs: &[TempRun], output: &Path, format: ItemFormat) -> io::Result<()> {
+    let mut readers = runs
+        .iter()
+        .map(|run| File::open(&run.path).map(BufReader::new))
+        .collect::<io::Result<Vec<_>>>()?;
+    let mut writer = BufWriter::new(File::create(output)?);
+
+    let mut heap = MoHeap::new_min();
+    for (index, reader) in readers.iter_mut().enumerate() {
+        if let Some(item) = read_item(reader, format)? {
+            heap.push((item, index));
+        }
+    }
+
+    while let Some((item, index)) = heap.pop() {
+        write_item(&mut writer, &item, format)?;
+        if let Some(next) = read_item(&mut readers[index], format)? {
+            heap.push((next, index));
+        }
+    }
+
+    writer.flush()
+}
+
+fn read_item(reader: &mut impl BufRead, format: ItemFormat) -> io::Result<Option<Vec<u8>>> {
+    match format {
+        ItemFormat::Lines => {
+            let mut line = Vec::new();
+            if reader.read_until(b'\n', &mut line)? == 0 {
+                return Ok(None);
+            }
+            if line.last() == Some(&b'\n') {
+                line.pop();
+            }
+            Ok(Some(line))
+        }
+        ItemFormat::Records(size) => {
+            let mut record = Vec::with_capacity(size);
+            let read = reader.take(size as u64).read_to_end(&mut record)?;
+            match read {
+                0 => Ok(None),
+                n if n == size => Ok(Some(record)),
+                n => Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("truncated record: got {n} of {size} bytes"),
+                )),
+            }
+        }
+    }
+}
+
+fn write_item(writer: &mut impl Write, item: &[u8], format: ItemFormat) -> io::Result<()> {
+    writer.write_all(item)?;
+    if format == ItemFormat::Lines {
+        writer.write_all(b"\n")?;
+    }
+    Ok(())
+}
+
+fn write_items(path: &Path, items: &[Vec<u8>], format: ItemFormat) -> io::Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    for item in items {
+        write_item(&mut writer, item, format)?;
+    }
+    writer.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algoritms::sorting::test_utils::random_values;
+
+    // scratch directory per test, removed at the end
+    struct TestDir(PathBuf);
+
+    impl TestDir {
+        fn new(name: &str) -> Self {
+            let dir = std::env::temp_dir().join(format!(
+                "external-sort-test-{}-{}",
+                name,
+                std::process::id()
+            ));
+            fs::create_dir_all(&dir).unwrap();
+            Self(dir)
+        }
+
+        fn path(&self, file: &str) -> PathBuf {
+            self.0.join(file)
+        }
+
+        fn leftover_runs(&self) -> usize {
+            fs::read_dir(&self.0)
+                .unwrap()
+                .filter(|e| e.as_ref().unwrap().path().extension() == Some("run".as_ref()))
+                .count()
+        }
+    }
+
+    impl Drop for TestDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn test_sort_lines_in_memory() {
+        let dir = TestDir::new("in-memory");
+        fs::write(dir.path("in.txt"), "pear\napple\nfig\n").unwrap();
+
+        let stats = ExternalSort::new()
+            .temp_dir(&dir.0)
+            .sort_lines(&dir.path("in.txt"), &dir.path("out.txt"))
+            .unwrap();
+
+        assert_eq!(
+            fs::read_to_string(dir.path("out.txt")).unwrap(),
+            "apple\nfig\npear\n"
+        );
+        assert_eq!(stats.items, 3);
+        assert_eq!(stats.runs, 0);
+        assert_eq!(stats.merge_passes, 0);
+    }
+
+    #[test]
+    fn test_sort_lines_spills_and_merges() {
+        let dir = TestDir::new("spill");
+        let lines: Vec<String> = random_values(5000, 1_000_000, 9)
+            .iter()
+            .map(|x| format!("line {x}"))
+            .collect();
+        fs::write(dir.path("in.txt"), lines.join("\n")).unwrap();
+
+        let stats = ExternalSort::new()
+            .memory_budget(4 * 1024)
+            .max_merge_width(4)
+            .temp_dir(&dir.0)
+            .sort_lines(&dir.path("in.txt"), &dir.path("out.txt"))
+            .unwrap();
+
+        let mut expected = lines.clone();
+        expected.sort();
+        let output = fs::read_to_string(dir.path("out.txt")).unwrap();
+        assert_eq!(output.lines().collect::<Vec<_>>(), expected);
+
+        assert_eq!(stats.items, 5000);
+        assert!(stats.runs > 16, "expected many runs, got {}", stats.runs);
+        assert!(stats.merge_passes > 1);
+        assert_eq!(dir.leftover_runs(), 0);
+    }
+
+    #[test]
+    fn test_sort_repeated_long_lines() {
+        // long duplicate lines share their whole length as a prefix, both the in-memory
+        // sort and every spilled run have to cope with that
+        let dir = TestDir::new("repeated");
+        let lines: Vec<String> = (0..400)
+            .map(|i| format!("{}{}", "-".repeat(5000), i % 3))
+            .collect();
+        fs::write(dir.path("in.txt"), lines.join("\n")).unwrap();
+        let mut expected = lines.clone();
+        expected.sort();
+
+        for budget in [usize::MAX, 64 * 1024] {
+            let stats = ExternalSort::new()
+                .memory_budget(budget)
+                .temp_dir(&dir.0)
+                .sort_lines(&dir.path("in.txt"), &dir.path("out.txt"))
+                .unwrap();
+            let output = fs::read_to_string(dir.path("out.txt")).unwrap();
+            assert_eq!(output.lines().collect::<Vec<_>>(), expected);
+            assert_eq!(stats.runs == 0, budget == usize::MAX);
+        }
+        assert_eq!(dir.leftover_runs(), 0);
+    }
+
+    #[test]
+    fn test_sort_moby_dick_lines() {
+        let dir = TestDir::new("moby");
+        let input = Path::new("data/moby_dick.txt");
+
+        ExternalSort::new()
+            .memory_budget(256 * 1024)
+            .temp_dir(&dir.0)
+            .sort_lines(input, &dir.path("out.txt"))
+            .unwrap();
+
+        let text = fs::read(input).unwrap();
+        let mut expected: Vec<&[u8]> = text.split(|&b| b == b'\n').collect();
+        // a trailing newline leaves an empty piece that isn't a line
+        if text.last() == Some(&b'\n') {
+            expected.pop();
+        }
+        expected.sort();
+
+        let output = fs::read(dir.path("out.txt")).unwrap();
+        let mut lines: Vec<&[u8]> = output.split(|&b| b == b'\n').collect();
+        lines.pop();
+        assert_eq!(lines, expected);
+    }
+
+    #[test]
+    fn test_sort_records() {
+        let dir = TestDir::new("records");
+        // big endian so byte order is numeric order
+        let values: Vec<u64> = random_values(3000, u64::MAX, 21)
+            .iter()
+            .map(|&x| x as u64)
+            .collect();
+        let bytes: Vec<u8> = values.iter().flat_map(|x| x.to_be_bytes()).collect();
+        fs::write(dir.path("in.bin"), bytes).unwrap();
+
+        let stats = ExternalSort::new()
+            .memory_budget(2048)
+            .max_merge_width(3)
+            .temp_dir(&dir.0)
+            .sort_records(&dir.path("in.bin"), &dir.path("out.bin"), 8)
+            .unwrap();
+
+        let output = fs::read(dir.path("out.bin")).unwrap();
+        let sorted: Vec<u64> = output
+            .chunks(8)
+            .map(|c| u64::from_be_bytes(c.try_into().unwrap()))
+            .collect();
+        let mut expected = values;
+        expected.sort();
+        assert_eq!(sorted, expected);
+        assert_eq!(stats.items, 3000);
+        assert!(stats.runs > 1);
+    }
+
+    #[test]
+    fn test_truncated_record_is_an_error() {
+        let dir = TestDir::new("truncated");
+        fs::write(dir.path("in.bin"), [1, 2, 3, 4, 5]).unwrap();
+
+        let result = ExternalSort::new().temp_dir(&dir.0).sort_records(
+            &dir.path("in.bin"),
+            &dir.path("out.bin"),
+            4,
+        );
+
+        let error = result.unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert!(error.to_string().contains("truncated"));
+    }
+
+    #[test]
+    fn test_missing_input() {
+        let dir = TestDir::new("missing");
+        let result = ExternalSort::new().sort_lines(&dir.path("nope.txt"), &dir.path("out.txt"));
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn test_empty_input() {
+        let dir = TestDir::new("empty");
+        fs::write(dir.path("in.txt"), "").unwrap();
+
+        let stats = ExternalSort::new()
+            .sort_lines(&dir.path("in.txt"), &dir.path("out.txt"))
+            .unwrap();
+        assert_eq!(stats.items, 0);
+        assert_eq!(fs::read_to_string(dir.path("out.txt")).unwrap(), "");
+    }
+}
@@ -0,0 +1,87 @@
+// Heap sort on top of the sift functions from `data_structures::binary_heap`.
+// In place, O(n log n) worst case and O(1) extra memory, but not stable.
+
+use super::instrument::{Probe, SortObserver};
+use crate::data_structures::binary_heap::{HeapKind, heapify, sift_down};
+
+pub fn heap_sort<T: Ord>(input_list: &mut [T]) {
+    heap_sort_d_ary(input_list, 2);
+}
+
+/// Heap sort with a d-ary max heap, fewer levels to sift through for larger `arity`
+pub fn heap_sort_d_ary<T: Ord>(input_list: &mut [T], arity: usize) {
+    heap_sort_probed(input_list, arity, &mut Probe::new(&mut ()));
+}
+
+pub fn heap_sort_observed<T: Ord, O: SortObserver<T>>(input_list: &mut [T], observer: &mut O) {
+    heap_sort_probed(input_list, 2, &mut Probe::new(observer));
+}
+
+pub(crate) fn heap_sort_probed<T: Ord, O: SortObserver<T>>(
+    input_list: &mut [T],
+    arity: usize,
+    probe: &mut Probe<'_, T, O>,
+) {
+    assert!(arity >= 2, "heap arity must be at least 2");
+
+    heapify(input_list, HeapKind::Max, arity, probe);
+
+    // move the current max behind the heap and shrink it by one
+    for end in (1..input_list.len()).rev() {
+        probe.swap(input_list, 0, end);
+        sift_down(input_list, 0, end, HeapKind::Max, arity, probe);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algoritms::sorting::test_utils::{organ_pipe, random_values};
+
+    #[test]
+    fn test_heap_sort_basic() {
+        let mut list = vec![3, 1, 4, 1, 5, 9, 2, 6];
+        heap_sort(&mut list);
+        assert_eq!(list, [1, 1, 2, 3, 4, 5, 6, 9]);
+    }
+
+    #[test]
+    fn test_heap_sort_empty_and_single() {
+        let mut empty: Vec<i32> = vec![];
+        heap_sort(&mut empty);
+        assert!(empty.is_empty());
+
+        let mut single = vec![1];
+        heap_sort(&mut single);
+        assert_eq!(single, [1]);
+    }
+
+    #[test]
+    fn test_heap_sort_strings() {
+        let mut list: Vec<String> = ["dog", "cat", "zebra", "ant", "bear"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        heap_sort(&mut list);
+        assert_eq!(list, ["ant", "bear", "cat", "dog", "zebra"]);
+    }
+
+    #[test]
+    fn test_heap_sort_matches_std() {
+        for values in [
+            random_values(1000, 1_000_000, 1),
+            random_values(1000, 3, 2),
+            organ_pipe(1000),
+            (0..1000).rev().collect(),
+        ] {
+            let mut expected = values.clone();
+            expected.sort();
+
+            for arity in [2, 3, 4, 8] {
+                let mut list = values.clone();
+                heap_sort_d_ary(&mut list, arity);
+                assert_eq!(list, expected, "arity {arity}");
+            }
+        }
+    }
+}
@@ -0,0 +1,68 @@
+use super::instrument::{Probe, SortObserver};
+
+/// Insertion sort, in place and stable. Quadratic, but the fastest option on short or
+/// nearly sorted slices, which is why the hybrid sorts bottom out in it.
+pub fn insertion_sort<T: Ord>(input_list: &mut [T]) {
+    insertion_sort_observed(input_list, &mut ());
+}
+
+pub fn insertion_sort_observed<T: Ord, O: SortObserver<T>>(input_list: &mut [T], observer: &mut O) {
+    insertion_sort_probed(input_list, &mut Probe::new(observer));
+}
+
+pub(crate) fn insertion_sort_probed<T: Ord, O: SortObserver<T>>(
+    input_list: &mut [T],
+    probe: &mut Probe<'_, T, O>,
+) {
+    for i in 1..input_list.len() {
+        let mut j = i;
+        while j > 0 && probe.less(input_list, j, j - 1) {
+            probe.swap(input_list, j, j - 1);
+            j -= 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algoritms::sorting::test_utils::Record;
+
+    #[test]
+    fn test_insertion_sort_basic() {
+        let mut list = vec![3, 1, 4, 1, 5, 9, 2, 6];
+        insertion_sort(&mut list);
+        assert_eq!(list, [1, 1, 2, 3, 4, 5, 6, 9]);
+    }
+
+    #[test]
+    fn test_insertion_sort_empty_and_single() {
+        let mut empty: Vec<i32> = vec![];
+        insertion_sort(&mut empty);
+        assert!(empty.is_empty());
+
+        let mut single = vec![42];
+        insertion_sort(&mut single);
+        assert_eq!(single, [42]);
+    }
+
+    #[test]
+    fn test_insertion_sort_reverse_sorted() {
+        let mut list = vec![5, 4, 3, 2, 1];
+        insertion_sort(&mut list);
+        assert_eq!(list, [1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_insertion_sort_is_stable() {
+        let mut list = vec![
+            Record::new(2, 'a'),
+            Record::new(1, 'b'),
+            Record::new(2, 'c'),
+            Record::new(1, 'd'),
+        ];
+        insertion_sort(&mut list);
+        let payloads: Vec<char> = list.iter().map(|r| r.payload).collect();
+        assert_eq!(payloads, ['b', 'd', 'a', 'c']);
+    }
+}
@@ -0,0 +1,291 @@
+// Instrumentation for the sorters. Every sorter has an `*_observed` variant that reports
+// its comparisons, swaps, writes, buffer allocations and partition / merge boundaries to
+// a `SortObserver`; the plain functions pass `()`, whose no-op hooks compile away.
+// The observer and `Probe` live in `crate::instrument` since the heap reports through
+// them too, they are re-exported here for the sorters.
+//
+// `SortReport` is the counting observer, `sort_instrumented` runs any of the comparison
+// sorters on a copy of the input and returns the sorted output with the report. The
+// counting, radix, bucket and string sorts only have their `*_observed` functions since
+// they don't work on any `Ord` type. The parallel and external sorts are not instrumented.
+
+use std::fmt::{self, Display};
+
+pub use crate::instrument::{Probe, SortObserver};
+
+use super::bubble_sort::bubble_sort_observed;
+use super::heap_sort::heap_sort_observed;
+use super::insertion_sort::insertion_sort_observed;
+use super::intro_sort::intro_sort_observed;
+use super::merge_sort::{merge_sort_bottom_up_observed, merge_sort_observed};
+use super::pdq_sort::pdq_sort_observed;
+use super::quick_sort::{
+    PartitionScheme, PivotStrategy, quick_sort_in_place_observed, quick_sort_observed,
+};
+use super::selection_sort::selection_sort_observed;
+use super::tim_sort::tim_sort_observed;
+
+/// Operation counts for one sort run
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SortReport {
+    pub comparisons: usize,
+    pub swaps: usize,
+    /// single element writes, e.g. merging back from a buffer
+    pub writes: usize,
+    pub allocations: usize,
+    pub allocated_elements: usize,
+}
+
+impl<T> SortObserver<T> for SortReport {
+    fn on_compare(&mut self, _i: usize, _j: usize) {
+        self.comparisons += 1;
+    }
+
+    fn on_swap(&mut self, _i: usize, _j: usize) {
+        self.swaps += 1;
+    }
+
+    fn on_write(&mut self, _index: usize, _value: &T) {
+        self.writes += 1;
+    }
+
+    fn on_alloc(&mut self, elements: usize) {
+        self.allocations += 1;
+        self.allocated_elements += elements;
+    }
+}
+
+impl Display for SortReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} comparisons, {} swaps, {} writes, {} allocations ({} elements)",
+            self.comparisons, self.swaps, self.writes, self.allocations, self.allocated_elements
+        )
+    }
+}
+
+/// The comparison sorters that can be run through [`sort_instrumented`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sorter {
+    Bubble,
+    Selection,
+    Insertion,
+    Quick,
+    QuickInPlace(PivotStrategy, PartitionScheme),
+    Intro,
+    Pdq,
+    Heap,
+    Merge,
+    MergeBottomUp,
+    Tim,
+}
+
+impl Sorter {
+    /// One of each, the in-place quick sort with median-of-three and Hoare partitioning
+    pub const ALL: [Sorter; 11] = [
+        Sorter::Bubble,
+        Sorter::Selection,
+        Sorter::Insertion,
+        Sorter::Quick,
+        Sorter::QuickInPlace(PivotStrategy::MedianOfThree, PartitionScheme::Hoare),
+        Sorter::Intro,
+        Sorter::Pdq,
+        Sorter::Heap,
+        Sorter::Merge,
+        Sorter::MergeBottomUp,
+        Sorter::Tim,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Sorter::Bubble => "bubble sort",
+            Sorter::Selection => "selection sort",
+            Sorter::Insertion => "insertion sort",
+            Sorter::Quick => "quick sort",
+            Sorter::QuickInPlace(..) => "quick sort (in place)",
+            Sorter::Intro => "intro sort",
+            Sorter::Pdq => "pdq sort",
+            Sorter::Heap => "heap sort",
+            Sorter::Merge => "merge sort",
+            Sorter::MergeBottomUp => "merge sort (bottom up)",
+            Sorter::Tim => "tim sort",
+        }
+    }
+
+    /// Sorts a copy of `input_list`, reporting every operation to `observer`.
+    /// The initial copy made for the in-place sorters is not reported.
+    pub fn sort_observed<T, O>(&self, input_list: &[T], observer: &mut O) -> Vec<T>
+    where
+        T: Clone + Ord,
+        O: SortObserver<T>,
+    {
+        // the in-place sorters work on a copy, the others build their output themselves
+        fn sorted_copy<T: Clone>(input_list: &[T], sort: impl FnOnce(&mut [T])) -> Vec<T> {
+            let mut list = input_list.to_vec();
+            sort(&mut list);
+            list
+        }
+
+        match *self {
+            Sorter::Bubble => bubble_sort_observed(input_list, observer),
+            Sorter::Selection => selection_sort_observed(input_list, observer),
+            Sorter::Quick => quick_sort_observed(input_list, observer),
+            Sorter::Insertion => {
+                sorted_copy(input_list, |list| insertion_sort_observed(list, observer))
+            }
+            Sorter::QuickInPlace(pivot, scheme) => sorted_copy(input_list, |list| {
+                quick_sort_in_place_observed(list, pivot, scheme, observer)
+            }),
+            Sorter::Intro => sorted_copy(input_list, |list| intro_sort_observed(list, observer)),
+            Sorter::Pdq => sorted_copy(input_list, |list| pdq_sort_observed(list, observer)),
+            Sorter::Heap => sorted_copy(input_list, |list| heap_sort_observed(list, observer)),
+            Sorter::Merge => sorted_copy(input_list, |list| merge_sort_observed(list, observer)),
+            Sorter::MergeBottomUp => sorted_copy(input_list, |list| {
+                merge_sort_bottom_up_observed(list, observer)
+            }),
+            Sorter::Tim => sorted_copy(input_list, |list| tim_sort_observed(list, observer)),
+        }
+    }
+}
+
+/// Sorts a copy of `input_list` with `sorter` and counts what it did
+pub fn sort_instrumented<T: Clone + Ord>(sorter: Sorter, input_list: &[T]) -> (Vec<T>, SortReport) {
+    let mut report = SortReport::default();
+    let sorted = sorter.sort_observed(input_list, &mut report);
+    (sorted, report)
+}
+
+/// Runs every sorter in `sorters` on the same input, for side by side comparisons
+pub fn compare_sorters<T: Clone + Ord>(
+    sorters: &[Sorter],
+    input_list: &[T],
+) -> Vec<(Sorter, SortReport)> {
+    sorters
+        .iter()
+        .map(|&sorter| (sorter, sort_instrumented(sorter, input_list).1))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algoritms::sorting::counting_sort::counting_sort_observed;
+    use crate::algoritms::sorting::radix_sort::radix_sort_observed;
+    use crate::algoritms::sorting::string_sort::multikey_quick_sort_observed;
+    use crate::algoritms::sorting::test_utils::random_values;
+
+    /// Records every index it is told about, to check they stay inside the input
+    #[derive(Default)]
+    struct MaxIndex(usize);
+
+    impl<T> SortObserver<T> for MaxIndex {
+        fn on_compare(&mut self, i: usize, j: usize) {
+            self.0 = self.0.max(i).max(j);
+        }
+
+        fn on_swap(&mut self, i: usize, j: usize) {
+            self.0 = self.0.max(i).max(j);
+        }
+
+        fn on_write(&mut self, index: usize, _value: &T) {
+            self.0 = self.0.max(index);
+        }
+    }
+
+    #[test]
+    fn test_every_sorter_sorts_and_reports() {
+        let values = random_values(500, 100, 3);
+        let mut expected = values.clone();
+        expected.sort();
+
+        for sorter in Sorter::ALL {
+            let (sorted, report) = sort_instrumented(sorter, &values);
+            assert_eq!(sorted, expected, "{}", sorter.name());
+            assert!(report.comparisons > 0, "{} reported nothing", sorter.name());
+        }
+    }
+
+    #[test]
+    fn test_reported_indices_stay_in_bounds() {
+        let values = random_values(3000, 50, 8);
+        for sorter in Sorter::ALL {
+            let mut observer = MaxIndex::default();
+            sorter.sort_observed(&values, &mut observer);
+            assert!(observer.0 < values.len(), "{}", sorter.name());
+        }
+    }
+
+    #[test]
+    fn test_bubble_sort_counts() {
+        // reversed input: every pair is compared and swapped exactly once
+        let values: Vec<i32> = (0..20).rev().collect();
+        let (_, report) = sort_instrumented(Sorter::Bubble, &values);
+        assert_eq!(report.comparisons, 20 * 19 / 2);
+        assert_eq!(report.swaps, 20 * 19 / 2);
+        // the output vector
+        assert_eq!(report.allocated_elements, 20);
+    }
+
+    #[test]
+    fn test_merge_sorts_allocate_and_heap_sort_does_not() {
+        let values = random_values(1000, 1000, 5);
+        let (_, heap) = sort_instrumented(Sorter::Heap, &values);
+        assert_eq!(heap.allocations, 0);
+        assert_eq!(heap.writes, 0);
+
+        for sorter in [Sorter::Merge, Sorter::MergeBottomUp, Sorter::Tim] {
+            let (_, report) = sort_instrumented(sorter, &values);
+            assert!(report.allocated_elements > 0, "{}", sorter.name());
+            assert!(report.writes > 0, "{}", sorter.name());
+        }
+    }
+
+    #[test]
+    fn test_compare_sorters() {
+        let values: Vec<i32> = (0..1000).collect();
+        let reports = compare_sorters(&[Sorter::Insertion, Sorter::Selection], &values);
+        assert_eq!(reports.len(), 2);
+        // sorted input: insertion sort does one pass, selection sort still does n^2 / 2
+        assert_eq!(reports[0].1.comparisons, 999);
+        assert_eq!(reports[1].1.comparisons, 1000 * 999 / 2);
+    }
+
+    #[test]
+    fn test_non_comparison_sorts() {
+        let mut list = vec![5, 3, 9, 3, 1];
+        let mut report = SortReport::default();
+        counting_sort_observed(&mut list, &mut report);
+        assert_eq!(list, [1, 3, 3, 5, 9]);
+        assert_eq!(report.comparisons, 0);
+        assert_eq!(report.writes, 5);
+
+        let mut list = vec![300u32, 2, 70_000, 1];
+        let mut report = SortReport::default();
+        radix_sort_observed(&mut list, &mut report);
+        assert_eq!(list, [1, 2, 300, 70_000]);
+        assert_eq!(report.comparisons, 0);
+        assert_eq!(report.allocations, 1);
+
+        let mut list = vec!["pear", "apple", "fig"];
+        let mut report = SortReport::default();
+        multikey_quick_sort_observed(&mut list, &mut report);
+        assert_eq!(list, ["apple", "fig", "pear"]);
+        assert!(report.comparisons > 0);
+    }
+
+    #[test]
+    fn test_report_display() {
+        let report = SortReport {
+            comparisons: 10,
+            swaps: 4,
+            writes: 2,
+            allocations: 1,
+            allocated_elements: 8,
+        };
+        assert_eq!(
+            report.to_string(),
+            "10 comparisons, 4 swaps, 2 writes, 1 allocations (8 elements)"
+        );
+    }
+}
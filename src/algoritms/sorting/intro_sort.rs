@@ -0,0 +1,172 @@
+// Introsort: quick sort that watches its own recursion depth and switches to heap sort
+// once the pivots have been bad for too long, so the worst case stays O(n log n).
+
+use super::heap_sort::heap_sort_probed;
+use super::insertion_sort::insertion_sort_probed;
+use super::instrument::{Probe, SortObserver};
+use super::quick_sort::{median_of_three, partition_hoare};
+
+/// Below this length insertion sort beats partitioning
+pub(crate) const INSERTION_THRESHOLD: usize = 16;
+
+pub fn intro_sort<T: Ord>(input_list: &mut [T]) {
+    intro_sort_observed(input_list, &mut ());
+}
+
+pub fn intro_sort_observed<T: Ord, O: SortObserver<T>>(input_list: &mut [T], observer: &mut O) {
+    let depth_limit = 2 * log2(input_list.len());
+    intro_sort_helper(input_list, depth_limit, &mut Probe::new(observer));
+}
+
+fn intro_sort_helper<T: Ord, O: SortObserver<T>>(
+    mut input_list: &mut [T],
+    mut depth_limit: u32,
+    probe: &mut Probe<'_, T, O>,
+) {
+    let mut offset = 0;
+
+    loop {
+        let mut probe = probe.at(offset);
+        let len = input_list.len();
+        if len <= INSERTION_THRESHOLD {
+            insertion_sort_probed(input_list, &mut probe);
+            return;
+        }
+        if depth_limit == 0 {
+            heap_sort_probed(input_list, 2, &mut probe);
+            return;
+        }
+        depth_limit -= 1;
+
+        let pivot_index = median_of_three(input_list, 0, len / 2, len - 1, &mut probe);
+        let mid = partition_hoare(input_list, pivot_index, &mut probe);
+        probe.partitioned(mid, len);
+
+        let (left, right) = std::mem::take(&mut input_list).split_at_mut(mid);
+        let right = &mut right[1..];
+
+        // recurse on the smaller side, keep looping on the larger one
+        if left.len() < right.len() {
+            intro_sort_helper(left, depth_limit, &mut probe);
+            input_list = right;
+            offset += mid + 1;
+        } else {
+            intro_sort_helper(right, depth_limit, &mut probe.at(mid + 1));
+            input_list = left;
+        }
+    }
+}
+
+/// floor(log2(n)), 0 for n < 2
+pub(crate) fn log2(n: usize) -> u32 {
+    if n < 2 {
+        0
+    } else {
+        usize::BITS - 1 - n.leading_zeros()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algoritms::sorting::quick_sort::{
+        PartitionScheme, PivotStrategy, quick_sort_in_place,
+    };
+    use crate::algoritms::sorting::test_utils::{
+        antiqsort, count_comparisons, median_of_three_killer, n_log_n, organ_pipe, random_values,
+        sawtooth,
+    };
+
+    fn check_sorts(values: &[i64]) {
+        let mut expected = values.to_vec();
+        expected.sort();
+        let mut list = values.to_vec();
+        intro_sort(&mut list);
+        assert_eq!(list, expected);
+    }
+
+    #[test]
+    fn test_intro_sort_basic() {
+        let mut list = vec![9, 7, 5, 11, 12, 2, 14, 3, 10, 6];
+        intro_sort(&mut list);
+        assert_eq!(list, [2, 3, 5, 6, 7, 9, 10, 11, 12, 14]);
+    }
+
+    #[test]
+    fn test_intro_sort_empty_and_single() {
+        let mut empty: Vec<i32> = vec![];
+        intro_sort(&mut empty);
+        assert!(empty.is_empty());
+
+        let mut single = vec![1];
+        intro_sort(&mut single);
+        assert_eq!(single, [1]);
+    }
+
+    #[test]
+    fn test_intro_sort_patterns() {
+        check_sorts(&random_values(5000, 1_000_000, 7));
+        check_sorts(&random_values(5000, 10, 8));
+        check_sorts(&organ_pipe(5000));
+        check_sorts(&sawtooth(5000, 37));
+        check_sorts(&median_of_three_killer(5000));
+        check_sorts(&(0..5000).collect::<Vec<_>>());
+        check_sorts(&(0..5000).rev().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_intro_sort_strings() {
+        let mut list = vec!["dog", "cat", "zebra", "ant", "bear"];
+        intro_sort(&mut list);
+        assert_eq!(list, ["ant", "bear", "cat", "dog", "zebra"]);
+    }
+
+    #[test]
+    fn test_log2() {
+        assert_eq!(log2(0), 0);
+        assert_eq!(log2(1), 0);
+        assert_eq!(log2(2), 1);
+        assert_eq!(log2(1023), 9);
+        assert_eq!(log2(1024), 10);
+    }
+
+    #[test]
+    fn test_median_of_three_killer_hurts_quick_sort_but_not_intro_sort() {
+        let len = 4000;
+        let killer = median_of_three_killer(len);
+
+        let quick = count_comparisons(&killer, |list| {
+            quick_sort_in_place(list, PivotStrategy::MedianOfThree, PartitionScheme::Hoare)
+        });
+        let intro = count_comparisons(&killer, intro_sort);
+
+        assert!(
+            quick > len * len / 16,
+            "killer should be quadratic, got {quick}"
+        );
+        assert!(
+            intro < 4 * n_log_n(len),
+            "intro sort made {intro} comparisons"
+        );
+    }
+
+    #[test]
+    fn test_adversary_against_intro_sort() {
+        let len = 4000;
+
+        // the adversary makes plain quick sort quadratic ...
+        let (quick, killer) = antiqsort(len, |list| {
+            quick_sort_in_place(list, PivotStrategy::MedianOfThree, PartitionScheme::Hoare)
+        });
+        assert!(quick > len * len / 16, "adversary should win, got {quick}");
+        check_sorts(&killer);
+
+        // ... but intro sort bails out to heap sort in time
+        let (intro, killer) = antiqsort(len, intro_sort);
+        assert!(
+            intro < 4 * n_log_n(len),
+            "intro sort made {intro} comparisons"
+        );
+        check_sorts(&killer);
+    }
+}
@@ -0,0 +1,203 @@
+// Merge sort, stable and O(n log n) in every case at the cost of an O(n) buffer.
+
+use super::instrument::{Probe, SortObserver};
+
+/// Top-down (recursive) merge sort
+pub fn merge_sort<T: Clone + Ord>(input_list: &mut [T]) {
+    merge_sort_observed(input_list, &mut ());
+}
+
+pub fn merge_sort_observed<T: Clone + Ord, O: SortObserver<T>>(
+    input_list: &mut [T],
+    observer: &mut O,
+) {
+    let mut probe = Probe::new(observer);
+    let mut buffer = Vec::with_capacity(input_list.len() / 2 + 1);
+    probe.alloc(buffer.capacity());
+    merge_sort_helper(input_list, &mut buffer, &mut probe);
+}
+
+fn merge_sort_helper<T: Clone + Ord, O: SortObserver<T>>(
+    input_list: &mut [T],
+    buffer: &mut Vec<T>,
+    probe: &mut Probe<'_, T, O>,
+) {
+    let len = input_list.len();
+    if len < 2 {
+        return;
+    }
+
+    let mid = len / 2;
+    merge_sort_helper(&mut input_list[..mid], buffer, probe);
+    merge_sort_helper(&mut input_list[mid..], buffer, &mut probe.at(mid));
+    merge(input_list, mid, buffer, probe);
+}
+
+/// Bottom-up (iterative) merge sort, merges runs of width 1, 2, 4, .. without recursion
+pub fn merge_sort_bottom_up<T: Clone + Ord>(input_list: &mut [T]) {
+    merge_sort_bottom_up_observed(input_list, &mut ());
+}
+
+pub fn merge_sort_bottom_up_observed<T: Clone + Ord, O: SortObserver<T>>(
+    input_list: &mut [T],
+    observer: &mut O,
+) {
+    let mut probe = Probe::new(observer);
+    let len = input_list.len();
+    let mut buffer = Vec::with_capacity(len / 2 + 1);
+    probe.alloc(buffer.capacity());
+    let mut width = 1;
+
+    while width < len {
+        let mut start = 0;
+        while start + width < len {
+            let end = (start + 2 * width).min(len);
+            merge(
+                &mut input_list[start..end],
+                width,
+                &mut buffer,
+                &mut probe.at(start),
+            );
+            start = end;
+        }
+        width *= 2;
+    }
+}
+
+/// Merges the sorted runs `input_list[..mid]` and `input_list[mid..]` in place.
+///
+/// Only the left run is copied out, the right run is read in place since the write
+/// cursor can never overtake it. Ties go to the left run, which keeps the sort stable.
+pub(crate) fn merge<T: Clone + Ord, O: SortObserver<T>>(
+    input_list: &mut [T],
+    mid: usize,
+    buffer: &mut Vec<T>,
+    probe: &mut Probe<'_, T, O>,
+) {
+    probe.merging(mid, input_list.len());
+
+    // runs already in order, nothing to do
+    if mid == 0 || mid == input_list.len() || !probe.less(input_list, mid, mid - 1) {
+        return;
+    }
+
+    buffer.clear();
+    buffer.extend_from_slice(&input_list[..mid]);
+
+    let len = input_list.len();
+    let (mut i, mut j, mut k) = (0, mid, 0);
+
+    // buffer[i] is reported as position i, where it came from
+    while i < buffer.len() && j < len {
+        if probe.less_values(&input_list[j], j, &buffer[i], i) {
+            input_list[k] = input_list[j].clone();
+            probe.wrote(k, &input_list[k]);
+            j += 1;
+        } else {
+            probe.write(input_list, k, &buffer[i]);
+            i += 1;
+        }
+        k += 1;
+    }
+
+    // leftovers from the right run are already in place
+    for value in &buffer[i..] {
+        probe.write(input_list, k, value);
+        k += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algoritms::sorting::test_utils::{Record, random_values};
+
+    type RecordSort = fn(&mut [Record<usize>]);
+
+    fn sorters() -> Vec<(&'static str, RecordSort)> {
+        vec![
+            ("top down", merge_sort),
+            ("bottom up", merge_sort_bottom_up),
+        ]
+    }
+
+    #[test]
+    fn test_merge_sort_basic() {
+        let mut list = vec![3, 1, 4, 1, 5, 9, 2, 6];
+        merge_sort(&mut list);
+        assert_eq!(list, [1, 1, 2, 3, 4, 5, 6, 9]);
+
+        let mut list = vec![3, 1, 4, 1, 5, 9, 2, 6];
+        merge_sort_bottom_up(&mut list);
+        assert_eq!(list, [1, 1, 2, 3, 4, 5, 6, 9]);
+    }
+
+    #[test]
+    fn test_merge_sort_empty_and_single() {
+        let mut empty: Vec<i32> = vec![];
+        merge_sort(&mut empty);
+        merge_sort_bottom_up(&mut empty);
+        assert!(empty.is_empty());
+
+        let mut single = vec![7];
+        merge_sort(&mut single);
+        merge_sort_bottom_up(&mut single);
+        assert_eq!(single, [7]);
+    }
+
+    #[test]
+    fn test_merge_sort_strings() {
+        let mut list: Vec<String> = ["dog", "cat", "zebra", "ant", "bear"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        merge_sort(&mut list);
+        assert_eq!(list, ["ant", "bear", "cat", "dog", "zebra"]);
+    }
+
+    #[test]
+    fn test_merge_sort_random_matches_std() {
+        for len in [2, 3, 17, 100, 1000, 1025] {
+            let values = random_values(len, 1000, len as u64);
+            let mut expected = values.clone();
+            expected.sort();
+
+            let mut top_down = values.clone();
+            merge_sort(&mut top_down);
+            assert_eq!(top_down, expected);
+
+            let mut bottom_up = values.clone();
+            merge_sort_bottom_up(&mut bottom_up);
+            assert_eq!(bottom_up, expected);
+        }
+    }
+
+    #[test]
+    fn test_merge_sort_is_stable() {
+        // few distinct keys so there are plenty of ties, payload is the original position
+        let keys = random_values(2000, 10, 99);
+        let records: Vec<Record<usize>> = keys
+            .iter()
+            .enumerate()
+            .map(|(i, &k)| Record::new(k as i32, i))
+            .collect();
+
+        for (name, sort) in sorters() {
+            let mut list = records.clone();
+            sort(&mut list);
+            assert!(
+                list.windows(2).all(|w| w[0].key < w[1].key
+                    || (w[0].key == w[1].key && w[0].payload < w[1].payload)),
+                "{name} merge sort is not stable"
+            );
+        }
+    }
+
+    #[test]
+    fn test_merge_helper() {
+        let mut list = vec![1, 4, 7, 2, 3, 8];
+        let mut buffer = Vec::new();
+        merge(&mut list, 3, &mut buffer, &mut Probe::new(&mut ()));
+        assert_eq!(list, [1, 2, 3, 4, 7, 8]);
+    }
+}
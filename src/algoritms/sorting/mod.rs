@@ -1,3 +1,22 @@
 pub mod bubble_sort;
+pub mod bucket_sort;
+pub mod counting_sort;
+pub mod external_sort;
+pub mod heap_sort;
+pub mod insertion_sort;
+pub mod instrument;
+pub mod intro_sort;
+pub mod merge_sort;
+pub mod parallel_sort;
+pub mod pdq_sort;
 pub mod quick_sort;
+pub mod radix_sort;
+pub mod select;
 pub mod selection_sort;
+pub mod sorting_network;
+pub mod string_sort;
+pub mod tim_sort;
+pub mod trace;
+
+#[cfg(test)]
+pub(crate) mod test_utils;
@@ -0,0 +1,179 @@
+// Parallel merge sort and quick sort on `std::thread::scope`. Work is split in two at
+// every level until there is one piece per core, then each piece is sorted with the
+// sequential version. Slices below `PARALLEL_THRESHOLD` never spawn a thread.
+
+use std::thread;
+
+use super::instrument::Probe;
+use super::intro_sort::{intro_sort, log2};
+use super::merge_sort::{merge, merge_sort};
+use super::quick_sort::{PivotStrategy, XorShift64, choose_pivot, partition_hoare};
+
+/// Below this length spawning a thread costs more than it saves
+pub const PARALLEL_THRESHOLD: usize = 1 << 14;
+
+/// Stable, gives exactly the same result as [`merge_sort`]
+pub fn parallel_merge_sort<T: Clone + Ord + Send>(input_list: &mut [T]) {
+    parallel_merge_sort_helper(input_list, split_depth());
+}
+
+fn parallel_merge_sort_helper<T: Clone + Ord + Send>(input_list: &mut [T], depth: u32) {
+    if depth == 0 || input_list.len() < PARALLEL_THRESHOLD {
+        merge_sort(input_list);
+        return;
+    }
+
+    let mid = input_list.len() / 2;
+    let (left, right) = input_list.split_at_mut(mid);
+    thread::scope(|scope| {
+        scope.spawn(|| parallel_merge_sort_helper(left, depth - 1));
+        parallel_merge_sort_helper(right, depth - 1);
+    });
+
+    // the final merges run on one thread, which is what caps the speedup
+    let mut buffer = Vec::with_capacity(mid);
+    merge(input_list, mid, &mut buffer, &mut Probe::new(&mut ()));
+}
+
+/// Partitions on the current thread and sorts both sides in parallel, leaves are sorted
+/// with [`intro_sort`] so the worst case stays O(n log n)
+pub fn parallel_quick_sort<T: Ord + Send>(input_list: &mut [T]) {
+    let mut rng = XorShift64::from_entropy();
+    parallel_quick_sort_helper(input_list, split_depth(), &mut rng);
+}
+
+fn parallel_quick_sort_helper<T: Ord + Send>(
+    input_list: &mut [T],
+    depth: u32,
+    rng: &mut XorShift64,
+) {
+    if depth == 0 || input_list.len() < PARALLEL_THRESHOLD {
+        intro_sort(input_list);
+        return;
+    }
+
+    let mut silent = ();
+    let mut probe = Probe::new(&mut silent);
+    let pivot_index = choose_pivot(input_list, PivotStrategy::Ninther, rng, &mut probe);
+    let mid = partition_hoare(input_list, pivot_index, &mut probe);
+    let (left, right) = input_list.split_at_mut(mid);
+    let right = &mut right[1..];
+
+    let mut left_rng = XorShift64::new(rng.next_u64());
+    thread::scope(|scope| {
+        scope.spawn(|| parallel_quick_sort_helper(left, depth - 1, &mut left_rng));
+        parallel_quick_sort_helper(right, depth - 1, rng);
+    });
+}
+
+/// How many times to split so every core gets roughly one piece (plus one level of slack
+/// for uneven quick sort partitions)
+fn split_depth() -> u32 {
+    let threads = thread::available_parallelism().map_or(1, |n| n.get());
+    log2(threads.next_power_of_two()) + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algoritms::sorting::test_utils::{Record, random_values};
+    use std::time::Instant;
+
+    #[test]
+    fn test_parallel_sorts_small() {
+        let mut list = vec![3, 1, 4, 1, 5, 9, 2, 6];
+        parallel_merge_sort(&mut list);
+        assert_eq!(list, [1, 1, 2, 3, 4, 5, 6, 9]);
+
+        let mut list = vec![3, 1, 4, 1, 5, 9, 2, 6];
+        parallel_quick_sort(&mut list);
+        assert_eq!(list, [1, 1, 2, 3, 4, 5, 6, 9]);
+
+        let mut empty: Vec<i32> = vec![];
+        parallel_merge_sort(&mut empty);
+        parallel_quick_sort(&mut empty);
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn test_parallel_sorts_match_sequential() {
+        for max in [10, 1_000_000_000] {
+            let values = random_values(200_000, max, max);
+
+            let mut expected = values.clone();
+            merge_sort(&mut expected);
+
+            let mut merged = values.clone();
+            parallel_merge_sort(&mut merged);
+            assert_eq!(merged, expected);
+
+            let mut quick = values.clone();
+            parallel_quick_sort(&mut quick);
+            assert_eq!(quick, expected);
+        }
+    }
+
+    #[test]
+    fn test_parallel_merge_sort_is_stable() {
+        let records: Vec<Record<usize>> = random_values(100_000, 50, 3)
+            .iter()
+            .enumerate()
+            .map(|(i, &k)| Record::new(k as i32, i))
+            .collect();
+
+        let mut expected = records.clone();
+        merge_sort(&mut expected);
+        let mut list = records;
+        parallel_merge_sort(&mut list);
+
+        let payloads = |list: &[Record<usize>]| list.iter().map(|r| r.payload).collect::<Vec<_>>();
+        assert_eq!(payloads(&list), payloads(&expected));
+    }
+
+    #[test]
+    fn test_parallel_sorts_strings() {
+        let values: Vec<String> = random_values(50_000, 100_000, 8)
+            .iter()
+            .map(|x| format!("item-{x}"))
+            .collect();
+        let mut expected = values.clone();
+        expected.sort();
+
+        let mut merged = values.clone();
+        parallel_merge_sort(&mut merged);
+        assert_eq!(merged, expected);
+
+        let mut quick = values;
+        parallel_quick_sort(&mut quick);
+        assert_eq!(quick, expected);
+    }
+
+    // cargo test --release bench_parallel_sorts -- --ignored --nocapture
+    #[test]
+    #[ignore]
+    fn bench_parallel_sorts() {
+        let values = random_values(20_000_000, u64::MAX, 12345);
+
+        let time = |name: &str, sort: fn(&mut [i64])| {
+            let mut list = values.clone();
+            let start = Instant::now();
+            sort(&mut list);
+            let elapsed = start.elapsed();
+            println!("{name:<22} {elapsed:?}");
+            assert!(list.windows(2).all(|w| w[0] <= w[1]));
+            elapsed
+        };
+
+        let merge = time("merge_sort", merge_sort);
+        let parallel_merge = time("parallel_merge_sort", parallel_merge_sort);
+        let intro = time("intro_sort", intro_sort);
+        let parallel_quick = time("parallel_quick_sort", parallel_quick_sort);
+
+        println!(
+            "speedup: merge {:.2}x, quick {:.2}x on {} threads",
+            merge.as_secs_f64() / parallel_merge.as_secs_f64(),
+            intro.as_secs_f64() / parallel_quick.as_secs_f64(),
+            thread::available_parallelism().map_or(1, |n| n.get())
+        );
+    }
+}
@@ -0,0 +1,334 @@
+// Pattern-defeating quicksort (Orson Peters' pdqsort). Introsort with a few extra tricks
+// so common real-world patterns run in linear time instead of just "not quadratic":
+// - sorted or reversed input is detected up front
+// - a partition that moved nothing suggests a sorted run, so try a bounded insertion sort
+// - runs of duplicates of an earlier pivot are split off in one pass
+// - after an unbalanced partition a few elements get shuffled to break up the pattern
+
+use super::heap_sort::heap_sort_probed;
+use super::insertion_sort::insertion_sort_probed;
+use super::instrument::{Probe, SortObserver};
+use super::intro_sort::{INSERTION_THRESHOLD, log2};
+use super::quick_sort::{PivotStrategy, XorShift64, choose_pivot};
+
+pub fn pdq_sort<T: Ord>(input_list: &mut [T]) {
+    pdq_sort_observed(input_list, &mut ());
+}
+
+pub fn pdq_sort_observed<T: Ord, O: SortObserver<T>>(input_list: &mut [T], observer: &mut O) {
+    let len = input_list.len();
+    if len < 2 {
+        return;
+    }
+    let mut probe = Probe::new(observer);
+
+    // whole input is one ascending or descending run
+    if (1..len).all(|i| !probe.less(input_list, i, i - 1)) {
+        return;
+    }
+    if (1..len).all(|i| !probe.less(input_list, i - 1, i)) {
+        for i in 0..len / 2 {
+            probe.swap(input_list, i, len - 1 - i);
+        }
+        return;
+    }
+
+    let mut rng = XorShift64::new(len as u64);
+    pdq_sort_helper(input_list, None, log2(len) + 1, &mut rng, &mut probe);
+}
+
+/// `pred` is the pivot of the partition this slice came from, if it sits directly to the
+/// left of the slice. Every element here is `>=` it.
+fn pdq_sort_helper<'a, T: Ord, O: SortObserver<T>>(
+    mut input_list: &'a mut [T],
+    mut pred: Option<&'a T>,
+    mut bad_pivots_left: u32,
+    rng: &mut XorShift64,
+    probe: &mut Probe<'_, T, O>,
+) {
+    let mut was_balanced = true;
+    let mut was_partitioned = true;
+    let mut offset = 0;
+
+    loop {
+        let mut probe = probe.at(offset);
+        let len = input_list.len();
+        if len <= INSERTION_THRESHOLD {
+            insertion_sort_probed(input_list, &mut probe);
+            return;
+        }
+        if bad_pivots_left == 0 {
+            heap_sort_probed(input_list, 2, &mut probe);
+            return;
+        }
+
+        if !was_balanced {
+            break_patterns(input_list, rng, &mut probe);
+            bad_pivots_left -= 1;
+        }
+
+        // last partition was balanced and didn't swap anything, likely a sorted run
+        if was_balanced && was_partitioned && partial_insertion_sort(input_list, &mut probe) {
+            return;
+        }
+
+        let pivot_index = choose_pivot(input_list, PivotStrategy::Ninther, rng, &mut probe);
+
+        // pivot equal to the predecessor means it is the smallest value here, all copies
+        // of it are already in their final place once moved to the front
+        if let Some(pred) = pred
+            && !probe.previous_less(pred, input_list, pivot_index)
+        {
+            let mid = partition_equal(input_list, pivot_index, &mut probe);
+            input_list = &mut std::mem::take(&mut input_list)[mid..];
+            offset += mid;
+            continue;
+        }
+
+        let (mid, already_partitioned) = partition(input_list, pivot_index, &mut probe);
+        probe.partitioned(mid, len);
+
+        let (left, right) = std::mem::take(&mut input_list).split_at_mut(mid);
+        let (pivot, right) = right.split_at_mut(1);
+        let pivot: &'a T = &pivot[0];
+
+        was_balanced = left.len().min(right.len()) >= len / 8;
+        was_partitioned = already_partitioned;
+
+        if left.len() < right.len() {
+            pdq_sort_helper(left, pred, bad_pivots_left, rng, &mut probe);
+            input_list = right;
+            pred = Some(pivot);
+            offset += mid + 1;
+        } else {
+            let mut right_probe = probe.at(mid + 1);
+            pdq_sort_helper(right, Some(pivot), bad_pivots_left, rng, &mut right_probe);
+            input_list = left;
+        }
+    }
+}
+
+/// Partitions into `< pivot` and `>= pivot`, returns the pivot's final index and whether
+/// the slice was already partitioned (no swaps needed).
+fn partition<T: Ord, O: SortObserver<T>>(
+    input_list: &mut [T],
+    pivot_index: usize,
+    probe: &mut Probe<'_, T, O>,
+) -> (usize, bool) {
+    if pivot_index != 0 {
+        probe.swap(input_list, 0, pivot_index);
+    }
+
+    // pivot stays at 0 while the cursors scan input_list[1..]
+    let mut left = 1;
+    let mut right = input_list.len();
+
+    while left < right && probe.less(input_list, left, 0) {
+        left += 1;
+    }
+    while left < right && !probe.less(input_list, right - 1, 0) {
+        right -= 1;
+    }
+    let already_partitioned = left >= right;
+
+    while left < right {
+        right -= 1;
+        probe.swap(input_list, left, right);
+        left += 1;
+
+        while left < right && probe.less(input_list, left, 0) {
+            left += 1;
+        }
+        while left < right && !probe.less(input_list, right - 1, 0) {
+            right -= 1;
+        }
+    }
+
+    let mid = left - 1;
+    if mid != 0 {
+        probe.swap(input_list, 0, mid);
+    }
+    (mid, already_partitioned)
+}
+
+/// Moves every element equal to the pivot to the front, returns the index of the first
+/// element greater than it. Only valid when the pivot is the minimum of the slice.
+fn partition_equal<T: Ord, O: SortObserver<T>>(
+    input_list: &mut [T],
+    pivot_index: usize,
+    probe: &mut Probe<'_, T, O>,
+) -> usize {
+    if pivot_index != 0 {
+        probe.swap(input_list, 0, pivot_index);
+    }
+
+    let mut store = 1;
+    for i in 1..input_list.len() {
+        if !probe.less(input_list, 0, i) {
+            if i != store {
+                probe.swap(input_list, i, store);
+            }
+            store += 1;
+        }
+    }
+    store
+}
+
+/// Insertion sort that gives up after a few out-of-order elements. Returns true if the
+/// slice is sorted afterwards.
+fn partial_insertion_sort<T: Ord, O: SortObserver<T>>(
+    input_list: &mut [T],
+    probe: &mut Probe<'_, T, O>,
+) -> bool {
+    const MAX_STEPS: usize = 5;
+    // on short slices it's cheaper to just partition than to shift things around
+    const SHORTEST_SHIFTING: usize = 50;
+
+    let len = input_list.len();
+    let mut i = 1;
+
+    for _ in 0..MAX_STEPS {
+        while i < len && !probe.less(input_list, i, i - 1) {
+            i += 1;
+        }
+        if i == len {
+            return true;
+        }
+        if len < SHORTEST_SHIFTING {
+            return false;
+        }
+
+        let mut j = i;
+        while j > 0 && probe.less(input_list, j, j - 1) {
+            probe.swap(input_list, j, j - 1);
+            j -= 1;
+        }
+    }
+    false
+}
+
+/// Swaps a few elements around the quartiles with random positions
+fn break_patterns<T, O: SortObserver<T>>(
+    input_list: &mut [T],
+    rng: &mut XorShift64,
+    probe: &mut Probe<'_, T, O>,
+) {
+    let len = input_list.len();
+    for position in [len / 4, len / 2, 3 * len / 4] {
+        let other = rng.next_below(len);
+        probe.swap(input_list, position, other);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algoritms::sorting::test_utils::{
+        antiqsort, count_comparisons, median_of_three_killer, n_log_n, organ_pipe, random_values,
+        sawtooth,
+    };
+
+    fn check_sorts(values: &[i64]) {
+        let mut expected = values.to_vec();
+        expected.sort();
+        let mut list = values.to_vec();
+        pdq_sort(&mut list);
+        assert_eq!(list, expected);
+    }
+
+    #[test]
+    fn test_pdq_sort_basic() {
+        let mut list = vec![9, 7, 5, 11, 12, 2, 14, 3, 10, 6];
+        pdq_sort(&mut list);
+        assert_eq!(list, [2, 3, 5, 6, 7, 9, 10, 11, 12, 14]);
+    }
+
+    #[test]
+    fn test_pdq_sort_empty_and_single() {
+        let mut empty: Vec<i32> = vec![];
+        pdq_sort(&mut empty);
+        assert!(empty.is_empty());
+
+        let mut single = vec![1];
+        pdq_sort(&mut single);
+        assert_eq!(single, [1]);
+    }
+
+    #[test]
+    fn test_pdq_sort_patterns() {
+        check_sorts(&random_values(5000, 1_000_000, 11));
+        check_sorts(&random_values(5000, 4, 12));
+        check_sorts(&organ_pipe(5000));
+        check_sorts(&sawtooth(5000, 37));
+        check_sorts(&median_of_three_killer(5000));
+    }
+
+    #[test]
+    fn test_pdq_sort_strings() {
+        let mut list = vec!["dog", "cat", "zebra", "ant", "bear", "cat"];
+        pdq_sort(&mut list);
+        assert_eq!(list, ["ant", "bear", "cat", "cat", "dog", "zebra"]);
+    }
+
+    #[test]
+    fn test_sorted_and_reversed_runs_are_linear() {
+        let len = 10_000;
+        let sorted: Vec<i64> = (0..len as i64).collect();
+        let reversed: Vec<i64> = sorted.iter().rev().copied().collect();
+
+        assert!(count_comparisons(&sorted, pdq_sort) < 2 * len);
+        assert!(count_comparisons(&reversed, pdq_sort) < 3 * len);
+    }
+
+    #[test]
+    fn test_nearly_sorted_is_cheap() {
+        let len = 10_000;
+        let mut list: Vec<i64> = (0..len as i64).collect();
+        list.swap(10, 20);
+        list.swap(5000, 5001);
+
+        assert!(count_comparisons(&list, pdq_sort) < n_log_n(len));
+    }
+
+    #[test]
+    fn test_many_duplicates_are_cheap() {
+        let len = 10_000;
+        let list = random_values(len, 3, 5);
+        assert!(count_comparisons(&list, pdq_sort) < 2 * n_log_n(len));
+    }
+
+    #[test]
+    fn test_quicksort_killers() {
+        let len = 4000;
+        for killer in [
+            median_of_three_killer(len),
+            organ_pipe(len),
+            sawtooth(len, 2),
+        ] {
+            let comparisons = count_comparisons(&killer, pdq_sort);
+            assert!(
+                comparisons < 4 * n_log_n(len),
+                "pdq sort made {comparisons}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_adversary_against_pdq_sort() {
+        let len = 4000;
+        let (comparisons, killer) = antiqsort(len, pdq_sort);
+        assert!(
+            comparisons < 4 * n_log_n(len),
+            "pdq sort made {comparisons}"
+        );
+        check_sorts(&killer);
+    }
+
+    #[test]
+    fn test_partition_equal() {
+        let mut list = vec![3, 5, 3, 4, 3, 9];
+        let mid = partition_equal(&mut list, 0, &mut Probe::new(&mut ()));
+        assert_eq!(mid, 3);
+        assert_eq!(&list[..3], [3, 3, 3]);
+    }
+}
@@ -1,30 +1,306 @@
 use std::cmp::Ordering;
+use std::collections::hash_map::RandomState;
+use std::hash::BuildHasher;
 
-pub fn quick_sort<T: Clone + Copy + Ord>(input_list: &[T]) -> Vec<T> {
+use super::instrument::{Probe, SortObserver};
+
+pub fn quick_sort<T: Clone + Ord>(input_list: &[T]) -> Vec<T> {
+    quick_sort_observed(input_list, &mut ())
+}
+
+/// Writes are reported at the positions the partitioned elements will end up in
+pub fn quick_sort_observed<T: Clone + Ord, O: SortObserver<T>>(
+    input_list: &[T],
+    observer: &mut O,
+) -> Vec<T> {
+    quick_sort_helper(input_list, &mut Probe::new(observer))
+}
+
+fn quick_sort_helper<T: Clone + Ord, O: SortObserver<T>>(
+    input_list: &[T],
+    probe: &mut Probe<'_, T, O>,
+) -> Vec<T> {
     if input_list.len() < 2 {
+        if !input_list.is_empty() {
+            probe.alloc(input_list.len());
+        }
         return input_list.to_vec();
     }
 
     let (mut less, mut greater, mut equal) = (Vec::new(), Vec::new(), Vec::new());
 
-    let pivot = input_list[input_list.len() / 2];
+    let pivot_index = input_list.len() / 2;
 
     input_list
         .iter()
-        .copied()
-        .for_each(|x| match x.cmp(&pivot) {
-            Ordering::Less => less.push(x),
-            Ordering::Greater => greater.push(x),
-            Ordering::Equal => equal.push(x),
+        .enumerate()
+        .for_each(|(i, x)| match probe.cmp(input_list, i, pivot_index) {
+            Ordering::Less => less.push(x.clone()),
+            Ordering::Greater => greater.push(x.clone()),
+            Ordering::Equal => equal.push(x.clone()),
         });
 
-    quick_sort(&less)
+    for part in [&less, &equal, &greater] {
+        if !part.is_empty() {
+            probe.alloc(part.len());
+        }
+    }
+    for (i, x) in less.iter().chain(&equal).chain(&greater).enumerate() {
+        probe.wrote(i, x);
+    }
+    probe.partitioned(less.len(), input_list.len());
+
+    let greater_offset = less.len() + equal.len();
+    let sorted_less = quick_sort_helper(&less, probe);
+    let sorted_greater = quick_sort_helper(&greater, &mut probe.at(greater_offset));
+
+    probe.alloc(input_list.len());
+    sorted_less
         .into_iter()
         .chain(equal)
-        .chain(quick_sort(&greater))
+        .chain(sorted_greater)
         .collect()
 }
 
+/// How the in-place quick sort picks its pivot
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PivotStrategy {
+    Middle,
+    MedianOfThree,
+    /// median of three medians-of-three (Tukey's ninther), for larger slices
+    Ninther,
+    Random,
+}
+
+/// Which partitioning loop the in-place quick sort uses
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PartitionScheme {
+    /// two cursors moving towards each other, fewer swaps and good with duplicates
+    Hoare,
+    /// single cursor with the pivot parked at the end, simpler but more swaps
+    Lomuto,
+}
+
+/// In-place quick sort, works on non-`Copy` data and does not allocate.
+///
+/// Recurses into the smaller half and loops on the larger one so the stack depth
+/// stays O(log n) even when the pivots are bad.
+pub fn quick_sort_in_place<T: Ord>(
+    input_list: &mut [T],
+    pivot: PivotStrategy,
+    scheme: PartitionScheme,
+) {
+    quick_sort_in_place_observed(input_list, pivot, scheme, &mut ());
+}
+
+pub fn quick_sort_in_place_observed<T: Ord, O: SortObserver<T>>(
+    input_list: &mut [T],
+    pivot: PivotStrategy,
+    scheme: PartitionScheme,
+    observer: &mut O,
+) {
+    let mut rng = XorShift64::from_entropy();
+    quick_sort_in_place_helper(
+        input_list,
+        pivot,
+        scheme,
+        &mut rng,
+        &mut Probe::new(observer),
+    );
+}
+
+fn quick_sort_in_place_helper<T: Ord, O: SortObserver<T>>(
+    mut input_list: &mut [T],
+    pivot: PivotStrategy,
+    scheme: PartitionScheme,
+    rng: &mut XorShift64,
+    probe: &mut Probe<'_, T, O>,
+) {
+    let mut offset = 0;
+
+    while input_list.len() > 1 {
+        let mut probe = probe.at(offset);
+        let pivot_index = choose_pivot(input_list, pivot, rng, &mut probe);
+        let mid = match scheme {
+            PartitionScheme::Hoare => partition_hoare(input_list, pivot_index, &mut probe),
+            PartitionScheme::Lomuto => partition_lomuto(input_list, pivot_index, &mut probe),
+        };
+        probe.partitioned(mid, input_list.len());
+
+        // pivot is in its final place at `mid`, sort both sides of it
+        let (left, right) = std::mem::take(&mut input_list).split_at_mut(mid);
+        let right = &mut right[1..];
+
+        if left.len() < right.len() {
+            quick_sort_in_place_helper(left, pivot, scheme, rng, &mut probe);
+            input_list = right;
+            offset += mid + 1;
+        } else {
+            quick_sort_in_place_helper(right, pivot, scheme, rng, &mut probe.at(mid + 1));
+            input_list = left;
+        }
+    }
+}
+
+/// Returns the index of the pivot to use for `input_list`, which must not be empty
+pub(crate) fn choose_pivot<T: Ord, O: SortObserver<T>>(
+    input_list: &[T],
+    strategy: PivotStrategy,
+    rng: &mut XorShift64,
+    probe: &mut Probe<'_, T, O>,
+) -> usize {
+    let len = input_list.len();
+    let mid = len / 2;
+    let mut median = |a, b, c| median_of_three(input_list, a, b, c, probe);
+
+    match strategy {
+        PivotStrategy::Middle => mid,
+        PivotStrategy::MedianOfThree => median(0, mid, len - 1),
+        // below this size the ninther samples overlap, plain median of three is just as good
+        PivotStrategy::Ninther if len < 40 => median(0, mid, len - 1),
+        PivotStrategy::Ninther => {
+            let step = len / 8;
+            let first = median(0, step, 2 * step);
+            let middle = median(mid - step, mid, mid + step);
+            let last = median(len - 1 - 2 * step, len - 1 - step, len - 1);
+            median(first, middle, last)
+        }
+        PivotStrategy::Random => rng.next_below(len),
+    }
+}
+
+/// Index of the median of the three values at `a`, `b` and `c`
+pub(crate) fn median_of_three<T: Ord, O: SortObserver<T>>(
+    input_list: &[T],
+    a: usize,
+    b: usize,
+    c: usize,
+    probe: &mut Probe<'_, T, O>,
+) -> usize {
+    if probe.less(input_list, a, b) {
+        if probe.less(input_list, b, c) {
+            b
+        } else if probe.less(input_list, a, c) {
+            c
+        } else {
+            a
+        }
+    } else if probe.less(input_list, a, c) {
+        a
+    } else if probe.less(input_list, b, c) {
+        c
+    } else {
+        b
+    }
+}
+
+/// Hoare style partition around the value at `pivot_index`.
+///
+/// Returns the final index of the pivot, everything left of it is `<=` and everything
+/// right of it is `>=`. Equal elements stop both cursors so runs of duplicates get
+/// split evenly instead of all landing on one side.
+pub(crate) fn partition_hoare<T: Ord, O: SortObserver<T>>(
+    input_list: &mut [T],
+    pivot_index: usize,
+    probe: &mut Probe<'_, T, O>,
+) -> usize {
+    if pivot_index != 0 {
+        probe.swap(input_list, 0, pivot_index);
+    }
+
+    // the pivot stays at 0, the cursors scan input_list[1..] from both ends
+    let mut left = 1;
+    let mut right = input_list.len();
+
+    loop {
+        while left < right && probe.less(input_list, left, 0) {
+            left += 1;
+        }
+        while left < right && probe.less(input_list, 0, right - 1) {
+            right -= 1;
+        }
+        if left >= right {
+            break;
+        }
+        right -= 1;
+        probe.swap(input_list, left, right);
+        left += 1;
+    }
+
+    // input_list[left - 1] is the last element <= pivot
+    let mid = left - 1;
+    if mid != 0 {
+        probe.swap(input_list, 0, mid);
+    }
+    mid
+}
+
+/// Lomuto partition around the value at `pivot_index`, same contract as [`partition_hoare`].
+pub(crate) fn partition_lomuto<T: Ord, O: SortObserver<T>>(
+    input_list: &mut [T],
+    pivot_index: usize,
+    probe: &mut Probe<'_, T, O>,
+) -> usize {
+    let last = input_list.len() - 1;
+    if pivot_index != last {
+        probe.swap(input_list, pivot_index, last);
+    }
+
+    let mut store = 0;
+    for i in 0..last {
+        if probe.less(input_list, i, last) {
+            if i != store {
+                probe.swap(input_list, i, store);
+            }
+            store += 1;
+        }
+    }
+
+    if store != last {
+        probe.swap(input_list, store, last);
+    }
+    store
+}
+
+/// Small xorshift generator so random pivots don't need an external crate.
+/// Not suitable for anything but shuffling and pivot picking.
+#[derive(Debug, Clone)]
+pub(crate) struct XorShift64 {
+    state: u64,
+}
+
+impl XorShift64 {
+    pub(crate) fn new(seed: u64) -> Self {
+        // zero is a fixed point of xorshift, so nudge it
+        Self {
+            state: if seed == 0 {
+                0x9E37_79B9_7F4A_7C15
+            } else {
+                seed
+            },
+        }
+    }
+
+    /// Seeded from the per-process random keys std uses for `HashMap`
+    pub(crate) fn from_entropy() -> Self {
+        Self::new(RandomState::new().hash_one(0u64))
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        let mut x = self.state;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.state = x;
+        x
+    }
+
+    /// Uniform-ish value in `0..bound`, `bound` must be non-zero
+    pub(crate) fn next_below(&mut self, bound: usize) -> usize {
+        (self.next_u64() % bound as u64) as usize
+    }
+}
+
 mod test {
     use super::*;
 
@@ -146,4 +422,110 @@ mod test {
         let result = quick_sort(&list);
         assert_eq!(result, [2, 3, 5, 6, 7, 9, 10, 11, 12, 14]);
     }
+
+    fn all_strategies() -> Vec<(PivotStrategy, PartitionScheme)> {
+        let mut result = Vec::new();
+        for pivot in [
+            PivotStrategy::Middle,
+            PivotStrategy::MedianOfThree,
+            PivotStrategy::Ninther,
+            PivotStrategy::Random,
+        ] {
+            for scheme in [PartitionScheme::Hoare, PartitionScheme::Lomuto] {
+                result.push((pivot, scheme));
+            }
+        }
+        result
+    }
+
+    #[test]
+    fn test_in_place_matches_quicksort() {
+        let list = vec![9, 7, 5, 11, 12, 2, 14, 3, 10, 6, -1, 0, 5, 5];
+        let expected = quick_sort(&list);
+
+        for (pivot, scheme) in all_strategies() {
+            let mut copy = list.clone();
+            quick_sort_in_place(&mut copy, pivot, scheme);
+            assert_eq!(copy, expected, "{:?} / {:?}", pivot, scheme);
+        }
+    }
+
+    #[test]
+    fn test_in_place_empty_and_single() {
+        for (pivot, scheme) in all_strategies() {
+            let mut empty: Vec<i32> = vec![];
+            quick_sort_in_place(&mut empty, pivot, scheme);
+            assert!(empty.is_empty());
+
+            let mut single = vec![42];
+            quick_sort_in_place(&mut single, pivot, scheme);
+            assert_eq!(single, [42]);
+        }
+    }
+
+    #[test]
+    fn test_in_place_all_same_and_sorted() {
+        for (pivot, scheme) in all_strategies() {
+            let mut same = vec![5; 100];
+            quick_sort_in_place(&mut same, pivot, scheme);
+            assert_eq!(same, vec![5; 100]);
+
+            let mut sorted: Vec<i32> = (0..500).collect();
+            quick_sort_in_place(&mut sorted, pivot, scheme);
+            assert_eq!(sorted, (0..500).collect::<Vec<_>>());
+
+            let mut reversed: Vec<i32> = (0..500).rev().collect();
+            quick_sort_in_place(&mut reversed, pivot, scheme);
+            assert_eq!(reversed, (0..500).collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn test_in_place_non_copy_strings() {
+        let list: Vec<String> = ["dog", "cat", "zebra", "ant", "bear", "cat"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        for (pivot, scheme) in all_strategies() {
+            let mut copy = list.clone();
+            quick_sort_in_place(&mut copy, pivot, scheme);
+            assert_eq!(copy, ["ant", "bear", "cat", "cat", "dog", "zebra"]);
+        }
+    }
+
+    #[test]
+    fn test_in_place_random_data() {
+        let mut rng = XorShift64::new(42);
+        let list: Vec<i64> = (0..2000).map(|_| (rng.next_u64() % 100) as i64).collect();
+        let mut expected = list.clone();
+        expected.sort();
+
+        for (pivot, scheme) in all_strategies() {
+            let mut copy = list.clone();
+            quick_sort_in_place(&mut copy, pivot, scheme);
+            assert_eq!(copy, expected, "{:?} / {:?}", pivot, scheme);
+        }
+    }
+
+    #[test]
+    fn test_median_of_three() {
+        let mut silent = ();
+        let mut probe = Probe::new(&mut silent);
+        assert_eq!(median_of_three(&[1, 2, 3], 0, 1, 2, &mut probe), 1);
+        assert_eq!(median_of_three(&[3, 2, 1], 0, 1, 2, &mut probe), 1);
+        assert_eq!(median_of_three(&[2, 3, 1], 0, 1, 2, &mut probe), 0);
+        assert_eq!(median_of_three(&[1, 3, 2], 0, 1, 2, &mut probe), 2);
+    }
+
+    #[test]
+    fn test_partition_places_pivot() {
+        for partition in [partition_hoare::<i32, ()>, partition_lomuto::<i32, ()>] {
+            let mut list = vec![5, 1, 9, 3, 7, 3, 8];
+            let mid = partition(&mut list, 3, &mut Probe::new(&mut ())); // pivot value 3
+            assert_eq!(list[mid], 3);
+            assert!(list[..mid].iter().all(|x| *x <= 3));
+            assert!(list[mid + 1..].iter().all(|x| *x >= 3));
+        }
+    }
 }
@@ -0,0 +1,207 @@
+// LSD (least significant digit) radix sort for integers and floats. Every key is mapped
+// to an unsigned integer with the same ordering, then sorted one byte at a time with a
+// stable counting pass, O(n * bytes).
+
+use super::instrument::{Probe, SortObserver};
+
+/// Maps a value to an unsigned key whose ordering matches the value's ordering
+pub trait RadixKey: Copy {
+    /// Number of bytes in the key, i.e. the number of passes
+    const BYTES: usize;
+
+    fn radix_key(&self) -> u64;
+}
+
+macro_rules! radix_key_unsigned {
+    ($($t:ty),*) => {
+        $(impl RadixKey for $t {
+            const BYTES: usize = std::mem::size_of::<$t>();
+
+            fn radix_key(&self) -> u64 {
+                *self as u64
+            }
+        })*
+    };
+}
+
+// flipping the sign bit moves negative numbers below the positive ones and keeps the
+// two's complement order inside each half
+macro_rules! radix_key_signed {
+    ($($t:ty => $u:ty),*) => {
+        $(impl RadixKey for $t {
+            const BYTES: usize = std::mem::size_of::<$t>();
+
+            fn radix_key(&self) -> u64 {
+                (*self as $u ^ (1 << (<$u>::BITS - 1))) as u64
+            }
+        })*
+    };
+}
+
+radix_key_unsigned!(u8, u16, u32, u64, usize);
+radix_key_signed!(i8 => u8, i16 => u16, i32 => u32, i64 => u64, isize => usize);
+
+// IEEE 754 floats compare like sign-magnitude integers: for positives set the sign bit so
+// they land above the negatives, for negatives flip every bit so larger magnitudes sort
+// lower. This gives the same order as `total_cmp` (-NaN < -inf < .. < -0.0 < 0.0 < .. < NaN).
+impl RadixKey for f32 {
+    const BYTES: usize = 4;
+
+    fn radix_key(&self) -> u64 {
+        let bits = self.to_bits();
+        let mask = if bits >> 31 == 1 { u32::MAX } else { 1 << 31 };
+        (bits ^ mask) as u64
+    }
+}
+
+impl RadixKey for f64 {
+    const BYTES: usize = 8;
+
+    fn radix_key(&self) -> u64 {
+        let bits = self.to_bits();
+        let mask = if bits >> 63 == 1 { u64::MAX } else { 1 << 63 };
+        bits ^ mask
+    }
+}
+
+pub fn radix_sort<T: RadixKey>(input_list: &mut [T]) {
+    radix_sort_observed(input_list, &mut ());
+}
+
+/// Reports the copy back from the scratch buffer after each pass as writes
+pub fn radix_sort_observed<T: RadixKey, O: SortObserver<T>>(
+    input_list: &mut [T],
+    observer: &mut O,
+) {
+    let len = input_list.len();
+    if len < 2 {
+        return;
+    }
+    let mut probe = Probe::new(observer);
+
+    let mut buffer = input_list.to_vec();
+    probe.alloc(len);
+
+    for byte in 0..T::BYTES {
+        let shift = byte * 8;
+        let digit = |x: &T| ((x.radix_key() >> shift) & 0xFF) as usize;
+
+        let mut counts = [0usize; 256];
+        for x in input_list.iter() {
+            counts[digit(x)] += 1;
+        }
+
+        // every key has the same digit here, the pass wouldn't change anything
+        if counts.contains(&len) {
+            continue;
+        }
+
+        let mut starts = [0usize; 256];
+        for d in 1..256 {
+            starts[d] = starts[d - 1] + counts[d - 1];
+        }
+
+        for x in input_list.iter() {
+            let d = digit(x);
+            buffer[starts[d]] = *x;
+            starts[d] += 1;
+        }
+        for (i, x) in buffer.iter().enumerate() {
+            probe.write(input_list, i, x);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algoritms::sorting::quick_sort::XorShift64;
+
+    #[test]
+    fn test_radix_sort_unsigned() {
+        let mut list: Vec<u32> = vec![170, 45, 75, 90, 802, 24, 2, 66, u32::MAX, 0];
+        radix_sort(&mut list);
+        assert_eq!(list, [0, 2, 24, 45, 66, 75, 90, 170, 802, u32::MAX]);
+    }
+
+    #[test]
+    fn test_radix_sort_signed() {
+        let mut list: Vec<i32> = vec![5, -1, 0, i32::MIN, -300, i32::MAX, 7, -1];
+        radix_sort(&mut list);
+        assert_eq!(list, [i32::MIN, -300, -1, -1, 0, 5, 7, i32::MAX]);
+
+        let mut small: Vec<i8> = vec![3, -128, 127, 0, -5];
+        radix_sort(&mut small);
+        assert_eq!(small, [-128, -5, 0, 3, 127]);
+    }
+
+    #[test]
+    fn test_radix_sort_empty_and_single() {
+        let mut empty: Vec<u64> = vec![];
+        radix_sort(&mut empty);
+        assert!(empty.is_empty());
+
+        let mut single = vec![-4i64];
+        radix_sort(&mut single);
+        assert_eq!(single, [-4]);
+    }
+
+    #[test]
+    fn test_radix_sort_random_matches_std() {
+        let mut rng = XorShift64::new(2024);
+        let mut unsigned: Vec<u64> = (0..5000).map(|_| rng.next_u64()).collect();
+        let mut signed: Vec<i64> = (0..5000).map(|_| rng.next_u64() as i64).collect();
+        let mut expected_unsigned = unsigned.clone();
+        let mut expected_signed = signed.clone();
+        expected_unsigned.sort();
+        expected_signed.sort();
+
+        radix_sort(&mut unsigned);
+        radix_sort(&mut signed);
+        assert_eq!(unsigned, expected_unsigned);
+        assert_eq!(signed, expected_signed);
+    }
+
+    #[test]
+    fn test_radix_sort_floats() {
+        let mut list = vec![
+            3.5,
+            -0.0,
+            0.0,
+            -2.25,
+            f64::INFINITY,
+            1e-300,
+            -1e300,
+            f64::NEG_INFINITY,
+            0.1,
+        ];
+        let mut expected = list.clone();
+        expected.sort_by(f64::total_cmp);
+        radix_sort(&mut list);
+        assert_eq!(
+            list.iter().map(|x| x.to_bits()).collect::<Vec<_>>(),
+            expected.iter().map(|x| x.to_bits()).collect::<Vec<_>>()
+        );
+
+        let mut single: Vec<f32> = vec![1.5, -1.5, 0.25, -100.0];
+        radix_sort(&mut single);
+        assert_eq!(single, [-100.0, -1.5, 0.25, 1.5]);
+    }
+
+    #[test]
+    fn test_radix_key_preserves_order() {
+        let values = [i16::MIN, -1000, -1, 0, 1, 1000, i16::MAX];
+        assert!(
+            values
+                .windows(2)
+                .all(|w| w[0].radix_key() < w[1].radix_key())
+        );
+
+        let floats = [f64::NEG_INFINITY, -1.0, -f64::MIN_POSITIVE, -0.0, 0.0, 2.0];
+        assert!(
+            floats
+                .windows(2)
+                .all(|w| w[0].radix_key() < w[1].radix_key())
+        );
+    }
+}
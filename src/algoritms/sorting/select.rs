@@ -0,0 +1,305 @@
+// Selection: finding the k-th smallest element without sorting everything.
+// - quick select: quick sort that only recurses into the side holding k, O(n) expected
+// - median of medians (Blum, Floyd, Pratt, Rivest, Tarjan): pivots are guaranteed to be
+//   good, O(n) in the worst case but with a larger constant
+// - top_k / bottom_k keep a heap of k items, O(n log k) on any iterator
+//
+// The in-place functions follow the `select_nth_unstable` contract: afterwards the
+// element at `k` is the one that would be there if sorted, everything before it is `<=`
+// and everything after it is `>=`.
+
+use std::cmp::Ordering;
+
+use super::insertion_sort::insertion_sort;
+use super::instrument::Probe;
+use super::intro_sort::intro_sort;
+use super::quick_sort::{PivotStrategy, XorShift64, choose_pivot, partition_hoare};
+use crate::data_structures::binary_heap::MoHeap;
+
+/// Puts the k-th smallest (0-based) element at index `k` and returns it, random pivots.
+/// Panics if `k` is out of bounds.
+pub fn quick_select<T: Ord>(input_list: &mut [T], k: usize) -> &T {
+    assert!(
+        k < input_list.len(),
+        "k is {k} but the length is {}",
+        input_list.len()
+    );
+    let mut rng = XorShift64::from_entropy();
+    let mut silent = ();
+    let mut probe = Probe::new(&mut silent);
+
+    let (mut start, mut end) = (0, input_list.len());
+    while end - start > 1 {
+        let part = &mut input_list[start..end];
+        let pivot_index = choose_pivot(part, PivotStrategy::Random, &mut rng, &mut probe);
+        let mid = start + partition_hoare(part, pivot_index, &mut probe);
+
+        match k.cmp(&mid) {
+            Ordering::Equal => break,
+            Ordering::Less => end = mid,
+            Ordering::Greater => start = mid + 1,
+        }
+    }
+    &input_list[k]
+}
+
+/// Same as [`quick_select`] but worst-case linear, the pivot is the median of the
+/// medians of groups of five. Panics if `k` is out of bounds.
+pub fn median_of_medians_select<T: Ord>(input_list: &mut [T], k: usize) -> &T {
+    assert!(
+        k < input_list.len(),
+        "k is {k} but the length is {}",
+        input_list.len()
+    );
+    select_in_place(input_list, k);
+    &input_list[k]
+}
+
+fn select_in_place<T: Ord>(input_list: &mut [T], k: usize) {
+    let mut silent = ();
+    let mut probe = Probe::new(&mut silent);
+    let (mut start, mut end) = (0, input_list.len());
+
+    loop {
+        let part = &mut input_list[start..end];
+        if part.len() <= 5 {
+            insertion_sort(part);
+            return;
+        }
+
+        let pivot_index = median_of_medians(part);
+        let mid = start + partition_hoare(part, pivot_index, &mut probe);
+
+        match k.cmp(&mid) {
+            Ordering::Equal => return,
+            Ordering::Less => end = mid,
+            Ordering::Greater => start = mid + 1,
+        }
+    }
+}
+
+/// Sorts each group of five, gathers their medians at the front and selects the median
+/// of those. At least 3/10 of the slice is on either side of the returned pivot.
+fn median_of_medians<T: Ord>(input_list: &mut [T]) -> usize {
+    let groups = input_list.len().div_ceil(5);
+    for group in 0..groups {
+        let start = group * 5;
+        let end = (start + 5).min(input_list.len());
+        insertion_sort(&mut input_list[start..end]);
+        input_list.swap(group, start + (end - start) / 2);
+    }
+
+    let mid = groups / 2;
+    select_in_place(&mut input_list[..groups], mid);
+    mid
+}
+
+/// Sorts just the `k` smallest elements into `input_list[..k]`, the order of the rest is
+/// unspecified. `k` larger than the slice sorts all of it.
+pub fn partial_sort<T: Ord>(input_list: &mut [T], k: usize) {
+    let k = k.min(input_list.len());
+    if k == 0 {
+        return;
+    }
+    if k < input_list.len() {
+        quick_select(input_list, k - 1);
+    }
+    intro_sort(&mut input_list[..k]);
+}
+
+/// The `k` largest items, largest first. Only `k` items are held at any time, so this
+/// works on streams that don't fit in memory.
+pub fn top_k<T: Ord>(items: impl IntoIterator<Item = T>, k: usize) -> Vec<T> {
+    // min heap: the root is the smallest of the current top k, the first to be replaced
+    let mut heap = MoHeap::new_min();
+    bounded_push(&mut heap, items, k);
+    let mut result = heap.into_sorted_vec();
+    result.reverse();
+    result
+}
+
+/// The `k` smallest items, smallest first
+pub fn bottom_k<T: Ord>(items: impl IntoIterator<Item = T>, k: usize) -> Vec<T> {
+    let mut heap = MoHeap::new_max();
+    bounded_push(&mut heap, items, k);
+    heap.into_sorted_vec()
+}
+
+fn bounded_push<T: Ord>(heap: &mut MoHeap<T>, items: impl IntoIterator<Item = T>, k: usize) {
+    if k == 0 {
+        return;
+    }
+    for item in items {
+        if heap.len() < k {
+            heap.push(item);
+        } else {
+            heap.push_pop(item);
+        }
+    }
+}
+
+/// The lower median, `None` for an empty slice. Reorders the slice.
+pub fn median<T: Ord>(input_list: &mut [T]) -> Option<&T> {
+    if input_list.is_empty() {
+        return None;
+    }
+    let k = (input_list.len() - 1) / 2;
+    Some(quick_select(input_list, k))
+}
+
+/// The `p`-th percentile by the nearest-rank method: the smallest element that at least
+/// `p` percent of the data is `<=` to. `None` for an empty slice, panics unless
+/// `0 <= p <= 100`. Reorders the slice.
+pub fn percentile<T: Ord>(input_list: &mut [T], p: f64) -> Option<&T> {
+    assert!(
+        (0.0..=100.0).contains(&p),
+        "percentile must be in 0..=100, got {p}"
+    );
+    if input_list.is_empty() {
+        return None;
+    }
+    let rank = (p / 100.0 * input_list.len() as f64).ceil() as usize;
+    Some(quick_select(input_list, rank.max(1) - 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algoritms::sorting::test_utils::{
+        counted, median_of_three_killer, organ_pipe, random_values, sawtooth,
+    };
+
+    type Select = fn(&mut [i64], usize) -> &i64;
+
+    fn selectors() -> Vec<(&'static str, Select)> {
+        vec![
+            ("quick select", quick_select),
+            ("median of medians", median_of_medians_select),
+        ]
+    }
+
+    fn check_selected(list: &[i64], k: usize, sorted: &[i64]) {
+        assert_eq!(list[k], sorted[k]);
+        assert!(list[..k].iter().all(|x| *x <= list[k]));
+        assert!(list[k + 1..].iter().all(|x| *x >= list[k]));
+    }
+
+    #[test]
+    fn test_select_every_k() {
+        for (name, select) in selectors() {
+            for len in [1, 2, 5, 6, 11, 40] {
+                let values = random_values(len, 10, len as u64);
+                let mut sorted = values.clone();
+                sorted.sort();
+                for k in 0..len {
+                    let mut list = values.clone();
+                    assert_eq!(*select(&mut list, k), sorted[k], "{name}, len {len}, k {k}");
+                    check_selected(&list, k, &sorted);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_select_patterns() {
+        let len = 5000;
+        for (name, select) in selectors() {
+            for values in [
+                random_values(len, 1_000_000, 1),
+                random_values(len, 3, 2),
+                organ_pipe(len),
+                sawtooth(len, 17),
+                median_of_three_killer(len),
+                (0..len as i64).collect(),
+            ] {
+                let mut sorted = values.clone();
+                sorted.sort();
+                for k in [0, 1, len / 3, len / 2, len - 1] {
+                    let mut list = values.clone();
+                    select(&mut list, k);
+                    check_selected(&list, k, &sorted);
+                    assert_eq!(list[k], sorted[k], "{name}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "k is 3 but the length is 3")]
+    fn test_select_out_of_bounds() {
+        quick_select(&mut [1, 2, 3], 3);
+    }
+
+    #[test]
+    fn test_median_of_medians_is_linear() {
+        let len = 20_000;
+        for values in [
+            random_values(len, 1_000_000, 7),
+            median_of_three_killer(len),
+            organ_pipe(len),
+            random_values(len, 2, 8),
+        ] {
+            let (mut list, counter) = counted(&values);
+            median_of_medians_select(&mut list, len / 2);
+            assert!(
+                counter.get() < 40 * len,
+                "made {} comparisons",
+                counter.get()
+            );
+        }
+    }
+
+    #[test]
+    fn test_partial_sort() {
+        let values = random_values(1000, 500, 3);
+        let mut sorted = values.clone();
+        sorted.sort();
+
+        for k in [0, 1, 10, 999, 1000, 2000] {
+            let mut list = values.clone();
+            partial_sort(&mut list, k);
+            let k = k.min(list.len());
+            assert_eq!(list[..k], sorted[..k]);
+
+            let mut rest = list[k..].to_vec();
+            rest.sort();
+            assert_eq!(rest, sorted[k..]);
+        }
+    }
+
+    #[test]
+    fn test_top_and_bottom_k() {
+        let values = random_values(1000, 100, 9);
+        let mut sorted = values.clone();
+        sorted.sort();
+
+        assert_eq!(bottom_k(values.iter().copied(), 10), sorted[..10]);
+        let mut largest = sorted[990..].to_vec();
+        largest.reverse();
+        assert_eq!(top_k(values.iter().copied(), 10), largest);
+
+        assert!(top_k(values.iter().copied(), 0).is_empty());
+        assert_eq!(bottom_k(vec![3, 1, 2], 5), [1, 2, 3]);
+        assert_eq!(top_k(vec!["b", "c", "a"], 2), ["c", "b"]);
+    }
+
+    #[test]
+    fn test_median_and_percentile() {
+        assert_eq!(median::<i32>(&mut []), None);
+        assert_eq!(median(&mut [5, 1, 3]), Some(&3));
+        assert_eq!(median(&mut [4, 1, 3, 2]), Some(&2));
+
+        let mut list: Vec<i32> = (1..=100).rev().collect();
+        assert_eq!(percentile(&mut list, 0.0), Some(&1));
+        assert_eq!(percentile(&mut list, 25.0), Some(&25));
+        assert_eq!(percentile(&mut list, 90.0), Some(&90));
+        assert_eq!(percentile(&mut list, 99.5), Some(&100));
+        assert_eq!(percentile(&mut list, 100.0), Some(&100));
+
+        // nearest rank of the classic example: 15, 20, 35, 40, 50
+        let mut list = vec![50, 15, 40, 20, 35];
+        assert_eq!(percentile(&mut list, 30.0), Some(&20));
+        assert_eq!(percentile(&mut list, 40.0), Some(&20));
+        assert_eq!(percentile(&mut list, 50.0), Some(&35));
+    }
+}
@@ -1,25 +1,41 @@
-fn find_smallest(input_list: &[i32]) -> usize {
-    let mut smallest = input_list[0];
+use super::instrument::{Probe, SortObserver};
 
+fn find_smallest<T: Ord>(input_list: &[T]) -> usize {
+    find_smallest_probed(input_list, &mut Probe::new(&mut ()))
+}
+
+fn find_smallest_probed<T: Ord, O: SortObserver<T>>(
+    input_list: &[T],
+    probe: &mut Probe<'_, T, O>,
+) -> usize {
     let mut smallest_index: usize = 0;
 
-    for (i, number) in input_list.iter().enumerate() {
-        if number < &smallest {
-            smallest = *number;
+    for i in 1..input_list.len() {
+        if probe.less(input_list, i, smallest_index) {
             smallest_index = i;
         }
     }
     smallest_index
 }
 
-pub fn selection_sort(input_list: &[i32]) -> Vec<i32> {
-    let mut result: Vec<i32> = vec![];
-    let mut cloned_list = input_list.to_vec();
-
-    while !cloned_list.is_empty() {
-        let smallest = find_smallest(&cloned_list);
+pub fn selection_sort<T: Clone + Ord>(input_list: &[T]) -> Vec<T> {
+    selection_sort_observed(input_list, &mut ())
+}
 
-        result.push(cloned_list.swap_remove(smallest))
+/// Grows a sorted prefix by swapping the smallest remaining element to its end
+pub fn selection_sort_observed<T: Clone + Ord, O: SortObserver<T>>(
+    input_list: &[T],
+    observer: &mut O,
+) -> Vec<T> {
+    let mut probe = Probe::new(observer);
+    let mut result = input_list.to_vec();
+    probe.alloc(result.len());
+
+    for i in 0..result.len() {
+        let smallest = i + find_smallest_probed(&result[i..], &mut probe.at(i));
+        if smallest != i {
+            probe.swap(&mut result, i, smallest);
+        }
     }
 
     result
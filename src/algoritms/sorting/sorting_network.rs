@@ -0,0 +1,399 @@
+// Sorting networks: a fixed sequence of compare-exchanges that sorts every input of one
+// size. The sequence doesn't depend on the data, so with `min` / `max` for the
+// compare-exchange there are no data dependent branches and the comparators inside a
+// layer are independent of each other, which is what SIMD code wants.
+//
+// The networks for n <= 16 are the smallest known (proven optimal up to 12), written out
+// layer by layer. Bitonic sort (Batcher) generates a network for any power of two, with
+// O(n log^2 n) comparators.
+
+/// Largest input `network_sort` has a network for
+pub const MAX_NETWORK_SIZE: usize = 16;
+
+/// Layers of comparators `(a, b)` with `a < b`, after which the smaller value is at `a`.
+/// Comparators within a layer touch disjoint positions.
+pub type Network = &'static [&'static [(u8, u8)]];
+
+const NETWORK_2: Network = &[&[(0, 1)]];
+
+const NETWORK_3: Network = &[&[(0, 2)], &[(0, 1)], &[(1, 2)]];
+
+const NETWORK_4: Network = &[&[(0, 1), (2, 3)], &[(0, 2), (1, 3)], &[(1, 2)]];
+
+const NETWORK_5: Network = &[
+    &[(0, 3), (1, 4)],
+    &[(0, 2), (1, 3)],
+    &[(0, 1), (2, 4)],
+    &[(1, 2), (3, 4)],
+    &[(2, 3)],
+];
+
+const NETWORK_6: Network = &[
+    &[(0, 5), (1, 3), (2, 4)],
+    &[(1, 2), (3, 4)],
+    &[(0, 3), (2, 5)],
+    &[(0, 1), (2, 3), (4, 5)],
+    &[(1, 2), (3, 4)],
+];
+
+const NETWORK_7: Network = &[
+    &[(0, 6), (2, 3), (4, 5)],
+    &[(0, 2), (1, 4), (3, 6)],
+    &[(0, 1), (2, 5), (3, 4)],
+    &[(1, 2), (4, 6)],
+    &[(2, 3), (4, 5)],
+    &[(1, 2), (3, 4), (5, 6)],
+];
+
+const NETWORK_8: Network = &[
+    &[(0, 2), (1, 3), (4, 6), (5, 7)],
+    &[(0, 4), (1, 5), (2, 6), (3, 7)],
+    &[(0, 1), (2, 3), (4, 5), (6, 7)],
+    &[(2, 4), (3, 5)],
+    &[(1, 4), (3, 6)],
+    &[(1, 2), (3, 4), (5, 6)],
+];
+
+const NETWORK_9: Network = &[
+    &[(0, 3), (1, 7), (2, 5), (4, 8)],
+    &[(0, 7), (2, 4), (3, 8), (5, 6)],
+    &[(0, 2), (1, 3), (4, 5), (7, 8)],
+    &[(1, 4), (3, 6), (5, 7)],
+    &[(0, 1), (2, 4), (3, 5), (6, 8)],
+    &[(2, 3), (4, 5), (6, 7)],
+    &[(1, 2), (3, 4), (5, 6)],
+];
+
+const NETWORK_10: Network = &[
+    &[(0, 8), (1, 9), (2, 7), (3, 5), (4, 6)],
+    &[(0, 2), (1, 4), (5, 8), (7, 9)],
+    &[(0, 3), (2, 4), (5, 7), (6, 9)],
+    &[(0, 1), (3, 6), (8, 9)],
+    &[(1, 5), (2, 3), (4, 8), (6, 7)],
+    &[(1, 2), (3, 5), (4, 6), (7, 8)],
+    &[(2, 3), (4, 5), (6, 7)],
+    &[(3, 4), (5, 6)],
+];
+
+const NETWORK_11: Network = &[
+    &[(0, 9), (1, 6), (2, 4), (3, 7), (5, 8)],
+    &[(0, 1), (3, 5), (4, 10), (6, 9), (7, 8)],
+    &[(1, 3), (2, 5), (4, 7), (8, 10)],
+    &[(0, 4), (1, 2), (3, 7), (5, 9), (6, 8)],
+    &[(0, 1), (2, 6), (4, 5), (7, 8), (9, 10)],
+    &[(2, 4), (3, 6), (5, 7), (8, 9)],
+    &[(1, 2), (3, 4), (5, 6), (7, 8)],
+    &[(2, 3), (4, 5), (6, 7)],
+];
+
+const NETWORK_12: Network = &[
+    &[(0, 8), (1, 7), (2, 6), (3, 11), (4, 10), (5, 9)],
+    &[(0, 1), (2, 5), (3, 4), (6, 9), (7, 8), (10, 11)],
+    &[(0, 2), (1, 6), (5, 10), (9, 11)],
+    &[(0, 3), (1, 2), (4, 6), (5, 7), (8, 11), (9, 10)],
+    &[(1, 4), (3, 5), (6, 8), (7, 10)],
+    &[(1, 3), (2, 5), (6, 9), (8, 10)],
+    &[(2, 3), (4, 5), (6, 7), (8, 9)],
+    &[(4, 6), (5, 7)],
+    &[(3, 4), (5, 6), (7, 8)],
+];
+
+const NETWORK_13: Network = &[
+    &[(0, 12), (1, 10), (2, 9), (3, 7), (5, 11), (6, 8)],
+    &[(1, 6), (2, 3), (4, 11), (7, 9), (8, 10)],
+    &[(0, 4), (1, 2), (3, 6), (7, 8), (9, 10), (11, 12)],
+    &[(4, 6), (5, 9), (8, 11), (10, 12)],
+    &[(0, 5), (3, 8), (4, 7), (6, 11), (9, 10)],
+    &[(0, 1), (2, 5), (6, 9), (7, 8), (10, 11)],
+    &[(1, 3), (2, 4), (5, 6), (9, 10)],
+    &[(1, 2), (3, 4), (5, 7), (6, 8)],
+    &[(2, 3), (4, 5), (6, 7), (8, 9)],
+    &[(3, 4), (5, 6)],
+];
+
+const NETWORK_14: Network = &[
+    &[(0, 1), (2, 3), (4, 5), (6, 7), (8, 9), (10, 11), (12, 13)],
+    &[(0, 2), (1, 3), (4, 8), (5, 9), (10, 12), (11, 13)],
+    &[(0, 4), (1, 2), (3, 7), (5, 8), (6, 10), (9, 13), (11, 12)],
+    &[(0, 6), (1, 5), (3, 9), (4, 10), (7, 13), (8, 12)],
+    &[(2, 10), (3, 11), (4, 6), (7, 9)],
+    &[(1, 3), (2, 8), (5, 11), (6, 7), (10, 12)],
+    &[(1, 4), (2, 6), (3, 5), (7, 11), (8, 10), (9, 12)],
+    &[(2, 4), (3, 6), (5, 8), (7, 10), (9, 11)],
+    &[(3, 4), (5, 6), (7, 8), (9, 10)],
+    &[(6, 7)],
+];
+
+// the 16 input network with the last input and its comparators removed
+const NETWORK_15: Network = &[
+    &[(0, 13), (1, 12), (3, 14), (4, 8), (5, 6), (7, 11), (9, 10)],
+    &[(0, 5), (1, 7), (2, 9), (3, 4), (6, 13), (8, 14), (11, 12)],
+    &[(0, 1), (2, 3), (4, 5), (6, 8), (7, 9), (10, 11), (12, 13)],
+    &[(0, 2), (1, 3), (4, 10), (5, 11), (6, 7), (8, 9), (12, 14)],
+    &[(1, 2), (3, 12), (4, 6), (5, 7), (8, 10), (9, 11), (13, 14)],
+    &[(1, 4), (2, 6), (5, 8), (7, 10), (9, 13), (11, 14)],
+    &[(2, 4), (3, 6), (9, 12), (11, 13)],
+    &[(3, 5), (6, 8), (7, 9), (10, 12)],
+    &[(3, 4), (5, 6), (7, 8), (9, 10), (11, 12)],
+    &[(6, 7), (8, 9)],
+];
+
+const NETWORK_16: Network = &[
+    &[
+        (0, 13),
+        (1, 12),
+        (2, 15),
+        (3, 14),
+        (4, 8),
+        (5, 6),
+        (7, 11),
+        (9, 10),
+    ],
+    &[
+        (0, 5),
+        (1, 7),
+        (2, 9),
+        (3, 4),
+        (6, 13),
+        (8, 14),
+        (10, 15),
+        (11, 12),
+    ],
+    &[
+        (0, 1),
+        (2, 3),
+        (4, 5),
+        (6, 8),
+        (7, 9),
+        (10, 11),
+        (12, 13),
+        (14, 15),
+    ],
+    &[
+        (0, 2),
+        (1, 3),
+        (4, 10),
+        (5, 11),
+        (6, 7),
+        (8, 9),
+        (12, 14),
+        (13, 15),
+    ],
+    &[(1, 2), (3, 12), (4, 6), (5, 7), (8, 10), (9, 11), (13, 14)],
+    &[(1, 4), (2, 6), (5, 8), (7, 10), (9, 13), (11, 14)],
+    &[(2, 4), (3, 6), (9, 12), (11, 13)],
+    &[(3, 5), (6, 8), (7, 9), (10, 12)],
+    &[(3, 4), (5, 6), (7, 8), (9, 10), (11, 12)],
+    &[(6, 7), (8, 9)],
+];
+
+const NETWORKS: [Network; MAX_NETWORK_SIZE + 1] = [
+    &[],
+    &[],
+    NETWORK_2,
+    NETWORK_3,
+    NETWORK_4,
+    NETWORK_5,
+    NETWORK_6,
+    NETWORK_7,
+    NETWORK_8,
+    NETWORK_9,
+    NETWORK_10,
+    NETWORK_11,
+    NETWORK_12,
+    NETWORK_13,
+    NETWORK_14,
+    NETWORK_15,
+    NETWORK_16,
+];
+
+/// The network for `n` inputs. Panics if `n > MAX_NETWORK_SIZE`.
+pub fn sorting_network(n: usize) -> Network {
+    assert!(
+        n <= MAX_NETWORK_SIZE,
+        "no sorting network for {n} inputs, the largest is {MAX_NETWORK_SIZE}"
+    );
+    NETWORKS[n]
+}
+
+/// Sorts up to 16 elements with the network for their count. Panics on longer slices.
+pub fn network_sort<T: Copy + Ord>(input_list: &mut [T]) {
+    for layer in sorting_network(input_list.len()) {
+        for &(a, b) in *layer {
+            compare_exchange(input_list, a as usize, b as usize);
+        }
+    }
+}
+
+/// Bitonic sort, the length must be a power of two (or zero)
+pub fn bitonic_sort<T: Copy + Ord>(input_list: &mut [T]) {
+    let len = input_list.len();
+    assert!(
+        len == 0 || len.is_power_of_two(),
+        "bitonic sort needs a power of two length, got {len}"
+    );
+
+    // `block` is the size of the bitonic sequences being merged, `distance` the span of
+    // the comparators in the current merge step
+    let mut block = 2;
+    while block <= len {
+        let mut distance = block / 2;
+        while distance > 0 {
+            for i in 0..len {
+                let partner = i ^ distance;
+                if partner > i {
+                    // blocks alternate between ascending and descending
+                    if i & block == 0 {
+                        compare_exchange(input_list, i, partner);
+                    } else {
+                        compare_exchange(input_list, partner, i);
+                    }
+                }
+            }
+            distance /= 2;
+        }
+        block *= 2;
+    }
+}
+
+/// The comparators `bitonic_sort` runs for `len` inputs, one vector per layer. A
+/// comparator `(a, b)` puts the smaller value at `a`, so `a > b` for descending ones.
+pub fn bitonic_network(len: usize) -> Vec<Vec<(usize, usize)>> {
+    assert!(
+        len == 0 || len.is_power_of_two(),
+        "bitonic sort needs a power of two length, got {len}"
+    );
+
+    let mut layers = Vec::new();
+    let mut block = 2;
+    while block <= len {
+        let mut distance = block / 2;
+        while distance > 0 {
+            let layer = (0..len)
+                .filter(|&i| (i ^ distance) > i)
+                .map(|i| {
+                    let partner = i ^ distance;
+                    if i & block == 0 {
+                        (i, partner)
+                    } else {
+                        (partner, i)
+                    }
+                })
+                .collect();
+            layers.push(layer);
+            distance /= 2;
+        }
+        block *= 2;
+    }
+    layers
+}
+
+/// Puts the smaller of `input_list[a]` and `input_list[b]` at `a`. Written with min / max
+/// instead of a conditional swap so it compiles to conditional moves.
+#[inline]
+fn compare_exchange<T: Copy + Ord>(input_list: &mut [T], a: usize, b: usize) {
+    let (x, y) = (input_list[a], input_list[b]);
+    input_list[a] = x.min(y);
+    input_list[b] = x.max(y);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algoritms::sorting::test_utils::random_values;
+
+    /// 0-1 principle: a comparator network sorts every input iff it sorts every sequence
+    /// of zeros and ones, so checking all 2^n of those is a proof
+    fn sorts_all_zero_one_inputs(n: usize, comparators: &[(usize, usize)]) -> bool {
+        (0u32..1 << n).all(|bits| {
+            let mut values: Vec<u8> = (0..n).map(|i| (bits >> i & 1) as u8).collect();
+            for &(a, b) in comparators {
+                compare_exchange(&mut values, a, b);
+            }
+            values.windows(2).all(|w| w[0] <= w[1])
+        })
+    }
+
+    #[test]
+    fn test_networks_sort_every_zero_one_input() {
+        for n in 0..=MAX_NETWORK_SIZE {
+            let comparators: Vec<(usize, usize)> = sorting_network(n)
+                .iter()
+                .flat_map(|layer| layer.iter().map(|&(a, b)| (a as usize, b as usize)))
+                .collect();
+            assert!(sorts_all_zero_one_inputs(n, &comparators), "network {n}");
+        }
+    }
+
+    #[test]
+    fn test_network_sizes() {
+        // smallest known comparator counts
+        let sizes = [0, 0, 1, 3, 5, 9, 12, 16, 19, 25, 29, 35, 39, 45, 51, 56, 60];
+        for (n, &size) in sizes.iter().enumerate() {
+            let network = sorting_network(n);
+            let count: usize = network.iter().map(|layer| layer.len()).sum();
+            assert_eq!(count, size, "network {n}");
+
+            for layer in network {
+                let mut seen = vec![false; n];
+                for &(a, b) in *layer {
+                    assert!(a < b);
+                    assert!(!seen[a as usize] && !seen[b as usize], "network {n}");
+                    seen[a as usize] = true;
+                    seen[b as usize] = true;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_bitonic_network_sorts_every_zero_one_input() {
+        for n in [0, 1, 2, 4, 8, 16] {
+            let comparators: Vec<(usize, usize)> = bitonic_network(n).concat();
+            assert!(sorts_all_zero_one_inputs(n, &comparators), "bitonic {n}");
+        }
+        // log n (log n + 1) / 2 layers of n / 2 comparators
+        assert_eq!(bitonic_network(16).len(), 10);
+        assert_eq!(bitonic_network(16).concat().len(), 80);
+    }
+
+    #[test]
+    fn test_network_sort_matches_std() {
+        for n in 0..=MAX_NETWORK_SIZE {
+            for seed in 0..20 {
+                let mut list = random_values(n, 10, seed * 17 + n as u64);
+                let mut expected = list.clone();
+                expected.sort();
+                network_sort(&mut list);
+                assert_eq!(list, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_bitonic_sort_matches_std() {
+        for len in [0, 1, 2, 4, 64, 1024] {
+            let mut list = random_values(len, 1000, len as u64);
+            let mut expected = list.clone();
+            expected.sort();
+            bitonic_sort(&mut list);
+            assert_eq!(list, expected);
+        }
+
+        let mut words = ["pear", "fig", "apple", "kiwi"];
+        bitonic_sort(&mut words);
+        assert_eq!(words, ["apple", "fig", "kiwi", "pear"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "power of two")]
+    fn test_bitonic_sort_rejects_other_lengths() {
+        bitonic_sort(&mut [3, 2, 1]);
+    }
+
+    #[test]
+    #[should_panic(expected = "no sorting network for 17 inputs")]
+    fn test_network_sort_rejects_long_slices() {
+        network_sort(&mut [0; 17]);
+    }
+}
@@ -0,0 +1,312 @@
+// Sorts for string keys that look at one byte at a time instead of comparing whole
+// strings, so shared prefixes are only scanned once. Both sort by byte order, which for
+// UTF-8 is the same as sorting by code point.
+
+use super::instrument::{Probe, SortObserver};
+
+/// Below this many strings a bucket is finished off with insertion sort
+const INSERTION_CUTOFF: usize = 16;
+/// Nested buckets the radix sort recurses into before handing over to multikey quicksort,
+/// every level keeps four 257 entry tables on the stack
+const MAX_RADIX_LEVELS: usize = 32;
+
+/// MSD (most significant digit) radix sort, done in place as an American flag sort:
+/// count the buckets for the current byte, then swap every string into its bucket.
+pub fn msd_radix_sort<S: AsRef<[u8]>>(input_list: &mut [S]) {
+    msd_radix_sort_observed(input_list, &mut ());
+}
+
+/// Reading a string's byte is not reported, only the comparisons in the insertion sorted
+/// buckets are
+pub fn msd_radix_sort_observed<S: AsRef<[u8]>, O: SortObserver<S>>(
+    input_list: &mut [S],
+    observer: &mut O,
+) {
+    msd_radix_sort_helper(input_list, 0, 0, &mut Probe::new(observer));
+}
+
+fn msd_radix_sort_helper<S: AsRef<[u8]>, O: SortObserver<S>>(
+    input_list: &mut [S],
+    mut depth: usize,
+    levels: usize,
+    probe: &mut Probe<'_, S, O>,
+) {
+    if input_list.len() <= INSERTION_CUTOFF {
+        insertion_sort_from(input_list, depth, probe);
+        return;
+    }
+    if levels == MAX_RADIX_LEVELS {
+        multikey_quick_sort_helper(input_list, depth, probe);
+        return;
+    }
+
+    // bucket 0 holds the strings that end at this depth, byte b goes to bucket b + 1
+    let mut counts = [0usize; 257];
+    loop {
+        counts.fill(0);
+        for s in input_list.iter() {
+            counts[bucket(s, depth)] += 1;
+        }
+        // a shared byte splits nothing, move on to the next one without recursing
+        match counts.iter().position(|&count| count == input_list.len()) {
+            Some(0) => return,
+            Some(_) => depth += 1,
+            None => break,
+        }
+    }
+
+    let mut starts = [0usize; 257];
+    for b in 1..257 {
+        starts[b] = starts[b - 1] + counts[b - 1];
+    }
+    let mut ends = [0usize; 257];
+    for b in 0..257 {
+        ends[b] = starts[b] + counts[b];
+    }
+
+    // cycle every misplaced string to the next free slot of its bucket
+    let mut next = starts;
+    for b in 0..257 {
+        while next[b] < ends[b] {
+            let target = bucket(&input_list[next[b]], depth);
+            if target == b {
+                next[b] += 1;
+            } else {
+                probe.swap(input_list, next[b], next[target]);
+                next[target] += 1;
+            }
+        }
+    }
+
+    // strings in bucket 0 are all equal up to here, the rest continue with the next byte
+    for b in 1..257 {
+        if counts[b] > 1 {
+            msd_radix_sort_helper(
+                &mut input_list[starts[b]..ends[b]],
+                depth + 1,
+                levels + 1,
+                &mut probe.at(starts[b]),
+            );
+        }
+    }
+}
+
+/// Multikey quicksort (Bentley & Sedgewick): three-way quick sort on the byte at the
+/// current depth, only the "equal" part moves on to the next byte.
+pub fn multikey_quick_sort<S: AsRef<[u8]>>(input_list: &mut [S]) {
+    multikey_quick_sort_observed(input_list, &mut ());
+}
+
+/// Each byte compared against the pivot's byte is reported as one comparison
+pub fn multikey_quick_sort_observed<S: AsRef<[u8]>, O: SortObserver<S>>(
+    input_list: &mut [S],
+    observer: &mut O,
+) {
+    multikey_quick_sort_helper(input_list, 0, &mut Probe::new(observer));
+}
+
+fn multikey_quick_sort_helper<S: AsRef<[u8]>, O: SortObserver<S>>(
+    mut input_list: &mut [S],
+    mut depth: usize,
+    probe: &mut Probe<'_, S, O>,
+) {
+    let mut offset = 0;
+    loop {
+        let mut probe = probe.at(offset);
+        if input_list.len() <= INSERTION_CUTOFF {
+            insertion_sort_from(input_list, depth, &mut probe);
+            return;
+        }
+
+        let len = input_list.len();
+        probe.swap(input_list, 0, len / 2);
+        let pivot = byte_at(&input_list[0], depth);
+
+        // [..lt] < pivot, [lt..i] == pivot, [gt..] > pivot. input_list[lt] always holds a
+        // string equal to the pivot, comparisons are reported against it.
+        let (mut lt, mut i, mut gt) = (0, 1, len);
+        while i < gt {
+            let b = byte_at(&input_list[i], depth);
+            probe.compared(i, lt);
+            if b < pivot {
+                probe.swap(input_list, lt, i);
+                lt += 1;
+                i += 1;
+            } else if b > pivot {
+                gt -= 1;
+                probe.swap(input_list, i, gt);
+            } else {
+                i += 1;
+            }
+        }
+
+        let (less, rest) = std::mem::take(&mut input_list).split_at_mut(lt);
+        let (equal, greater) = rest.split_at_mut(gt - lt);
+        multikey_quick_sort_helper(less, depth, &mut probe);
+        multikey_quick_sort_helper(greater, depth, &mut probe.at(gt));
+
+        // strings that already ended are all equal, nothing left to sort
+        if pivot.is_none() {
+            return;
+        }
+        input_list = equal;
+        offset += lt;
+        depth += 1;
+    }
+}
+
+/// Byte at `depth`, `None` once the string has ended (sorts before every byte)
+fn byte_at<S: AsRef<[u8]>>(s: &S, depth: usize) -> Option<u8> {
+    s.as_ref().get(depth).copied()
+}
+
+fn bucket<S: AsRef<[u8]>>(s: &S, depth: usize) -> usize {
+    byte_at(s, depth).map_or(0, |b| b as usize + 1)
+}
+
+fn suffix<S: AsRef<[u8]>>(s: &S, depth: usize) -> &[u8] {
+    let bytes = s.as_ref();
+    &bytes[depth.min(bytes.len())..]
+}
+
+/// Insertion sort for strings that are known to share their first `depth` bytes
+fn insertion_sort_from<S: AsRef<[u8]>, O: SortObserver<S>>(
+    input_list: &mut [S],
+    depth: usize,
+    probe: &mut Probe<'_, S, O>,
+) {
+    for i in 1..input_list.len() {
+        let mut j = i;
+        while j > 0 && {
+            probe.compared(j, j - 1);
+            suffix(&input_list[j], depth) < suffix(&input_list[j - 1], depth)
+        } {
+            probe.swap(input_list, j, j - 1);
+            j -= 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algoritms::sorting::quick_sort::XorShift64;
+
+    type StringSort = fn(&mut [String]);
+
+    fn sorters() -> Vec<(&'static str, StringSort)> {
+        vec![
+            ("msd radix", msd_radix_sort),
+            ("multikey quick", multikey_quick_sort),
+        ]
+    }
+
+    fn random_words(count: usize, seed: u64) -> Vec<String> {
+        let mut rng = XorShift64::new(seed);
+        (0..count)
+            .map(|_| {
+                let len = rng.next_below(8);
+                // small alphabet so there are lots of shared prefixes and duplicates
+                (0..len)
+                    .map(|_| (b'a' + rng.next_below(4) as u8) as char)
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_string_sorts_basic() {
+        for (name, sort) in sorters() {
+            let mut list: Vec<String> = ["dog", "cat", "zebra", "ant", "bear", "", "ca", "cat"]
+                .iter()
+                .map(|s| s.to_string())
+                .collect();
+            sort(&mut list);
+            assert_eq!(
+                list,
+                ["", "ant", "bear", "ca", "cat", "cat", "dog", "zebra"],
+                "{name}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_string_sorts_empty() {
+        for (_, sort) in sorters() {
+            let mut list: Vec<String> = vec![];
+            sort(&mut list);
+            assert!(list.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_string_sorts_match_std() {
+        for (name, sort) in sorters() {
+            let mut list = random_words(3000, 77);
+            let mut expected = list.clone();
+            expected.sort();
+            sort(&mut list);
+            assert_eq!(list, expected, "{name}");
+        }
+    }
+
+    #[test]
+    fn test_string_sorts_unicode() {
+        for (name, sort) in sorters() {
+            let mut list: Vec<String> = ["ø", "a", "世界", "z", "é", "ab", "🦀"]
+                .iter()
+                .map(|s| s.to_string())
+                .collect();
+            let mut expected = list.clone();
+            expected.sort();
+            sort(&mut list);
+            assert_eq!(list, expected, "{name}");
+        }
+    }
+
+    #[test]
+    fn test_string_sorts_byte_slices() {
+        let mut list: Vec<&[u8]> = vec![b"\xff", b"\x00\x01", b"\x00", b""];
+        msd_radix_sort(&mut list);
+        assert_eq!(list, [b"" as &[u8], b"\x00", b"\x00\x01", b"\xff"]);
+
+        let mut list: Vec<&str> = vec!["b", "a", "ab"];
+        multikey_quick_sort(&mut list);
+        assert_eq!(list, ["a", "ab", "b"]);
+    }
+
+    #[test]
+    fn test_long_shared_prefixes() {
+        let prefix = "x".repeat(200);
+        for (name, sort) in sorters() {
+            let mut list: Vec<String> = (0..100).rev().map(|i| format!("{prefix}{i:03}")).collect();
+            sort(&mut list);
+            let expected: Vec<String> = (0..100).map(|i| format!("{prefix}{i:03}")).collect();
+            assert_eq!(list, expected, "{name}");
+        }
+    }
+
+    #[test]
+    fn test_long_equal_strings() {
+        // one byte of depth per shared byte used to mean one stack frame per byte
+        for (name, sort) in sorters() {
+            let mut list = vec!["q".repeat(5000); 200];
+            list.push("p".repeat(4999));
+            sort(&mut list);
+            assert_eq!(list[0], "p".repeat(4999), "{name}");
+            assert!(list[1..].iter().all(|s| *s == "q".repeat(5000)), "{name}");
+        }
+    }
+
+    #[test]
+    fn test_nested_prefixes() {
+        // every byte splits off one string, so the buckets nest as deep as the strings go
+        for (name, sort) in sorters() {
+            let mut list: Vec<String> = (0..3000).map(|i| format!("{}b", "a".repeat(i))).collect();
+            let mut expected = list.clone();
+            expected.sort();
+            sort(&mut list);
+            assert_eq!(list, expected, "{name}");
+        }
+    }
+}
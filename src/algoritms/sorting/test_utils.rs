@@ -0,0 +1,217 @@
+// shared helpers for the sorting tests: input generators, comparison counting and a
+// quicksort adversary
+
+use std::cell::{Cell, RefCell};
+use std::cmp::Ordering;
+use std::rc::Rc;
+
+use super::quick_sort::XorShift64;
+
+/// Record that only compares on `key`, so stability can be checked through `payload`
+#[derive(Debug, Clone)]
+pub struct Record<P> {
+    pub key: i32,
+    pub payload: P,
+}
+
+impl<P> Record<P> {
+    pub fn new(key: i32, payload: P) -> Self {
+        Self { key, payload }
+    }
+}
+
+impl<P> PartialEq for Record<P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
+    }
+}
+
+impl<P> Eq for Record<P> {}
+
+impl<P> PartialOrd for Record<P> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<P> Ord for Record<P> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key.cmp(&other.key)
+    }
+}
+
+/// Value that bumps a shared counter every time it is compared
+#[derive(Debug, Clone)]
+pub struct Counted {
+    pub value: i64,
+    pub counter: Rc<Cell<usize>>,
+}
+
+impl PartialEq for Counted {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Counted {}
+
+impl PartialOrd for Counted {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Counted {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.counter.set(self.counter.get() + 1);
+        self.value.cmp(&other.value)
+    }
+}
+
+/// Wraps `values` in [`Counted`], returns them with the shared counter
+pub fn counted(values: &[i64]) -> (Vec<Counted>, Rc<Cell<usize>>) {
+    let counter = Rc::new(Cell::new(0));
+    let list = values
+        .iter()
+        .map(|&value| Counted {
+            value,
+            counter: counter.clone(),
+        })
+        .collect();
+    (list, counter)
+}
+
+/// Runs `sort` on counted copies of `values` and returns how many comparisons it made
+pub fn count_comparisons(values: &[i64], sort: impl Fn(&mut [Counted])) -> usize {
+    let (mut list, counter) = counted(values);
+    sort(&mut list);
+    assert!(list.windows(2).all(|w| w[0].value <= w[1].value));
+    counter.get()
+}
+
+pub fn random_values(len: usize, max: u64, seed: u64) -> Vec<i64> {
+    let mut rng = XorShift64::new(seed);
+    (0..len).map(|_| (rng.next_u64() % max) as i64).collect()
+}
+
+/// 0, 1, .., n/2, .., 1, 0
+pub fn organ_pipe(len: usize) -> Vec<i64> {
+    (0..len).map(|i| i.min(len - 1 - i) as i64).collect()
+}
+
+pub fn sawtooth(len: usize, teeth: usize) -> Vec<i64> {
+    (0..len).map(|i| (i % teeth) as i64).collect()
+}
+
+/// Musser's median-of-3 killer: every median-of-three pivot ends up being the second
+/// smallest element, so partitions only shrink by two. `len` must be even.
+pub fn median_of_three_killer(len: usize) -> Vec<i64> {
+    let k = len / 2;
+    let mut result = vec![0; len];
+    for i in 1..=k {
+        if i % 2 == 1 {
+            result[i - 1] = i as i64;
+            result[i] = (k + i) as i64;
+        }
+        result[k + i - 1] = (2 * i) as i64;
+    }
+    result
+}
+
+// McIlroy's "A Killer Adversary for Quicksort". Values start out as "gas" and are only
+// frozen to a concrete value when the sort forces it, the pivot candidate always gets
+// frozen last so the sort keeps picking bad pivots.
+struct Adversary {
+    values: Vec<usize>,
+    gas: usize,
+    solid: usize,
+    candidate: usize,
+    comparisons: usize,
+}
+
+impl Adversary {
+    fn freeze(&mut self, id: usize) {
+        self.values[id] = self.solid;
+        self.solid += 1;
+    }
+
+    fn compare(&mut self, x: usize, y: usize) -> Ordering {
+        self.comparisons += 1;
+        if self.values[x] == self.gas && self.values[y] == self.gas {
+            if x == self.candidate {
+                self.freeze(x);
+            } else {
+                self.freeze(y);
+            }
+        }
+        if self.values[x] == self.gas {
+            self.candidate = x;
+        } else if self.values[y] == self.gas {
+            self.candidate = y;
+        }
+        self.values[x].cmp(&self.values[y])
+    }
+}
+
+#[derive(Clone)]
+pub struct AdversaryItem {
+    id: usize,
+    state: Rc<RefCell<Adversary>>,
+}
+
+impl PartialEq for AdversaryItem {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for AdversaryItem {}
+
+impl PartialOrd for AdversaryItem {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for AdversaryItem {
+    fn cmp(&self, other: &Self) -> Ordering {
+        if self.id == other.id {
+            return Ordering::Equal;
+        }
+        self.state.borrow_mut().compare(self.id, other.id)
+    }
+}
+
+/// Plays the adversary against `sort` and returns (comparisons made, the input that
+/// triggers the same behaviour when fed to the sort as plain numbers)
+pub fn antiqsort(len: usize, sort: impl Fn(&mut [AdversaryItem])) -> (usize, Vec<i64>) {
+    let state = Rc::new(RefCell::new(Adversary {
+        values: vec![len; len],
+        gas: len,
+        solid: 0,
+        candidate: 0,
+        comparisons: 0,
+    }));
+    let mut list: Vec<AdversaryItem> = (0..len)
+        .map(|id| AdversaryItem {
+            id,
+            state: state.clone(),
+        })
+        .collect();
+
+    sort(&mut list);
+
+    let mut state = state.borrow_mut();
+    for id in 0..len {
+        if state.values[id] == state.gas {
+            state.freeze(id);
+        }
+    }
+    let killer = state.values.iter().map(|&v| v as i64).collect();
+    (state.comparisons, killer)
+}
+
+/// Rough n * log2(n) budget used to tell O(n log n) apart from quadratic behaviour
+pub fn n_log_n(len: usize) -> usize {
+    len * (usize::BITS - len.leading_zeros()) as usize
+}
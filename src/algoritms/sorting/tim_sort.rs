@@ -0,0 +1,488 @@
+// Timsort (Tim Peters, CPython / Java). Stable merge sort that works on the runs already
+// present in the input:
+// - ascending runs are used as is, strictly descending runs are reversed
+// - short runs are extended to `min_run` with binary insertion sort
+// - runs are kept on a stack and merged so their lengths stay roughly balanced
+// - merges switch to galloping (exponential search) when one run keeps winning
+
+use super::instrument::{Probe, SortObserver};
+
+const MIN_MERGE: usize = 32;
+const MIN_GALLOP: usize = 7;
+
+#[derive(Debug, Clone, Copy)]
+struct Run {
+    start: usize,
+    len: usize,
+}
+
+struct MergeState<T> {
+    min_gallop: usize,
+    buffer: Vec<T>,
+}
+
+pub fn tim_sort<T: Clone + Ord>(input_list: &mut [T]) {
+    tim_sort_observed(input_list, &mut ());
+}
+
+pub fn tim_sort_observed<T: Clone + Ord, O: SortObserver<T>>(
+    input_list: &mut [T],
+    observer: &mut O,
+) {
+    let len = input_list.len();
+    if len < 2 {
+        return;
+    }
+
+    let mut probe = Probe::new(observer);
+    let min_run = min_run_length(len);
+    let mut runs: Vec<Run> = Vec::new();
+    let mut state = MergeState {
+        min_gallop: MIN_GALLOP,
+        buffer: Vec::new(),
+    };
+
+    let mut start = 0;
+    while start < len {
+        let mut run_probe = probe.at(start);
+        let mut run_len = count_run_and_make_ascending(&mut input_list[start..], &mut run_probe);
+
+        if run_len < min_run {
+            let forced = min_run.min(len - start);
+            let run = &mut input_list[start..start + forced];
+            binary_insertion_sort(run, run_len, &mut run_probe);
+            run_len = forced;
+        }
+
+        runs.push(Run {
+            start,
+            len: run_len,
+        });
+        merge_collapse(input_list, &mut runs, &mut state, &mut probe);
+        start += run_len;
+    }
+
+    // merge whatever is left on the stack
+    while runs.len() > 1 {
+        let n = runs.len();
+        let at = if n >= 3 && runs[n - 3].len < runs[n - 1].len {
+            n - 3
+        } else {
+            n - 2
+        };
+        merge_at(input_list, &mut runs, at, &mut state, &mut probe);
+    }
+}
+
+/// Picks a run length in `MIN_MERGE/2..=MIN_MERGE` so that `len / min_run` is a power of
+/// two or slightly less, which keeps the final merges balanced.
+fn min_run_length(mut len: usize) -> usize {
+    let mut extra = 0;
+    while len >= MIN_MERGE {
+        extra |= len & 1;
+        len >>= 1;
+    }
+    len + extra
+}
+
+/// Length of the run at the start of `input_list`. Strictly descending runs are reversed
+/// (strictly, so equal elements never swap order).
+fn count_run_and_make_ascending<T: Ord, O: SortObserver<T>>(
+    input_list: &mut [T],
+    probe: &mut Probe<'_, T, O>,
+) -> usize {
+    let len = input_list.len();
+    if len < 2 {
+        return len;
+    }
+
+    let mut end = 2;
+    if probe.less(input_list, 1, 0) {
+        while end < len && probe.less(input_list, end, end - 1) {
+            end += 1;
+        }
+        for i in 0..end / 2 {
+            probe.swap(input_list, i, end - 1 - i);
+        }
+    } else {
+        while end < len && !probe.less(input_list, end, end - 1) {
+            end += 1;
+        }
+    }
+    end
+}
+
+/// Insertion sort using binary search for the insert position, `input_list[..sorted]` is
+/// already sorted. Inserts after equal elements to stay stable.
+fn binary_insertion_sort<T: Ord, O: SortObserver<T>>(
+    input_list: &mut [T],
+    sorted: usize,
+    probe: &mut Probe<'_, T, O>,
+) {
+    for i in sorted.max(1)..input_list.len() {
+        let (sorted_part, rest) = input_list.split_at(i);
+        let position = gallop_right(&rest[0], i, sorted_part, 0, probe);
+        if position == i {
+            continue;
+        }
+
+        input_list[position..=i].rotate_right(1);
+        for (k, value) in input_list.iter().enumerate().take(i + 1).skip(position) {
+            probe.wrote(k, value);
+        }
+    }
+}
+
+/// Restores the stack invariants (with the 2015 fix that also checks four runs deep):
+/// run[n-3] > run[n-2] + run[n-1] and run[n-2] > run[n-1]
+fn merge_collapse<T: Clone + Ord, O: SortObserver<T>>(
+    input_list: &mut [T],
+    runs: &mut Vec<Run>,
+    state: &mut MergeState<T>,
+    probe: &mut Probe<'_, T, O>,
+) {
+    while runs.len() > 1 {
+        let n = runs.len();
+        let mut at = n - 2;
+
+        if (n >= 3 && runs[n - 3].len <= runs[n - 2].len + runs[n - 1].len)
+            || (n >= 4 && runs[n - 4].len <= runs[n - 3].len + runs[n - 2].len)
+        {
+            if runs[n - 3].len < runs[n - 1].len {
+                at = n - 3;
+            }
+        } else if runs[n - 2].len > runs[n - 1].len {
+            break;
+        }
+
+        merge_at(input_list, runs, at, state, probe);
+    }
+}
+
+/// Merges runs `at` and `at + 1` on the stack
+fn merge_at<T: Clone + Ord, O: SortObserver<T>>(
+    input_list: &mut [T],
+    runs: &mut Vec<Run>,
+    at: usize,
+    state: &mut MergeState<T>,
+    probe: &mut Probe<'_, T, O>,
+) {
+    let left = runs[at];
+    let right = runs.remove(at + 1);
+    runs[at].len += right.len;
+
+    let run = &mut input_list[left.start..right.start + right.len];
+    let mut probe = probe.at(left.start);
+    probe.merging(left.len, run.len());
+    merge_runs(run, left.len, state, &mut probe);
+}
+
+fn merge_runs<T: Clone + Ord, O: SortObserver<T>>(
+    input_list: &mut [T],
+    mid: usize,
+    state: &mut MergeState<T>,
+    probe: &mut Probe<'_, T, O>,
+) {
+    // left elements <= the first right element are already in place
+    let (left, right) = input_list.split_at(mid);
+    let skip = gallop_right(&right[0], mid, left, 0, probe);
+    let input_list = &mut input_list[skip..];
+    let mut probe = probe.at(skip);
+    let mid = mid - skip;
+    if mid == 0 {
+        return;
+    }
+
+    // same for right elements >= the last left element
+    let (left, right) = input_list.split_at(mid);
+    let keep = gallop_left(&left[mid - 1], mid - 1, right, mid, &mut probe);
+    if keep == 0 {
+        return;
+    }
+
+    merge_lo(&mut input_list[..mid + keep], mid, state, &mut probe);
+}
+
+/// Merge with the left run copied to the buffer, switching to galloping mode whenever one
+/// side wins `min_gallop` times in a row. Buffered elements are reported at the position
+/// they were copied from.
+fn merge_lo<T: Clone + Ord, O: SortObserver<T>>(
+    input_list: &mut [T],
+    mid: usize,
+    state: &mut MergeState<T>,
+    probe: &mut Probe<'_, T, O>,
+) {
+    let capacity = state.buffer.capacity();
+    state.buffer.clear();
+    state.buffer.extend_from_slice(&input_list[..mid]);
+    if state.buffer.capacity() > capacity {
+        probe.alloc(state.buffer.capacity());
+    }
+    let buffer = &state.buffer;
+
+    let len = input_list.len();
+    let (mut i, mut j, mut k) = (0, mid, 0);
+    let mut min_gallop = state.min_gallop;
+
+    'merge: while i < mid && j < len {
+        let (mut left_wins, mut right_wins) = (0, 0);
+
+        // one element at a time until a side starts winning consistently
+        loop {
+            if probe.less_values(&input_list[j], j, &buffer[i], i) {
+                input_list[k] = input_list[j].clone();
+                probe.wrote(k, &input_list[k]);
+                j += 1;
+                right_wins += 1;
+                left_wins = 0;
+            } else {
+                probe.write(input_list, k, &buffer[i]);
+                i += 1;
+                left_wins += 1;
+                right_wins = 0;
+            }
+            k += 1;
+
+            if i == mid || j == len {
+                break 'merge;
+            }
+            if left_wins.max(right_wins) >= min_gallop {
+                break;
+            }
+        }
+
+        // galloping: find how far each side can be copied in one go
+        loop {
+            let from_left = gallop_right(&input_list[j], j, &buffer[i..], i, probe);
+            for value in &buffer[i..i + from_left] {
+                probe.write(input_list, k, value);
+                k += 1;
+            }
+            i += from_left;
+            if i == mid {
+                break 'merge;
+            }
+
+            let from_right = gallop_left(&buffer[i], i, &input_list[j..], j, probe);
+            for _ in 0..from_right {
+                input_list[k] = input_list[j].clone();
+                probe.wrote(k, &input_list[k]);
+                j += 1;
+                k += 1;
+            }
+            if j == len {
+                break 'merge;
+            }
+
+            // galloping is paying off, make it easier to enter next time
+            min_gallop = min_gallop.saturating_sub(1);
+            if from_left < MIN_GALLOP && from_right < MIN_GALLOP {
+                break;
+            }
+        }
+        // and harder after leaving it
+        min_gallop += 2;
+    }
+
+    // right run exhausted: the rest of the buffer goes at the end. If the left run ran
+    // out instead the remaining right elements are already where they belong.
+    for value in &buffer[i..] {
+        probe.write(input_list, k, value);
+        k += 1;
+    }
+    state.min_gallop = min_gallop.max(1);
+}
+
+/// Number of elements in the sorted `run` that are `<= key` (upper bound), found with
+/// exponential search from the front followed by binary search. `key_index` and
+/// `run_start` are the positions reported to the probe.
+fn gallop_right<T: Ord, O: SortObserver<T>>(
+    key: &T,
+    key_index: usize,
+    run: &[T],
+    run_start: usize,
+    probe: &mut Probe<'_, T, O>,
+) -> usize {
+    gallop(run.len(), |x| {
+        !probe.less_values(key, key_index, &run[x], run_start + x)
+    })
+}
+
+/// Number of elements in the sorted `run` that are `< key` (lower bound)
+fn gallop_left<T: Ord, O: SortObserver<T>>(
+    key: &T,
+    key_index: usize,
+    run: &[T],
+    run_start: usize,
+    probe: &mut Probe<'_, T, O>,
+) -> usize {
+    gallop(run.len(), |x| {
+        probe.less_values(&run[x], run_start + x, key, key_index)
+    })
+}
+
+/// Length of the prefix of a run of `len` elements for which `is_before(index)` holds,
+/// `is_before` must be true for a prefix and false afterwards
+fn gallop(len: usize, mut is_before: impl FnMut(usize) -> bool) -> usize {
+    let mut low = 0;
+    let mut high = 1;
+
+    // 1, 3, 7, 15 .. until we overshoot
+    while high <= len && is_before(high - 1) {
+        low = high;
+        high = high * 2 + 1;
+    }
+    let mut high = high.min(len + 1) - 1;
+
+    // answer is in low..=high
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if is_before(mid) {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+    low
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algoritms::sorting::merge_sort::merge_sort;
+    use crate::algoritms::sorting::test_utils::{
+        Record, count_comparisons, organ_pipe, random_values, sawtooth,
+    };
+
+    fn check_sorts(values: &[i64]) {
+        let mut expected = values.to_vec();
+        expected.sort();
+        let mut list = values.to_vec();
+        tim_sort(&mut list);
+        assert_eq!(list, expected);
+    }
+
+    #[test]
+    fn test_tim_sort_basic() {
+        let mut list = vec![3, 1, 4, 1, 5, 9, 2, 6];
+        tim_sort(&mut list);
+        assert_eq!(list, [1, 1, 2, 3, 4, 5, 6, 9]);
+    }
+
+    #[test]
+    fn test_tim_sort_empty_and_single() {
+        let mut empty: Vec<i32> = vec![];
+        tim_sort(&mut empty);
+        assert!(empty.is_empty());
+
+        let mut single = vec![7];
+        tim_sort(&mut single);
+        assert_eq!(single, [7]);
+    }
+
+    #[test]
+    fn test_tim_sort_patterns() {
+        for len in [2, 31, 32, 33, 64, 100, 1000, 5000] {
+            check_sorts(&random_values(len, 1_000_000, len as u64));
+            check_sorts(&random_values(len, 5, len as u64 + 1));
+            check_sorts(&organ_pipe(len));
+            check_sorts(&sawtooth(len, 50));
+            check_sorts(&(0..len as i64).rev().collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn test_tim_sort_strings() {
+        let mut list: Vec<String> = ["dog", "cat", "zebra", "ant", "bear"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        tim_sort(&mut list);
+        assert_eq!(list, ["ant", "bear", "cat", "dog", "zebra"]);
+    }
+
+    #[test]
+    fn test_tim_sort_is_stable() {
+        // runs of equal keys in both directions to exercise run reversal and galloping
+        let mut keys = random_values(3000, 8, 17);
+        keys.extend((0..500).map(|i| 7 - i / 100));
+        keys.extend(sawtooth(1000, 4));
+
+        let mut list: Vec<Record<usize>> = keys
+            .iter()
+            .enumerate()
+            .map(|(i, &k)| Record::new(k as i32, i))
+            .collect();
+        tim_sort(&mut list);
+
+        assert!(list.windows(2).all(|w| {
+            w[0].key < w[1].key || (w[0].key == w[1].key && w[0].payload < w[1].payload)
+        }));
+    }
+
+    #[test]
+    fn test_sorted_input_is_single_pass() {
+        let len = 10_000;
+        let sorted: Vec<i64> = (0..len as i64).collect();
+        let reversed: Vec<i64> = sorted.iter().rev().copied().collect();
+
+        assert_eq!(count_comparisons(&sorted, tim_sort), len - 1);
+        assert_eq!(count_comparisons(&reversed, tim_sort), len - 1);
+    }
+
+    #[test]
+    fn test_galloping_beats_plain_merge() {
+        // two runs where the whole right run belongs before the left run
+        let len = 4000;
+        let mut list: Vec<i64> = (2000..4000).collect();
+        list.extend(0..2000);
+
+        let tim = count_comparisons(&list, tim_sort);
+        let merge = count_comparisons(&list, merge_sort);
+        assert!(tim < len + 100, "tim sort made {tim} comparisons");
+        assert!(tim < merge);
+    }
+
+    #[test]
+    fn test_min_run_length() {
+        assert_eq!(min_run_length(31), 31);
+        assert_eq!(min_run_length(32), 16);
+        assert_eq!(min_run_length(33), 17);
+        assert_eq!(min_run_length(65), 17);
+        assert_eq!(min_run_length(2048), 16);
+        for len in 32..5000 {
+            let min_run = min_run_length(len);
+            assert!((16..=32).contains(&min_run));
+        }
+    }
+
+    #[test]
+    fn test_count_run() {
+        let mut silent = ();
+        let mut probe = Probe::new(&mut silent);
+        let mut ascending = vec![1, 2, 2, 3, 1];
+        assert_eq!(count_run_and_make_ascending(&mut ascending, &mut probe), 4);
+
+        let mut descending = vec![5, 4, 3, 3, 1];
+        assert_eq!(count_run_and_make_ascending(&mut descending, &mut probe), 3);
+        assert_eq!(descending, [3, 4, 5, 3, 1]);
+    }
+
+    #[test]
+    fn test_gallop() {
+        let mut silent = ();
+        let mut probe = Probe::new(&mut silent);
+        let mut left = |key: i32, run: &[i32]| gallop_left(&key, 0, run, 0, &mut probe);
+        let run = [1, 2, 2, 2, 5, 8, 8, 9];
+        assert_eq!(left(2, &run), 1);
+        assert_eq!(left(0, &run), 0);
+        assert_eq!(left(1, &[]), 0);
+
+        let mut silent = ();
+        let mut probe = Probe::new(&mut silent);
+        let mut right = |key: i32, run: &[i32]| gallop_right(&key, 0, run, 0, &mut probe);
+        assert_eq!(right(2, &run), 4);
+        assert_eq!(right(9, &run), 8);
+        assert_eq!(right(7, &run), 5);
+    }
+}
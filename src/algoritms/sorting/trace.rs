@@ -0,0 +1,307 @@
+// Step-by-step traces of the sorters, for watching them work. `trace` records every
+// event of a sort run, `Tracer` hands them to a callback as they happen, and `BarChart`
+// replays them as ASCII bar-chart frames.
+
+use std::fmt::{self, Display};
+use std::io::{self, Write};
+use std::thread;
+use std::time::Duration;
+
+use super::instrument::{SortObserver, Sorter};
+
+/// One step of a sort, indices are positions in the slice being sorted
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TraceEvent<T> {
+    Compare(usize, usize),
+    Swap(usize, usize),
+    Write {
+        index: usize,
+        value: T,
+    },
+    Alloc(usize),
+    Partition {
+        start: usize,
+        pivot: usize,
+        end: usize,
+    },
+    Merge {
+        start: usize,
+        mid: usize,
+        end: usize,
+    },
+}
+
+impl<T> Display for TraceEvent<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TraceEvent::Compare(i, j) => write!(f, "compare {i} and {j}"),
+            TraceEvent::Swap(i, j) => write!(f, "swap {i} and {j}"),
+            TraceEvent::Write { index, .. } => write!(f, "write {index}"),
+            TraceEvent::Alloc(elements) => write!(f, "allocate {elements}"),
+            TraceEvent::Partition { start, pivot, end } => {
+                write!(f, "partition {start}..{end} around {pivot}")
+            }
+            TraceEvent::Merge { start, mid, end } => {
+                write!(f, "merge {start}..{mid} with {mid}..{end}")
+            }
+        }
+    }
+}
+
+/// Observer that passes every event on to a callback
+pub struct Tracer<F> {
+    callback: F,
+}
+
+impl<F> Tracer<F> {
+    pub fn new(callback: F) -> Self {
+        Self { callback }
+    }
+}
+
+impl<T: Clone, F: FnMut(TraceEvent<T>)> SortObserver<T> for Tracer<F> {
+    fn on_compare(&mut self, i: usize, j: usize) {
+        (self.callback)(TraceEvent::Compare(i, j));
+    }
+
+    fn on_swap(&mut self, i: usize, j: usize) {
+        (self.callback)(TraceEvent::Swap(i, j));
+    }
+
+    fn on_write(&mut self, index: usize, value: &T) {
+        (self.callback)(TraceEvent::Write {
+            index,
+            value: value.clone(),
+        });
+    }
+
+    fn on_alloc(&mut self, elements: usize) {
+        (self.callback)(TraceEvent::Alloc(elements));
+    }
+
+    fn on_partition(&mut self, start: usize, pivot: usize, end: usize) {
+        (self.callback)(TraceEvent::Partition { start, pivot, end });
+    }
+
+    fn on_merge(&mut self, start: usize, mid: usize, end: usize) {
+        (self.callback)(TraceEvent::Merge { start, mid, end });
+    }
+}
+
+/// Sorts a copy of `input_list` with `sorter` and returns every event in order
+pub fn trace<T: Clone + Ord>(sorter: Sorter, input_list: &[T]) -> Vec<TraceEvent<T>> {
+    let mut events = Vec::new();
+    sorter.sort_observed(input_list, &mut Tracer::new(|event| events.push(event)));
+    events
+}
+
+/// Replays a trace on a copy of the input and draws it as vertical bars, one column per
+/// element. Bar heights come from the value's rank, so any `Ord` type can be drawn.
+pub struct BarChart<T> {
+    values: Vec<T>,
+    sorted: Vec<T>,
+    height: usize,
+    highlight: Vec<usize>,
+    /// start, marked position, end and the marker for it
+    range: Option<(usize, usize, usize, char)>,
+    caption: String,
+}
+
+impl<T: Clone + Ord> BarChart<T> {
+    pub fn new(input_list: &[T], height: usize) -> Self {
+        let mut sorted = input_list.to_vec();
+        sorted.sort();
+        Self {
+            values: input_list.to_vec(),
+            sorted,
+            height: height.max(1),
+            highlight: Vec::new(),
+            range: None,
+            caption: String::new(),
+        }
+    }
+
+    /// The values as they are after the events applied so far
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+
+    /// Moves to the state after `event`
+    pub fn apply(&mut self, event: &TraceEvent<T>) {
+        self.highlight.clear();
+        match *event {
+            TraceEvent::Compare(i, j) => self.highlight.extend([i, j]),
+            TraceEvent::Swap(i, j) => {
+                self.values.swap(i, j);
+                self.highlight.extend([i, j]);
+            }
+            TraceEvent::Write { index, ref value } => {
+                self.values[index] = value.clone();
+                self.highlight.push(index);
+            }
+            TraceEvent::Alloc(_) => {}
+            TraceEvent::Partition { start, pivot, end } => {
+                self.range = Some((start, pivot, end, '^'));
+            }
+            TraceEvent::Merge { start, mid, end } => self.range = Some((start, mid, end, '|')),
+        }
+        self.caption = event.to_string();
+    }
+
+    fn bar_height(&self, value: &T) -> usize {
+        let rank = self.sorted.partition_point(|x| x < value) + 1;
+        (rank * self.height).div_ceil(self.sorted.len())
+    }
+
+    /// The current frame: the bars (`#`, or `*` for the elements the last event touched),
+    /// a marker line under the active partition (`^` is the pivot) or merge (`|` is where
+    /// the right run starts), and a caption.
+    pub fn render(&self) -> String {
+        let heights: Vec<usize> = self.values.iter().map(|v| self.bar_height(v)).collect();
+        let mut frame = String::new();
+
+        for row in (1..=self.height).rev() {
+            let line: String = heights
+                .iter()
+                .enumerate()
+                .map(|(i, &h)| match (h >= row, self.highlight.contains(&i)) {
+                    (false, _) => ' ',
+                    (true, false) => '#',
+                    (true, true) => '*',
+                })
+                .collect();
+            frame.push_str(line.trim_end());
+            frame.push('\n');
+        }
+
+        let markers: String = (0..self.values.len())
+            .map(|i| match self.range {
+                Some((_, mark, _, marker)) if i == mark => marker,
+                Some((start, _, end, _)) if (start..end).contains(&i) => '-',
+                _ => ' ',
+            })
+            .collect();
+        frame.push_str(markers.trim_end());
+        frame.push('\n');
+        frame.push_str(&self.caption);
+        frame.push('\n');
+        frame
+    }
+}
+
+/// Plays `sorter` on `input_list` in the terminal, one frame per event with `delay`
+/// between them. Comparisons and allocations are skipped unless `show_compares` is set,
+/// they make up most of the trace without changing the picture.
+pub fn animate<T: Clone + Ord, W: Write>(
+    sorter: Sorter,
+    input_list: &[T],
+    height: usize,
+    delay: Duration,
+    show_compares: bool,
+    out: &mut W,
+) -> io::Result<()> {
+    let mut chart = BarChart::new(input_list, height);
+    for event in trace(sorter, input_list) {
+        if !show_compares && matches!(event, TraceEvent::Compare(..) | TraceEvent::Alloc(_)) {
+            continue;
+        }
+        chart.apply(&event);
+        // clear the screen and move the cursor home before each frame
+        write!(out, "\x1b[2J\x1b[H{}\n{}", sorter.name(), chart.render())?;
+        out.flush()?;
+        thread::sleep(delay);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algoritms::sorting::quick_sort::{PartitionScheme, PivotStrategy};
+    use crate::algoritms::sorting::test_utils::random_values;
+
+    #[test]
+    fn test_replaying_the_trace_sorts_the_input() {
+        let values = random_values(200, 50, 21);
+        let mut expected = values.clone();
+        expected.sort();
+
+        let lomuto = Sorter::QuickInPlace(PivotStrategy::Random, PartitionScheme::Lomuto);
+        for sorter in Sorter::ALL.into_iter().chain([lomuto]) {
+            let mut chart = BarChart::new(&values, 10);
+            for event in trace(sorter, &values) {
+                chart.apply(&event);
+            }
+            assert_eq!(chart.values(), expected, "{}", sorter.name());
+        }
+    }
+
+    #[test]
+    fn test_trace_events() {
+        let events = trace(Sorter::Insertion, &[2, 1]);
+        assert_eq!(events, [TraceEvent::Compare(1, 0), TraceEvent::Swap(1, 0)]);
+
+        let events = trace(Sorter::Merge, &[3, 1, 2]);
+        assert!(events.contains(&TraceEvent::Merge {
+            start: 0,
+            mid: 1,
+            end: 3
+        }));
+
+        let events = trace(Sorter::Intro, &random_values(100, 1000, 4));
+        assert!(
+            events
+                .iter()
+                .any(|e| matches!(e, TraceEvent::Partition { .. }))
+        );
+    }
+
+    #[test]
+    fn test_tracer_callback() {
+        let mut swaps = 0;
+        let values = [4, 3, 2, 1];
+        Sorter::Bubble.sort_observed(
+            &values,
+            &mut Tracer::new(|event: TraceEvent<i32>| {
+                if let TraceEvent::Swap(..) = event {
+                    swaps += 1;
+                }
+            }),
+        );
+        assert_eq!(swaps, 6);
+    }
+
+    #[test]
+    fn test_render_frame() {
+        let mut chart = BarChart::new(&[3, 1, 2], 3);
+        assert_eq!(chart.render(), "#\n# #\n###\n\n\n");
+
+        chart.apply(&TraceEvent::Swap(0, 1));
+        chart.apply(&TraceEvent::Partition {
+            start: 0,
+            pivot: 1,
+            end: 3,
+        });
+        chart.apply(&TraceEvent::Compare(1, 2));
+        assert_eq!(chart.render(), " *\n **\n#**\n-^-\ncompare 1 and 2\n");
+    }
+
+    #[test]
+    fn test_animate_writes_frames() {
+        let mut out = Vec::new();
+        animate(
+            Sorter::Insertion,
+            &[2, 1],
+            2,
+            Duration::ZERO,
+            false,
+            &mut out,
+        )
+        .unwrap();
+        let out = String::from_utf8(out).unwrap();
+        // only the swap makes a frame
+        assert_eq!(out.matches("\x1b[2J").count(), 1);
+        assert!(out.contains("insertion sort\n"));
+        assert!(out.contains("swap 1 and 0"));
+    }
+}
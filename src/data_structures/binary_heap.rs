@@ -0,0 +1,349 @@
+// Binary (or d-ary) heap stored in a Vec, either min or max ordered.
+// The sift functions work on plain slices so heap sort can reuse them in place.
+
+use crate::instrument::{Probe, SortObserver};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeapKind {
+    Min,
+    Max,
+}
+
+impl HeapKind {
+    /// true if `a` belongs closer to the root than `b`
+    fn before<T: Ord>(self, a: &T, b: &T) -> bool {
+        match self {
+            HeapKind::Min => a < b,
+            HeapKind::Max => a > b,
+        }
+    }
+
+    /// [`HeapKind::before`] for two positions in a slice, reported to the probe
+    fn before_at<T: Ord, O: SortObserver<T>>(
+        self,
+        data: &[T],
+        a: usize,
+        b: usize,
+        probe: &mut Probe<'_, T, O>,
+    ) -> bool {
+        match self {
+            HeapKind::Min => probe.less(data, a, b),
+            HeapKind::Max => probe.less(data, b, a),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MoHeap<T> {
+    data: Vec<T>,
+    kind: HeapKind,
+    arity: usize,
+}
+
+impl<T: Ord> MoHeap<T> {
+    pub fn new(kind: HeapKind) -> Self {
+        Self::with_arity(kind, 2)
+    }
+
+    pub fn new_min() -> Self {
+        Self::new(HeapKind::Min)
+    }
+
+    pub fn new_max() -> Self {
+        Self::new(HeapKind::Max)
+    }
+
+    /// d-ary heap, every node has up to `arity` children. Wider heaps are shallower, so
+    /// pushes get cheaper while pops compare more children per level.
+    pub fn with_arity(kind: HeapKind, arity: usize) -> Self {
+        assert!(arity >= 2, "heap arity must be at least 2");
+        Self {
+            data: Vec::new(),
+            kind,
+            arity,
+        }
+    }
+
+    /// Builds a heap from an existing vec in O(n) (bottom-up heapify)
+    pub fn from_vec(data: Vec<T>, kind: HeapKind) -> Self {
+        Self::from_vec_with_arity(data, kind, 2)
+    }
+
+    pub fn from_vec_with_arity(mut data: Vec<T>, kind: HeapKind, arity: usize) -> Self {
+        assert!(arity >= 2, "heap arity must be at least 2");
+        heapify(&mut data, kind, arity, &mut Probe::new(&mut ()));
+        Self { data, kind, arity }
+    }
+
+    pub fn kind(&self) -> HeapKind {
+        self.kind
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// The smallest item for a min heap, the largest for a max heap
+    pub fn peek(&self) -> Option<&T> {
+        self.data.first()
+    }
+
+    pub fn push(&mut self, item: T) {
+        self.data.push(item);
+        let last = self.data.len() - 1;
+        sift_up(&mut self.data, last, self.kind, self.arity);
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        if self.data.is_empty() {
+            return None;
+        }
+        let item = self.data.swap_remove(0);
+        let len = self.data.len();
+        self.sift_down(0, len);
+        Some(item)
+    }
+
+    /// Push followed by pop, but with a single sift. If `item` would come straight back
+    /// out it is returned without touching the heap at all.
+    pub fn push_pop(&mut self, mut item: T) -> T {
+        match self.data.first() {
+            Some(top) if self.kind.before(top, &item) => {
+                std::mem::swap(&mut self.data[0], &mut item);
+                let len = self.data.len();
+                self.sift_down(0, len);
+                item
+            }
+            _ => item,
+        }
+    }
+
+    pub fn into_vec(self) -> Vec<T> {
+        self.data
+    }
+
+    /// Consumes the heap and returns its items in ascending order, whatever the kind
+    pub fn into_sorted_vec(mut self) -> Vec<T> {
+        for end in (1..self.data.len()).rev() {
+            self.data.swap(0, end);
+            self.sift_down(0, end);
+        }
+        // popping the root to the back leaves a max heap ascending, a min heap descending
+        if self.kind == HeapKind::Min {
+            self.data.reverse();
+        }
+        self.data
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.data.iter()
+    }
+
+    fn sift_down(&mut self, node: usize, end: usize) {
+        let (kind, arity) = (self.kind, self.arity);
+        sift_down(
+            &mut self.data,
+            node,
+            end,
+            kind,
+            arity,
+            &mut Probe::new(&mut ()),
+        );
+    }
+}
+
+impl<T: Ord> Default for MoHeap<T> {
+    fn default() -> Self {
+        Self::new_max()
+    }
+}
+
+/// Turns `data` into a heap in place, sifting down every internal node from the last one
+pub(crate) fn heapify<T: Ord, O: SortObserver<T>>(
+    data: &mut [T],
+    kind: HeapKind,
+    arity: usize,
+    probe: &mut Probe<'_, T, O>,
+) {
+    let len = data.len();
+    if len < 2 {
+        return;
+    }
+    for node in (0..=(len - 2) / arity).rev() {
+        sift_down(data, node, len, kind, arity, probe);
+    }
+}
+
+/// Moves `data[node]` down until the heap property holds within `data[..end]`
+pub(crate) fn sift_down<T: Ord, O: SortObserver<T>>(
+    data: &mut [T],
+    mut node: usize,
+    end: usize,
+    kind: HeapKind,
+    arity: usize,
+    probe: &mut Probe<'_, T, O>,
+) {
+    loop {
+        let first_child = node * arity + 1;
+        if first_child >= end {
+            return;
+        }
+
+        // pick the child that should be closest to the root
+        let last_child = (first_child + arity).min(end);
+        let mut best = first_child;
+        for child in first_child + 1..last_child {
+            if kind.before_at(data, child, best, probe) {
+                best = child;
+            }
+        }
+
+        if !kind.before_at(data, best, node, probe) {
+            return;
+        }
+        probe.swap(data, node, best);
+        node = best;
+    }
+}
+
+fn sift_up<T: Ord>(data: &mut [T], mut node: usize, kind: HeapKind, arity: usize) {
+    while node > 0 {
+        let parent = (node - 1) / arity;
+        if !kind.before(&data[node], &data[parent]) {
+            return;
+        }
+        data.swap(node, parent);
+        node = parent;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn drain<T: Ord>(mut heap: MoHeap<T>) -> Vec<T> {
+        let mut result = Vec::new();
+        while let Some(item) = heap.pop() {
+            result.push(item);
+        }
+        result
+    }
+
+    #[test]
+    fn test_new_heap_is_empty() {
+        let heap: MoHeap<i32> = MoHeap::new_min();
+        assert!(heap.is_empty());
+        assert_eq!(heap.len(), 0);
+        assert_eq!(heap.peek(), None);
+    }
+
+    #[test]
+    fn test_min_heap_pops_ascending() {
+        let mut heap = MoHeap::new_min();
+        for x in [5, 3, 8, 1, 9, 2] {
+            heap.push(x);
+        }
+        assert_eq!(heap.peek(), Some(&1));
+        assert_eq!(drain(heap), [1, 2, 3, 5, 8, 9]);
+    }
+
+    #[test]
+    fn test_max_heap_pops_descending() {
+        let mut heap = MoHeap::new_max();
+        for x in [5, 3, 8, 1, 9, 2] {
+            heap.push(x);
+        }
+        assert_eq!(heap.peek(), Some(&9));
+        assert_eq!(drain(heap), [9, 8, 5, 3, 2, 1]);
+    }
+
+    #[test]
+    fn test_pop_empty() {
+        let mut heap: MoHeap<i32> = MoHeap::new_max();
+        assert_eq!(heap.pop(), None);
+    }
+
+    #[test]
+    fn test_duplicates() {
+        let heap = MoHeap::from_vec(vec![2, 2, 1, 1, 3, 3], HeapKind::Min);
+        assert_eq!(drain(heap), [1, 1, 2, 2, 3, 3]);
+    }
+
+    #[test]
+    fn test_from_vec_heapifies() {
+        let heap = MoHeap::from_vec(vec![4, 7, 1, 9, 3, 6, 2], HeapKind::Max);
+        assert_eq!(heap.len(), 7);
+        assert_eq!(heap.peek(), Some(&9));
+        assert_eq!(drain(heap), [9, 7, 6, 4, 3, 2, 1]);
+    }
+
+    #[test]
+    fn test_d_ary_heaps() {
+        let values: Vec<i32> = (0..200).map(|i| (i * 37) % 101).collect();
+        let mut expected = values.clone();
+        expected.sort();
+
+        for arity in 2..=6 {
+            let heap = MoHeap::from_vec_with_arity(values.clone(), HeapKind::Min, arity);
+            assert_eq!(drain(heap), expected, "arity {arity}");
+
+            let mut heap = MoHeap::with_arity(HeapKind::Min, arity);
+            for &x in &values {
+                heap.push(x);
+            }
+            assert_eq!(drain(heap), expected, "arity {arity}");
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "arity")]
+    fn test_arity_one_panics() {
+        let _heap: MoHeap<i32> = MoHeap::with_arity(HeapKind::Min, 1);
+    }
+
+    #[test]
+    fn test_push_pop() {
+        let mut heap = MoHeap::from_vec(vec![3, 5, 7], HeapKind::Min);
+
+        // smaller than the top, comes straight back
+        assert_eq!(heap.push_pop(1), 1);
+        assert_eq!(heap.len(), 3);
+
+        // larger, the old top comes out instead
+        assert_eq!(heap.push_pop(6), 3);
+        assert_eq!(drain(heap), [5, 6, 7]);
+    }
+
+    #[test]
+    fn test_push_pop_empty() {
+        let mut heap = MoHeap::new_max();
+        assert_eq!(heap.push_pop(4), 4);
+        assert!(heap.is_empty());
+    }
+
+    #[test]
+    fn test_into_sorted_vec() {
+        let values = vec![5, 1, 4, 1, 5, 9, 2, 6];
+        let expected = vec![1, 1, 2, 4, 5, 5, 6, 9];
+
+        let max_heap = MoHeap::from_vec(values.clone(), HeapKind::Max);
+        assert_eq!(max_heap.into_sorted_vec(), expected);
+
+        let min_heap = MoHeap::from_vec_with_arity(values, HeapKind::Min, 3);
+        assert_eq!(min_heap.into_sorted_vec(), expected);
+    }
+
+    #[test]
+    fn test_strings() {
+        let mut heap = MoHeap::new_min();
+        for word in ["pear", "apple", "fig"] {
+            heap.push(word.to_string());
+        }
+        assert_eq!(heap.pop().as_deref(), Some("apple"));
+        assert_eq!(heap.pop().as_deref(), Some("fig"));
+    }
+}
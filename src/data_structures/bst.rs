@@ -39,38 +39,34 @@ impl<K: Ord + Clone, V: Clone> Bst<K, V> {
         }
     }
 
+    // walks down instead of recursing, a degenerate tree can be as deep as it is long
     fn insert_helper(node: &Rc<RefCell<BSTNode<K, V>>>, key: K, value: V) {
-        let borrowed = node.borrow();
-
-        if key < borrowed.key {
-            if let Some(ref left_node) = borrowed.left.clone() {
-                drop(borrowed);
-                Self::insert_helper(left_node, key, value);
-            } else {
-                drop(borrowed);
-                node.borrow_mut().left = Some(Rc::new(RefCell::new(BSTNode {
-                    key,
-                    value,
-                    left: None,
-                    right: None,
-                })));
-            }
-        } else if key > borrowed.key {
-            if let Some(ref right_node) = borrowed.right.clone() {
-                drop(borrowed);
-                Self::insert_helper(right_node, key, value);
-            } else {
-                drop(borrowed);
-                node.borrow_mut().right = Some(Rc::new(RefCell::new(BSTNode {
-                    key,
-                    value,
-                    left: None,
-                    right: None,
-                })));
-            }
-        } else {
-            drop(borrowed);
-            node.borrow_mut().value = value
+        let mut current = node.clone();
+        loop {
+            let next = {
+                let mut borrowed = current.borrow_mut();
+                let child = if key < borrowed.key {
+                    &mut borrowed.left
+                } else if key > borrowed.key {
+                    &mut borrowed.right
+                } else {
+                    borrowed.value = value;
+                    return;
+                };
+                match child {
+                    Some(next) => next.clone(),
+                    None => {
+                        *child = Some(Rc::new(RefCell::new(BSTNode {
+                            key,
+                            value,
+                            left: None,
+                            right: None,
+                        })));
+                        return;
+                    }
+                }
+            };
+            current = next;
         }
     }
 
@@ -82,25 +78,22 @@ impl<K: Ord + Clone, V: Clone> Bst<K, V> {
         }
     }
     fn search_helper(node: &Rc<RefCell<BSTNode<K, V>>>, key: &K) -> Option<V> {
-        let borrowed = node.borrow();
-        let left_node = borrowed.left.clone();
-        let right_node = borrowed.right.clone();
-        if *key == borrowed.key {
-            return Some(borrowed.value.clone());
-        }
-        if *key < borrowed.key {
-            // Go left here to check left side
-            if let Some(ref left) = left_node {
-                drop(borrowed);
-                Self::search_helper(left, key)
-            } else {
-                None // return none if not found.
-            }
-        } else if let Some(ref right) = right_node {
-            drop(borrowed);
-            Self::search_helper(right, key)
-        } else {
-            None
+        let mut current = node.clone();
+        loop {
+            let next = {
+                let borrowed = current.borrow();
+                if *key == borrowed.key {
+                    return Some(borrowed.value.clone());
+                }
+                // go left or right, none if there is no child that way
+                let child = if *key < borrowed.key {
+                    &borrowed.left
+                } else {
+                    &borrowed.right
+                };
+                child.clone()?
+            };
+            current = next;
         }
     }
     pub fn delete(&mut self, key: &K) {
@@ -113,53 +106,75 @@ impl<K: Ord + Clone, V: Clone> Bst<K, V> {
         node: &Rc<RefCell<BSTNode<K, V>>>,
         key: &K,
     ) -> Option<Rc<RefCell<BSTNode<K, V>>>> {
-        let mut borrowed = node.borrow_mut();
-
-        if *key < borrowed.key {
-            if let Some(ref left) = borrowed.left.take() {
-                borrowed.left = Self::delete_helper(left, key);
-            }
-        } else if *key > borrowed.key {
-            if let Some(ref right) = borrowed.right.take() {
-                borrowed.right = Self::delete_helper(right, key);
-            }
-        } else {
-            // Is leaf (no children)
-            if borrowed.left.is_none() && borrowed.right.is_none() {
-                return None;
-            }
-
-            // Parent node has only one child
+        // find the node holding the key, and its parent
+        let mut parent: Option<Rc<RefCell<BSTNode<K, V>>>> = None;
+        let mut current = node.clone();
+        loop {
+            let next = {
+                let borrowed = current.borrow();
+                if *key == borrowed.key {
+                    break;
+                }
+                let child = if *key < borrowed.key {
+                    &borrowed.left
+                } else {
+                    &borrowed.right
+                };
+                match child {
+                    Some(child) => child.clone(),
+                    // not in the tree, nothing changes
+                    None => return Some(node.clone()),
+                }
+            };
+            parent = Some(std::mem::replace(&mut current, next));
+        }
 
-            if borrowed.left.is_none() {
-                return borrowed.right.take();
+        // what takes the deleted node's place under its parent
+        let replacement = {
+            let mut borrowed = current.borrow_mut();
+            match (borrowed.left.take(), borrowed.right.take()) {
+                // Is leaf (no children)
+                (None, None) => None,
+                // Parent node has only one child
+                (Some(child), None) | (None, Some(child)) => Some(child),
+                // Parent node has two children, the successor has no left child so deleting
+                // it from the right subtree stops right there
+                (Some(left), Some(right)) => {
+                    let (successor_key, successor_value) = Self::find_min(&right);
+                    borrowed.left = Some(left);
+                    borrowed.right = Self::delete_helper(&right, &successor_key);
+                    borrowed.key = successor_key;
+                    borrowed.value = successor_value;
+                    Some(current.clone())
+                }
             }
-            if borrowed.right.is_none() {
-                return borrowed.left.take();
+        };
+
+        match parent {
+            None => replacement,
+            Some(parent) => {
+                let mut borrowed = parent.borrow_mut();
+                if *key < borrowed.key {
+                    borrowed.left = replacement;
+                } else {
+                    borrowed.right = replacement;
+                }
+                Some(node.clone())
             }
-
-            // Parent node has two children
-            // If this panic, something is totally wrong, since have checked for none above
-            let right = borrowed
-                .right
-                .take()
-                .expect("Two children case, right child should exist, should not be None.");
-            let (successor_key, successor_value) = Self::find_min(&right);
-            borrowed.key = successor_key;
-            borrowed.value = successor_value;
-            borrowed.right = Self::delete_helper(&right, &borrowed.key)
         }
-
-        Some(node.clone())
     }
 
     fn find_min(node: &Rc<RefCell<BSTNode<K, V>>>) -> (K, V) {
-        let borrowed = node.borrow();
-
         // we only go left because left subtrees have min, going right we would find max
-        if let Some(ref left) = borrowed.left {
-            return Self::find_min(left);
+        let mut current = node.clone();
+        loop {
+            let left = current.borrow().left.clone();
+            match left {
+                Some(left) => current = left,
+                None => break,
+            }
         }
+        let borrowed = current.borrow();
         (borrowed.key.clone(), borrowed.value.clone())
     }
 
@@ -172,6 +187,22 @@ impl<K: Ord + Clone, V: Clone> Bst<K, V> {
     }
 }
 
+// The derived drop recurses once per level, a degenerate tree would overflow the stack.
+// Nodes are taken apart one at a time instead, children first moved to a list.
+impl<K, V> Drop for Bst<K, V> {
+    fn drop(&mut self) {
+        let mut pending: Vec<_> = self.root.take().into_iter().collect();
+        while let Some(node) = pending.pop() {
+            // a node still shared elsewhere is left to its other owner
+            if let Ok(node) = Rc::try_unwrap(node) {
+                let mut node = node.into_inner();
+                pending.extend(node.left.take());
+                pending.extend(node.right.take());
+            }
+        }
+    }
+}
+
 // custom display implementation
 impl<K: Display + Ord, V: Display> Display for Bst<K, V> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
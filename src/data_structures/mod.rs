@@ -1,7 +1,10 @@
 pub mod avl_bst;
 pub mod b_tree;
+pub mod binary_heap;
 pub mod bst;
 pub mod graphs;
 pub mod hash_tables;
 pub mod hash_tables_generic;
+pub mod radix_tree;
 pub mod tree;
+pub mod trie;
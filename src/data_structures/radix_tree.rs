@@ -0,0 +1,402 @@
+// Radix tree (compressed trie): a `Trie` where a chain of nodes with one child and no
+// value is merged into a single edge labelled with all their bytes. Same operations and
+// costs, but the number of nodes is at most twice the number of keys instead of growing
+// with their total length.
+//
+// Inserting can split an edge where the new key leaves it, removing can merge a node
+// back into its only child.
+
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+
+use crate::algoritms::sorting::select::top_k;
+
+struct RadixNode<V> {
+    /// the bytes on the edge into this node
+    label: Vec<u8>,
+    value: Option<V>,
+    /// keyed by the first byte of the child's label
+    children: BTreeMap<u8, RadixNode<V>>,
+}
+
+impl<V> RadixNode<V> {
+    fn new(label: Vec<u8>, value: Option<V>) -> Self {
+        RadixNode {
+            label,
+            value,
+            children: BTreeMap::new(),
+        }
+    }
+}
+
+pub struct RadixTree<V> {
+    root: RadixNode<V>,
+    len: usize,
+}
+
+impl<V> RadixTree<V> {
+    pub fn new() -> Self {
+        RadixTree {
+            root: RadixNode::new(Vec::new(), None),
+            len: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Number of nodes, the root included
+    pub fn node_count(&self) -> usize {
+        fn count<V>(node: &RadixNode<V>) -> usize {
+            1 + node.children.values().map(count).sum::<usize>()
+        }
+        count(&self.root)
+    }
+
+    /// Inserts `value` under `key`, returning the value it replaces
+    pub fn insert(&mut self, key: impl AsRef<[u8]>, value: V) -> Option<V> {
+        let old = insert(&mut self.root, key.as_ref(), value);
+        if old.is_none() {
+            self.len += 1;
+        }
+        old
+    }
+
+    pub fn get(&self, key: impl AsRef<[u8]>) -> Option<&V> {
+        let mut node = &self.root;
+        let mut key = key.as_ref();
+        while let Some(first) = key.first() {
+            node = node.children.get(first)?;
+            key = key.strip_prefix(node.label.as_slice())?;
+        }
+        node.value.as_ref()
+    }
+
+    pub fn get_mut(&mut self, key: impl AsRef<[u8]>) -> Option<&mut V> {
+        let mut node = &mut self.root;
+        let mut key = key.as_ref();
+        while let Some(first) = key.first() {
+            node = node.children.get_mut(first)?;
+            key = key.strip_prefix(node.label.as_slice())?;
+        }
+        node.value.as_mut()
+    }
+
+    pub fn contains_key(&self, key: impl AsRef<[u8]>) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Removes `key` and returns its value. A node left without a value is dropped if it
+    /// has no children and merged into its child if it has one.
+    pub fn remove(&mut self, key: impl AsRef<[u8]>) -> Option<V> {
+        let removed = remove(&mut self.root, key.as_ref());
+        if removed.is_some() {
+            self.len -= 1;
+        }
+        removed
+    }
+
+    /// Every key starting with `prefix` and its value, in lexicographic order
+    pub fn iter_prefix(&self, prefix: impl AsRef<[u8]>) -> PrefixIter<'_, V> {
+        let mut node = &self.root;
+        let mut path = Vec::new();
+        let mut rest = prefix.as_ref();
+        let mut stack = Vec::new();
+
+        loop {
+            let Some(first) = rest.first() else {
+                stack.push((path, node));
+                break;
+            };
+            let Some(child) = node.children.get(first) else {
+                break;
+            };
+            path.extend_from_slice(&child.label);
+            // the prefix can end part way along an edge
+            if child.label.starts_with(rest) {
+                stack.push((path, child));
+                break;
+            }
+            let Some(after) = rest.strip_prefix(child.label.as_slice()) else {
+                break;
+            };
+            rest = after;
+            node = child;
+        }
+        PrefixIter { stack }
+    }
+
+    pub fn iter(&self) -> PrefixIter<'_, V> {
+        self.iter_prefix([])
+    }
+
+    /// The longest key that is a prefix of `text`, as that prefix of `text`, and its value
+    pub fn longest_prefix_match<'t>(&self, text: &'t [u8]) -> Option<(&'t [u8], &V)> {
+        let mut node = &self.root;
+        let mut matched = 0;
+        let mut longest = node.value.as_ref().map(|value| (&text[..0], value));
+        while let Some(first) = text.get(matched) {
+            let Some(child) = node.children.get(first) else {
+                break;
+            };
+            if !text[matched..].starts_with(&child.label) {
+                break;
+            }
+            matched += child.label.len();
+            node = child;
+            if let Some(value) = &node.value {
+                longest = Some((&text[..matched], value));
+            }
+        }
+        longest
+    }
+
+    /// The `k` keys starting with `prefix` that have the largest values, largest first and
+    /// alphabetically on a tie
+    pub fn autocomplete(&self, prefix: impl AsRef<[u8]>, k: usize) -> Vec<(Vec<u8>, &V)>
+    where
+        V: Ord,
+    {
+        top_k(
+            self.iter_prefix(prefix)
+                .map(|(key, value)| (value, Reverse(key))),
+            k,
+        )
+        .into_iter()
+        .map(|(value, Reverse(key))| (key, value))
+        .collect()
+    }
+}
+
+impl<V> Default for RadixTree<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// `key` is what is left of the key below `node`
+fn insert<V>(node: &mut RadixNode<V>, key: &[u8], value: V) -> Option<V> {
+    let Some(&first) = key.first() else {
+        return node.value.replace(value);
+    };
+    let Some(child) = node.children.get_mut(&first) else {
+        node.children
+            .insert(first, RadixNode::new(key.to_vec(), Some(value)));
+        return None;
+    };
+
+    let common = child
+        .label
+        .iter()
+        .zip(key)
+        .take_while(|(a, b)| a == b)
+        .count();
+    if common < child.label.len() {
+        // the key leaves the edge part way: split it with a node where they part
+        let mut lower = std::mem::replace(child, RadixNode::new(key[..common].to_vec(), None));
+        lower.label.drain(..common);
+        child.children.insert(lower.label[0], lower);
+    }
+    insert(child, &key[common..], value)
+}
+
+fn remove<V>(node: &mut RadixNode<V>, key: &[u8]) -> Option<V> {
+    let Some(first) = key.first() else {
+        return node.value.take();
+    };
+    let child = node.children.get_mut(first)?;
+    let rest = key.strip_prefix(child.label.as_slice())?;
+    let removed = remove(child, rest)?;
+
+    if child.value.is_none() {
+        match child.children.len() {
+            0 => {
+                node.children.remove(first);
+            }
+            1 => {
+                let (_, mut only) = child.children.pop_first().unwrap();
+                let mut label = std::mem::take(&mut child.label);
+                label.append(&mut only.label);
+                only.label = label;
+                *child = only;
+            }
+            _ => {}
+        }
+    }
+    Some(removed)
+}
+
+/// Depth-first walk below one node, children in byte order
+pub struct PrefixIter<'a, V> {
+    stack: Vec<(Vec<u8>, &'a RadixNode<V>)>,
+}
+
+impl<'a, V> Iterator for PrefixIter<'a, V> {
+    type Item = (Vec<u8>, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((key, node)) = self.stack.pop() {
+            for child in node.children.values().rev() {
+                let mut child_key = key.clone();
+                child_key.extend_from_slice(&child.label);
+                self.stack.push((child_key, child));
+            }
+            if let Some(value) = &node.value {
+                return Some((key, value));
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+    use crate::algoritms::sorting::quick_sort::XorShift64;
+    use crate::data_structures::trie::Trie;
+    use crate::data_structures::trie::tests::moby_dick_words;
+
+    /// Every node but the root has a value or at least two children
+    fn check_compressed<V>(node: &RadixNode<V>, is_root: bool) {
+        if !is_root {
+            assert!(!node.label.is_empty());
+            assert!(node.value.is_some() || node.children.len() >= 2);
+        }
+        for (first, child) in &node.children {
+            assert_eq!(child.label[0], *first);
+            check_compressed(child, false);
+        }
+    }
+
+    #[test]
+    fn test_splits_and_merges() {
+        let mut tree = RadixTree::new();
+        tree.insert("romane", 1);
+        tree.insert("romanus", 2);
+        tree.insert("romulus", 3);
+        tree.insert("rubens", 4);
+        tree.insert("ruber", 5);
+        tree.insert("rubicon", 6);
+        tree.insert("rubicundus", 7);
+        check_compressed(&tree.root, true);
+        // r, om, an, e, us, ulus, ub, e, ns, r, ic, on, undus and the root
+        assert_eq!(tree.node_count(), 14);
+        assert_eq!(tree.get("rubicon"), Some(&6));
+        assert_eq!(tree.get("rub"), None);
+        assert_eq!(tree.get("rubiconx"), None);
+
+        assert_eq!(tree.remove("rubicon"), Some(6));
+        assert_eq!(tree.remove("rubicon"), None);
+        check_compressed(&tree.root, true);
+        // ic and undus are merged into icundus
+        assert_eq!(tree.node_count(), 12);
+        assert_eq!(tree.get("rubicundus"), Some(&7));
+
+        tree.insert("rom", 8);
+        assert_eq!(tree.get("rom"), Some(&8));
+        check_compressed(&tree.root, true);
+        assert_eq!(tree.len(), 7);
+    }
+
+    #[test]
+    fn test_matches_btree_map() {
+        let mut rng = XorShift64::new(46);
+        let mut tree = RadixTree::new();
+        let mut expected = BTreeMap::new();
+        for step in 0..5000 {
+            let key: Vec<u8> = (0..rng.next_below(6))
+                .map(|_| b'a' + rng.next_below(3) as u8)
+                .collect();
+            if rng.next_below(3) == 0 {
+                assert_eq!(tree.remove(&key), expected.remove(&key));
+            } else {
+                assert_eq!(tree.insert(&key, step), expected.insert(key, step));
+            }
+            assert_eq!(tree.len(), expected.len());
+            check_compressed(&tree.root, true);
+        }
+        let all: Vec<(Vec<u8>, &i32)> = tree.iter().collect();
+        let reference: Vec<(Vec<u8>, &i32)> =
+            expected.iter().map(|(k, v)| (k.clone(), v)).collect();
+        assert_eq!(all, reference);
+
+        for prefix in [&b"a"[..], b"ab", b"bca", b"ccc", b""] {
+            let found: Vec<Vec<u8>> = tree.iter_prefix(prefix).map(|(key, _)| key).collect();
+            let reference: Vec<Vec<u8>> = expected
+                .keys()
+                .filter(|key| key.starts_with(prefix))
+                .cloned()
+                .collect();
+            assert_eq!(found, reference);
+        }
+    }
+
+    #[test]
+    fn test_longest_prefix_match() {
+        let mut tree = RadixTree::new();
+        for (route, hop) in [("10.", 1), ("10.1.", 2), ("10.1.2.", 3), ("192.168.", 4)] {
+            tree.insert(route, hop);
+        }
+        assert_eq!(
+            tree.longest_prefix_match(b"10.1.2.3"),
+            Some((&b"10.1.2."[..], &3))
+        );
+        assert_eq!(
+            tree.longest_prefix_match(b"10.1.3.1"),
+            Some((&b"10.1."[..], &2))
+        );
+        assert_eq!(tree.longest_prefix_match(b"10.9"), Some((&b"10."[..], &1)));
+        assert_eq!(tree.longest_prefix_match(b"192.1"), None);
+    }
+
+    #[test]
+    fn test_moby_dick_matches_trie() {
+        let words = moby_dick_words();
+        let mut tree = RadixTree::new();
+        let mut trie = Trie::new();
+        for word in &words {
+            match tree.get_mut(word) {
+                Some(count) => *count += 1,
+                None => {
+                    tree.insert(word, 1usize);
+                }
+            }
+            match trie.get_mut(word) {
+                Some(count) => *count += 1,
+                None => {
+                    trie.insert(word, 1usize);
+                }
+            }
+        }
+        assert_eq!(tree.len(), trie.len());
+        assert!(tree.iter().eq(trie.iter()));
+        // far fewer nodes than the trie, and never more than two per key
+        assert!(tree.node_count() <= 2 * tree.len());
+        assert!(tree.node_count() * 2 < trie.node_count());
+
+        for prefix in ["wha", "ahab", "sea", "q", "xyz"] {
+            assert_eq!(tree.autocomplete(prefix, 5), trie.autocomplete(prefix, 5));
+        }
+        let top: Vec<Vec<u8>> = tree
+            .autocomplete("", 3)
+            .into_iter()
+            .map(|(key, _)| key)
+            .collect();
+        assert_eq!(top, [b"the".to_vec(), b"of".to_vec(), b"and".to_vec()]);
+
+        // removing every other distinct word keeps both in step
+        let keys: Vec<Vec<u8>> = trie.iter().map(|(key, _)| key).step_by(2).collect();
+        for key in &keys {
+            assert_eq!(tree.remove(key), trie.remove(key));
+        }
+        check_compressed(&tree.root, true);
+        assert!(tree.iter().eq(trie.iter()));
+    }
+}
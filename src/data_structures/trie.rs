@@ -0,0 +1,314 @@
+// Trie: a map from byte strings to values where every key is a path from the root, one
+// byte per edge. Lookups cost O(key length) whatever the number of keys, and all keys
+// sharing a prefix sit below the same node, which makes prefix queries cheap. Children
+// are kept in a BTreeMap so iteration is in lexicographic order.
+//
+// `RadixTree` in radix_tree.rs is the same map with chains of single-child nodes merged.
+
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+
+use crate::algoritms::sorting::select::top_k;
+
+struct TrieNode<V> {
+    value: Option<V>,
+    children: BTreeMap<u8, TrieNode<V>>,
+}
+
+impl<V> TrieNode<V> {
+    fn new() -> Self {
+        TrieNode {
+            value: None,
+            children: BTreeMap::new(),
+        }
+    }
+}
+
+pub struct Trie<V> {
+    root: TrieNode<V>,
+    len: usize,
+}
+
+impl<V> Trie<V> {
+    pub fn new() -> Self {
+        Trie {
+            root: TrieNode::new(),
+            len: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Number of nodes, the root included
+    pub fn node_count(&self) -> usize {
+        fn count<V>(node: &TrieNode<V>) -> usize {
+            1 + node.children.values().map(count).sum::<usize>()
+        }
+        count(&self.root)
+    }
+
+    /// Inserts `value` under `key`, returning the value it replaces
+    pub fn insert(&mut self, key: impl AsRef<[u8]>, value: V) -> Option<V> {
+        let mut node = &mut self.root;
+        for &byte in key.as_ref() {
+            node = node.children.entry(byte).or_insert_with(TrieNode::new);
+        }
+        let old = node.value.replace(value);
+        if old.is_none() {
+            self.len += 1;
+        }
+        old
+    }
+
+    pub fn get(&self, key: impl AsRef<[u8]>) -> Option<&V> {
+        self.find(key.as_ref())?.value.as_ref()
+    }
+
+    pub fn get_mut(&mut self, key: impl AsRef<[u8]>) -> Option<&mut V> {
+        let mut node = &mut self.root;
+        for byte in key.as_ref() {
+            node = node.children.get_mut(byte)?;
+        }
+        node.value.as_mut()
+    }
+
+    pub fn contains_key(&self, key: impl AsRef<[u8]>) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Removes `key` and returns its value, dropping the nodes left without keys below them
+    pub fn remove(&mut self, key: impl AsRef<[u8]>) -> Option<V> {
+        fn remove<V>(node: &mut TrieNode<V>, key: &[u8]) -> Option<V> {
+            let Some((first, rest)) = key.split_first() else {
+                return node.value.take();
+            };
+            let child = node.children.get_mut(first)?;
+            let removed = remove(child, rest)?;
+            if child.value.is_none() && child.children.is_empty() {
+                node.children.remove(first);
+            }
+            Some(removed)
+        }
+
+        let removed = remove(&mut self.root, key.as_ref());
+        if removed.is_some() {
+            self.len -= 1;
+        }
+        removed
+    }
+
+    fn find(&self, key: &[u8]) -> Option<&TrieNode<V>> {
+        let mut node = &self.root;
+        for byte in key {
+            node = node.children.get(byte)?;
+        }
+        Some(node)
+    }
+
+    /// Every key starting with `prefix` and its value, in lexicographic order
+    pub fn iter_prefix(&self, prefix: impl AsRef<[u8]>) -> PrefixIter<'_, V> {
+        let prefix = prefix.as_ref();
+        PrefixIter {
+            stack: self
+                .find(prefix)
+                .map(|node| (prefix.to_vec(), node))
+                .into_iter()
+                .collect(),
+        }
+    }
+
+    pub fn iter(&self) -> PrefixIter<'_, V> {
+        self.iter_prefix([])
+    }
+
+    /// The longest key that is a prefix of `text`, as that prefix of `text`, and its value
+    pub fn longest_prefix_match<'t>(&self, text: &'t [u8]) -> Option<(&'t [u8], &V)> {
+        let mut node = &self.root;
+        let mut longest = node.value.as_ref().map(|value| (&text[..0], value));
+        for (i, byte) in text.iter().enumerate() {
+            let Some(child) = node.children.get(byte) else {
+                break;
+            };
+            node = child;
+            if let Some(value) = &node.value {
+                longest = Some((&text[..=i], value));
+            }
+        }
+        longest
+    }
+
+    /// The `k` keys starting with `prefix` that have the largest values, largest first and
+    /// alphabetically on a tie. With word counts as values, the likeliest completions.
+    pub fn autocomplete(&self, prefix: impl AsRef<[u8]>, k: usize) -> Vec<(Vec<u8>, &V)>
+    where
+        V: Ord,
+    {
+        top_k(
+            self.iter_prefix(prefix)
+                .map(|(key, value)| (value, Reverse(key))),
+            k,
+        )
+        .into_iter()
+        .map(|(value, Reverse(key))| (key, value))
+        .collect()
+    }
+}
+
+impl<V> Default for Trie<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Depth-first walk below one node, children in byte order
+pub struct PrefixIter<'a, V> {
+    stack: Vec<(Vec<u8>, &'a TrieNode<V>)>,
+}
+
+impl<'a, V> Iterator for PrefixIter<'a, V> {
+    type Item = (Vec<u8>, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((key, node)) = self.stack.pop() {
+            // reversed, so the smallest byte is popped first
+            for (&byte, child) in node.children.iter().rev() {
+                let mut child_key = key.clone();
+                child_key.push(byte);
+                self.stack.push((child_key, child));
+            }
+            if let Some(value) = &node.value {
+                return Some((key, value));
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::collections::BTreeMap;
+    use std::fs;
+
+    use super::*;
+    use crate::algoritms::sorting::quick_sort::XorShift64;
+
+    /// The lowercase words of Moby Dick, the radix tree tests use them too
+    pub(crate) fn moby_dick_words() -> Vec<String> {
+        fs::read_to_string("data/moby_dick.txt")
+            .unwrap()
+            .split(|c: char| !c.is_ascii_alphabetic())
+            .filter(|word| !word.is_empty())
+            .map(|word| word.to_ascii_lowercase())
+            .collect()
+    }
+
+    fn strings<V: Copy>(entries: Vec<(Vec<u8>, &V)>) -> Vec<(String, V)> {
+        entries
+            .into_iter()
+            .map(|(key, value)| (String::from_utf8(key).unwrap(), *value))
+            .collect()
+    }
+
+    #[test]
+    fn test_insert_get_remove() {
+        let mut trie = Trie::new();
+        assert_eq!(trie.insert("tea", 1), None);
+        assert_eq!(trie.insert("ten", 2), None);
+        assert_eq!(trie.insert("te", 3), None);
+        assert_eq!(trie.insert("tea", 4), Some(1));
+        assert_eq!(trie.len(), 3);
+
+        assert_eq!(trie.get("tea"), Some(&4));
+        assert_eq!(trie.get("t"), None);
+        assert_eq!(trie.get("team"), None);
+        *trie.get_mut("te").unwrap() += 10;
+        assert_eq!(trie.get("te"), Some(&13));
+
+        assert_eq!(trie.remove("t"), None);
+        assert_eq!(trie.remove("tea"), Some(4));
+        assert_eq!(trie.remove("tea"), None);
+        assert!(!trie.contains_key("tea"));
+        assert!(trie.contains_key("ten"));
+        assert_eq!(trie.len(), 2);
+        // "tea" had no children, its node is gone
+        assert_eq!(trie.node_count(), 4);
+
+        assert_eq!(trie.insert("", 0), None);
+        assert_eq!(trie.get(""), Some(&0));
+    }
+
+    #[test]
+    fn test_matches_btree_map() {
+        let mut rng = XorShift64::new(45);
+        let mut trie = Trie::new();
+        let mut expected = BTreeMap::new();
+        for step in 0..5000 {
+            let key: Vec<u8> = (0..rng.next_below(5))
+                .map(|_| b'a' + rng.next_below(3) as u8)
+                .collect();
+            if rng.next_below(3) == 0 {
+                assert_eq!(trie.remove(&key), expected.remove(&key));
+            } else {
+                assert_eq!(trie.insert(&key, step), expected.insert(key, step));
+            }
+            assert_eq!(trie.len(), expected.len());
+        }
+        let all: Vec<(Vec<u8>, &i32)> = trie.iter().collect();
+        let reference: Vec<(Vec<u8>, &i32)> =
+            expected.iter().map(|(k, v)| (k.clone(), v)).collect();
+        assert_eq!(all, reference);
+    }
+
+    #[test]
+    fn test_prefix_queries() {
+        let mut trie = Trie::new();
+        for (i, word) in ["a", "an", "and", "ant", "bee", "be"].iter().enumerate() {
+            trie.insert(word, i);
+        }
+        let keys: Vec<String> = trie
+            .iter_prefix("an")
+            .map(|(key, _)| String::from_utf8(key).unwrap())
+            .collect();
+        assert_eq!(keys, ["an", "and", "ant"]);
+        assert_eq!(trie.iter_prefix("c").count(), 0);
+
+        assert_eq!(trie.longest_prefix_match(b"ants"), Some((&b"ant"[..], &3)));
+        assert_eq!(trie.longest_prefix_match(b"anvil"), Some((&b"an"[..], &1)));
+        assert_eq!(trie.longest_prefix_match(b"bx"), None);
+        assert_eq!(trie.longest_prefix_match(b""), None);
+    }
+
+    #[test]
+    fn test_moby_dick_autocomplete() {
+        let words = moby_dick_words();
+        let mut trie = Trie::new();
+        for word in &words {
+            match trie.get_mut(word) {
+                Some(count) => *count += 1,
+                None => {
+                    trie.insert(word, 1usize);
+                }
+            }
+        }
+        let whales = words.iter().filter(|word| *word == "whale").count();
+        assert_eq!(trie.get("whale"), Some(&whales));
+
+        let completions = strings(trie.autocomplete("wha", 3));
+        assert_eq!(completions[0], ("whale".to_string(), whales));
+        assert_eq!(completions.len(), 3);
+        assert!(completions.windows(2).all(|w| w[0].1 >= w[1].1));
+
+        let distinct: std::collections::HashSet<&String> = words.iter().collect();
+        assert_eq!(trie.len(), distinct.len());
+        assert!(
+            trie.iter_prefix("ahab")
+                .all(|(key, _)| key.starts_with(b"ahab"))
+        );
+    }
+}
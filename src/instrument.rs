@@ -0,0 +1,135 @@
+// Hooks for counting what an algorithm does to a slice: comparisons, swaps, writes,
+// allocations. The sorters report through them, and so do the heap's sift functions
+// that heap sort is built on. What gets counted and how is up to the observer, see
+// `algoritms::sorting::instrument` for the sorting side.
+
+use std::cmp::Ordering;
+use std::marker::PhantomData;
+
+/// Receives the operations a sorter performs. Indices are positions in the slice that was
+/// passed to the sorter, all hooks default to doing nothing.
+pub trait SortObserver<T> {
+    fn on_compare(&mut self, _i: usize, _j: usize) {}
+
+    fn on_swap(&mut self, _i: usize, _j: usize) {}
+
+    /// `value` was written to `index`, copied in from a buffer or another position
+    fn on_write(&mut self, _index: usize, _value: &T) {}
+
+    /// a buffer with room for `elements` items was allocated
+    fn on_alloc(&mut self, _elements: usize) {}
+
+    /// `start..end` was partitioned, the pivot ended up at `pivot`
+    fn on_partition(&mut self, _start: usize, _pivot: usize, _end: usize) {}
+
+    /// the sorted runs `start..mid` and `mid..end` are about to be merged
+    fn on_merge(&mut self, _start: usize, _mid: usize, _end: usize) {}
+}
+
+impl<T> SortObserver<T> for () {}
+
+/// What the sorters use to talk to the observer. Carries the offset of the sub-slice
+/// being worked on, so recursive calls can keep using local indices.
+pub struct Probe<'o, T, O> {
+    observer: &'o mut O,
+    offset: usize,
+    _items: PhantomData<fn(&T)>,
+}
+
+impl<'o, T, O: SortObserver<T>> Probe<'o, T, O> {
+    pub fn new(observer: &'o mut O) -> Self {
+        Self {
+            observer,
+            offset: 0,
+            _items: PhantomData,
+        }
+    }
+
+    /// Probe for the sub-slice starting at `offset` (relative to this one)
+    pub fn at(&mut self, offset: usize) -> Probe<'_, T, O> {
+        Probe {
+            observer: &mut *self.observer,
+            offset: self.offset + offset,
+            _items: PhantomData,
+        }
+    }
+
+    /// `input_list[i] < input_list[j]`
+    pub fn less(&mut self, input_list: &[T], i: usize, j: usize) -> bool
+    where
+        T: Ord,
+    {
+        self.observer.on_compare(self.offset + i, self.offset + j);
+        input_list[i] < input_list[j]
+    }
+
+    /// Three-way comparison of `input_list[i]` and `input_list[j]`, counts as one
+    pub fn cmp(&mut self, input_list: &[T], i: usize, j: usize) -> Ordering
+    where
+        T: Ord,
+    {
+        self.observer.on_compare(self.offset + i, self.offset + j);
+        input_list[i].cmp(&input_list[j])
+    }
+
+    /// `a < b` for values that are not (or no longer) in the slice, `i` and `j` are the
+    /// positions they stand for
+    pub fn less_values(&mut self, a: &T, i: usize, b: &T, j: usize) -> bool
+    where
+        T: Ord,
+    {
+        self.observer.on_compare(self.offset + i, self.offset + j);
+        a < b
+    }
+
+    /// `previous < input_list[i]`, where `previous` is the element right before this
+    /// sub-slice (pdq sort's predecessor pivot)
+    pub fn previous_less(&mut self, previous: &T, input_list: &[T], i: usize) -> bool
+    where
+        T: Ord,
+    {
+        self.observer.on_compare(self.offset - 1, self.offset + i);
+        *previous < input_list[i]
+    }
+
+    /// Reports a comparison the caller did itself, for keys that are not `Ord` or are
+    /// only compared in part (a float, a single byte of a string)
+    pub fn compared(&mut self, i: usize, j: usize) {
+        self.observer.on_compare(self.offset + i, self.offset + j);
+    }
+
+    pub fn swap(&mut self, input_list: &mut [T], i: usize, j: usize) {
+        self.observer.on_swap(self.offset + i, self.offset + j);
+        input_list.swap(i, j);
+    }
+
+    /// `input_list[index] = value.clone()`
+    pub fn write(&mut self, input_list: &mut [T], index: usize, value: &T)
+    where
+        T: Clone,
+    {
+        input_list[index] = value.clone();
+        self.wrote(index, value);
+    }
+
+    /// Reports a write the caller already did itself
+    pub fn wrote(&mut self, index: usize, value: &T) {
+        self.observer.on_write(self.offset + index, value);
+    }
+
+    pub fn alloc(&mut self, elements: usize) {
+        self.observer.on_alloc(elements);
+    }
+
+    /// The first `len` elements were partitioned around the pivot now at `pivot`
+    pub fn partitioned(&mut self, pivot: usize, len: usize) {
+        self.observer
+            .on_partition(self.offset, self.offset + pivot, self.offset + len);
+    }
+
+    /// The runs `..mid` and `mid..len` are about to be merged
+    pub fn merging(&mut self, mid: usize, len: usize) {
+        self.observer
+            .on_merge(self.offset, self.offset + mid, self.offset + len);
+    }
+}
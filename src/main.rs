@@ -1,11 +1,15 @@
 mod algoritms;
 mod data_structures;
+mod instrument;
 
 use std::fs;
+use std::io::{self, Write};
 use std::time::{Duration, Instant};
 use std::{cell::RefCell, collections::VecDeque, fs::read_dir, rc::Rc};
+use std::{env, process};
 
 use crate::algoritms::compression::huffman::decode;
+use crate::algoritms::diff::{self, Granularity, TextDiff};
 use crate::data_structures::avl_bst::Avl;
 use crate::data_structures::b_tree::{BTree, BTreeNode};
 use crate::data_structures::bst::Bst;
@@ -22,6 +26,17 @@ use crate::{
 };
 
 fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.first().is_some_and(|command| command == "diff") {
+        match diff_command(&args[1..]) {
+            Ok(changed) => process::exit(i32::from(changed)),
+            Err(message) => {
+                eprintln!("{message}");
+                process::exit(2);
+            }
+        }
+    }
+
     let mut tree = BTree::new(5);
 
     //just a test
@@ -37,3 +52,40 @@ fn main() {
     println!("Search for 10: {:?}", tree.search(&10));
     println!("Search for 99: {:?}", tree.search(&99));
 }
+
+/// `diff [--words] [--patience] [-U <context>] <old> <new>`: prints a unified diff of two
+/// files and returns whether they differ, exit codes as with `diff`
+fn diff_command(args: &[String]) -> Result<bool, String> {
+    const USAGE: &str = "usage: diff [--words] [--patience] [-U <context>] <old> <new>";
+    let mut granularity = Granularity::Lines;
+    let mut algorithm = diff::Algorithm::Myers;
+    let mut context = 3;
+    let mut paths = Vec::new();
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--words" => granularity = Granularity::Words,
+            "--patience" => algorithm = diff::Algorithm::Patience,
+            "-U" => context = args.next().and_then(|n| n.parse().ok()).ok_or(USAGE)?,
+            _ => paths.push(arg.as_str()),
+        }
+    }
+    let [old_path, new_path] = paths[..] else {
+        return Err(USAGE.to_string());
+    };
+
+    let read = |path: &str| fs::read_to_string(path).map_err(|error| format!("{path}: {error}"));
+    let (old, new) = (read(old_path)?, read(new_path)?);
+    let diff = TextDiff::new(&old, &new, granularity, algorithm);
+    let unified = diff.unified(old_path, new_path, context);
+    let mut stdout = io::stdout().lock();
+    match stdout
+        .write_all(unified.as_bytes())
+        .and_then(|()| stdout.flush())
+    {
+        // the reader stopped early (`| head`), the exit code still says what was found
+        Err(error) if error.kind() != io::ErrorKind::BrokenPipe => Err(format!("stdout: {error}")),
+        _ => Ok(diff.has_changes()),
+    }
+}